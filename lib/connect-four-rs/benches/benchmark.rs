@@ -0,0 +1,30 @@
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use std::time::Duration;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("minimax empty depth 8", |b| {
+        b.iter(|| connect_four::minimax(black_box(connect_four::Board::new()), black_box(8)))
+    });
+    c.bench_function("has won", |b| {
+        let board = connect_four::Board::new()
+            .play(3)
+            .play(3)
+            .play(2)
+            .play(4)
+            .play(4)
+            .play(2);
+        b.iter(|| black_box(board).has_won(black_box(connect_four::Team::Red)))
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(10));
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
@@ -0,0 +1,431 @@
+use crate::{
+    Team,
+    HEIGHT,
+    WIDTH,
+};
+
+// The bitboard is column-major, starting at the bottom left.
+// Each column has an extra empty bit on top,
+// so lines cannot wrap from the top of one column into the bottom of the next.
+//
+// 6 13 20 27 34 41 48
+// 5 12 19 26 33 40 47
+// 4 11 18 25 32 39 46
+// 3 10 17 24 31 38 45
+// 2  9 16 23 30 37 44
+// 1  8 15 22 29 36 43
+// 0  7 14 21 28 35 42
+
+/// The # of bits used by a column
+const COLUMN_BITS: u8 = HEIGHT + 1;
+
+/// The bottom tile of every column
+const BOTTOM_MASK: u64 = bottom_mask();
+
+/// Every tile of the board
+const BOARD_MASK: u64 = BOTTOM_MASK * ((1 << HEIGHT) - 1);
+
+const fn bottom_mask() -> u64 {
+    let mut mask = 0;
+    let mut column = 0;
+    while column < WIDTH {
+        mask |= 1 << (column * COLUMN_BITS);
+        column += 1;
+    }
+    mask
+}
+
+/// Get the mask of every tile in a column
+fn column_mask(column: u8) -> u64 {
+    ((1 << HEIGHT) - 1) << (column * COLUMN_BITS)
+}
+
+/// Get the mask of the top tile in a column
+fn top_mask(column: u8) -> u64 {
+    1 << (HEIGHT - 1 + column * COLUMN_BITS)
+}
+
+/// Get the mask of a tile
+fn tile_mask(column: u8, row: u8) -> u64 {
+    1 << (column * COLUMN_BITS + row)
+}
+
+/// The win type
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WinType {
+    Horizontal,
+    Vertical,
+    /// Going up to the right
+    Diagonal,
+    /// Going down to the right
+    AntiDiagonal,
+}
+
+impl WinType {
+    /// Get the distance between 2 tiles of this line in the bitboard
+    fn shift(self) -> u8 {
+        match self {
+            Self::Horizontal => COLUMN_BITS,
+            Self::Vertical => 1,
+            Self::Diagonal => COLUMN_BITS + 1,
+            Self::AntiDiagonal => COLUMN_BITS - 1,
+        }
+    }
+}
+
+/// Winner Info
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct WinnerInfo {
+    /// The winning team
+    pub team: Team,
+
+    /// The tiles that are part of the win, as (column, row) pairs.
+    ///
+    /// Sorted from left to right, then bottom to top.
+    pub tiles: [(u8, u8); 4],
+
+    /// The win type
+    pub win_type: WinType,
+}
+
+impl WinnerInfo {
+    /// Get the first tile
+    pub fn start_tile(&self) -> (u8, u8) {
+        self.tiles[0]
+    }
+
+    /// Get the last tile
+    pub fn end_tile(&self) -> (u8, u8) {
+        self.tiles[3]
+    }
+}
+
+/// A Connect Four board
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    // the bitboard
+    // 7 columns of 7 bits, so it cannot fit in a u32 but can fit in a u64
+    red_state: u64,
+    yellow_state: u64,
+}
+
+impl Board {
+    /// Make a new [`Board`].
+    pub fn new() -> Self {
+        Board {
+            red_state: 0,
+            yellow_state: 0,
+        }
+    }
+
+    /// Get the mask of filled tiles
+    fn mask(self) -> u64 {
+        self.red_state | self.yellow_state
+    }
+
+    /// Get the state of a team
+    fn state(self, team: Team) -> u64 {
+        match team {
+            Team::Red => self.red_state,
+            Team::Yellow => self.yellow_state,
+        }
+    }
+
+    /// Get the # of moves made
+    pub fn num_moves(self) -> u8 {
+        // There are only 42 tiles
+        self.mask().count_ones() as u8
+    }
+
+    /// Get the team whos turn it is.
+    pub fn get_turn(self) -> Team {
+        if self.red_state.count_ones() > self.yellow_state.count_ones() {
+            Team::Yellow
+        } else {
+            Team::Red
+        }
+    }
+
+    /// Returns true if it is a draw.
+    ///
+    /// This does not check for wins.
+    pub fn is_draw(self) -> bool {
+        self.mask() == BOARD_MASK
+    }
+
+    /// Check if the given team won.
+    ///
+    /// This is designed to be fast.
+    pub fn has_won(self, team: Team) -> bool {
+        let state = self.state(team);
+
+        [
+            WinType::Horizontal,
+            WinType::Vertical,
+            WinType::Diagonal,
+            WinType::AntiDiagonal,
+        ]
+        .into_iter()
+        .any(|win_type| {
+            let shift = win_type.shift();
+            let pairs = state & (state >> shift);
+            pairs & (pairs >> (2 * shift)) != 0
+        })
+    }
+
+    /// Get the winner if they exist
+    pub fn get_winner(self) -> Option<Team> {
+        if self.has_won(Team::Red) {
+            Some(Team::Red)
+        } else if self.has_won(Team::Yellow) {
+            Some(Team::Yellow)
+        } else {
+            None
+        }
+    }
+
+    /// Get the winner info, if there is a winner
+    ///
+    /// This is slower than [`Self::get_winner`].
+    pub fn get_winner_info(self) -> Option<WinnerInfo> {
+        let team = self.get_winner()?;
+        let state = self.state(team);
+
+        [
+            WinType::Horizontal,
+            WinType::Vertical,
+            WinType::Diagonal,
+            WinType::AntiDiagonal,
+        ]
+        .into_iter()
+        .find_map(|win_type| {
+            let shift = win_type.shift();
+            let pairs = state & (state >> shift);
+            let lines = pairs & (pairs >> (2 * shift));
+            if lines == 0 {
+                return None;
+            }
+
+            // The bitboard has 49 bits
+            let start = lines.trailing_zeros() as u8;
+            let tiles = [0, 1, 2, 3].map(|i| {
+                let index = start + (i * shift);
+                (index / COLUMN_BITS, index % COLUMN_BITS)
+            });
+
+            Some(WinnerInfo {
+                team,
+                tiles,
+                win_type,
+            })
+        })
+    }
+
+    /// Get a bitboard of the empty tiles that would complete a line of 4 for the team.
+    pub(crate) fn get_threats(self, team: Team) -> u64 {
+        let state = self.state(team);
+
+        let mut threats = 0;
+        for shift in [
+            WinType::Horizontal,
+            WinType::Vertical,
+            WinType::Diagonal,
+            WinType::AntiDiagonal,
+        ]
+        .map(WinType::shift)
+        {
+            // 3 in a row, with the gap on either end
+            let pairs = (state << shift) & (state << (2 * shift));
+            threats |= pairs & (state << (3 * shift));
+            threats |= pairs & (state >> shift);
+
+            let pairs = (state >> shift) & (state >> (2 * shift));
+            threats |= pairs & (state >> (3 * shift));
+            threats |= pairs & (state << shift);
+        }
+
+        threats & (BOARD_MASK ^ self.mask())
+    }
+
+    /// Get the # of pieces in a column.
+    ///
+    /// # Panics
+    /// Panics if the column >= 7.
+    pub fn get_column_height(self, column: u8) -> u8 {
+        assert!(column < WIDTH);
+        // There are only 6 rows
+        (self.mask() & column_mask(column)).count_ones() as u8
+    }
+
+    /// Returns true if a piece can be dropped into the column.
+    ///
+    /// # Panics
+    /// Panics if the column >= 7.
+    pub fn can_play(self, column: u8) -> bool {
+        assert!(column < WIDTH);
+        self.mask() & top_mask(column) == 0
+    }
+
+    /// Drop a piece of the current team into the column.
+    ///
+    /// # Panics
+    /// Panics if the column >= 7 or the column is full.
+    #[must_use]
+    pub fn play(mut self, column: u8) -> Self {
+        assert!(self.can_play(column), "column {column} is full");
+
+        let tile = tile_mask(column, self.get_column_height(column));
+        match self.get_turn() {
+            Team::Red => self.red_state |= tile,
+            Team::Yellow => self.yellow_state |= tile,
+        }
+        self
+    }
+
+    /// Get the tile at the column and row.
+    ///
+    /// Row 0 is the bottom row.
+    ///
+    /// # Panics
+    /// Panics if the column >= 7 or the row >= 6.
+    pub fn get(self, column: u8, row: u8) -> Option<Team> {
+        assert!(column < WIDTH);
+        assert!(row < HEIGHT);
+
+        let tile = tile_mask(column, row);
+        if self.red_state & tile != 0 {
+            Some(Team::Red)
+        } else if self.yellow_state & tile != 0 {
+            Some(Team::Yellow)
+        } else {
+            None
+        }
+    }
+
+    /// Get an iterator over child board states.
+    ///
+    /// # Returns
+    /// Returns an Iterator where Items are tuples.
+    /// The first item is the column that was played.
+    /// The second is the resulting board state.
+    pub fn iter_children(self) -> ChildrenIter {
+        ChildrenIter::new(self)
+    }
+
+    /// Encode this board as a [`u64`].
+    ///
+    /// Each column stores the red pieces, with a 1 above the top piece.
+    /// This only uses the lower 49 bits.
+    pub fn encode_u64(self) -> u64 {
+        // Each column of the mask is filled from the bottom,
+        // so adding 1 to each column sets the bit above the top piece without carrying.
+        self.red_state + self.mask() + BOTTOM_MASK
+    }
+
+    /// Decode a [`u64`] into a board
+    pub fn decode_u64(data: u64) -> Self {
+        let mut ret = Self::new();
+        for column in 0..WIDTH {
+            let column_data = (data >> (column * COLUMN_BITS)) & ((1 << COLUMN_BITS) - 1);
+            let height = column_data.checked_ilog2().unwrap_or(0);
+            let pieces = ((1 << height) - 1) << (column * COLUMN_BITS);
+            let column_data = column_data << (column * COLUMN_BITS);
+
+            ret.red_state |= column_data & pieces;
+            ret.yellow_state |= !column_data & pieces;
+        }
+        ret
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct ChildrenIter {
+    board: Board,
+    column: u8,
+}
+
+impl ChildrenIter {
+    fn new(board: Board) -> Self {
+        Self { board, column: 0 }
+    }
+}
+
+impl Iterator for ChildrenIter {
+    type Item = (u8, Board);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.board.get_winner().is_some() {
+            return None;
+        }
+
+        while self.column < WIDTH {
+            let column = self.column;
+            self.column += 1;
+
+            if self.board.can_play(column) {
+                return Some((column, self.board.play(column)));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(usize::from(WIDTH - self.column.min(WIDTH))))
+    }
+}
+
+impl std::iter::FusedIterator for ChildrenIter {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let board = Board::new().play(3).play(3).play(4).play(2).play(6);
+        assert_eq!(Board::decode_u64(board.encode_u64()), board);
+        assert_eq!(Board::decode_u64(Board::new().encode_u64()), Board::new());
+    }
+
+    #[test]
+    fn winner_info() {
+        // Red plays 0 to 3 on the bottom row, yellow stacks on top
+        let board = Board::new()
+            .play(0)
+            .play(0)
+            .play(1)
+            .play(1)
+            .play(2)
+            .play(2)
+            .play(3);
+        let winner_info = board.get_winner_info().expect("missing winner");
+        assert_eq!(winner_info.team, Team::Red);
+        assert_eq!(winner_info.win_type, WinType::Horizontal);
+        assert_eq!(winner_info.tiles, [(0, 0), (1, 0), (2, 0), (3, 0)]);
+
+        // Red climbs a staircase
+        let board = Board::new()
+            .play(0)
+            .play(1)
+            .play(1)
+            .play(2)
+            .play(2)
+            .play(3)
+            .play(2)
+            .play(3)
+            .play(3)
+            .play(6)
+            .play(3);
+        let winner_info = board.get_winner_info().expect("missing winner");
+        assert_eq!(winner_info.team, Team::Red);
+        assert_eq!(winner_info.win_type, WinType::Diagonal);
+        assert_eq!(winner_info.tiles, [(0, 0), (1, 1), (2, 2), (3, 3)]);
+    }
+}
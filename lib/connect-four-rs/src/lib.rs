@@ -0,0 +1,63 @@
+pub mod board;
+pub mod search;
+pub mod team;
+
+pub use self::{
+    board::{
+        Board,
+        WinType,
+        WinnerInfo,
+    },
+    search::{
+        alpha_beta,
+        TranspositionTable,
+    },
+    team::Team,
+};
+
+/// The # of Connect Four columns
+pub const WIDTH: u8 = 7;
+
+/// The # of Connect Four rows
+pub const HEIGHT: u8 = 6;
+
+/// The # of Connect Four tiles
+pub const NUM_TILES: u8 = WIDTH * HEIGHT;
+
+/// Run an alpha-beta search on a board with a new [`TranspositionTable`].
+///
+/// This is negamax. The returned score is relative to the current player.
+///
+/// # Returns
+/// Returns a tuple. The first element is the score. The second is the column to play.
+pub fn minimax(board: Board, depth: u8) -> (i16, u8) {
+    alpha_beta(board, depth, &mut TranspositionTable::default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn minimax_empty() {
+        let (_score, column) = minimax(Board::new(), 8);
+        assert_eq!(column, 3);
+    }
+
+    #[test]
+    fn minimax_win() {
+        // Red has 3 in a row on the bottom
+        let board = Board::new().play(1).play(1).play(2).play(2).play(3).play(3);
+        let (score, column) = minimax(board, 8);
+        assert!(score > search::WIN_SCORE, "expected red win");
+        assert!(column == 0 || column == 4);
+    }
+
+    #[test]
+    fn minimax_block() {
+        // Red has 3 in a column, so yellow must block
+        let board = Board::new().play(0).play(6).play(0).play(6).play(0);
+        let (_score, column) = minimax(board, 8);
+        assert_eq!(column, 0);
+    }
+}
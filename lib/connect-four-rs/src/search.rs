@@ -0,0 +1,192 @@
+use crate::{
+    Board,
+    WIDTH,
+};
+
+/// The score of a win.
+///
+/// Wins that take fewer moves score higher.
+pub const WIN_SCORE: i16 = 1000;
+
+/// The order to search columns in.
+///
+/// Center columns are part of more lines, so they are usually better moves.
+const COLUMN_ORDER: [u8; WIDTH as usize] = [3, 2, 4, 1, 5, 0, 6];
+
+/// How a stored score relates to the real score
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Bound {
+    /// The score is exact
+    Exact,
+
+    /// The real score is at least this score
+    Lower,
+
+    /// The real score is at most this score
+    Upper,
+}
+
+/// A searched position
+#[derive(Debug, Copy, Clone)]
+struct Entry {
+    /// The encoded board
+    key: u64,
+    score: i16,
+    depth: u8,
+    bound: Bound,
+    best_move: u8,
+}
+
+/// A table of searched positions.
+///
+/// This is a fixed size hash table, where new entries replace old ones.
+/// It can be reused between searches of the same game.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+}
+
+impl TranspositionTable {
+    /// The default # of entries
+    pub const DEFAULT_CAPACITY: usize = 1 << 16;
+
+    /// Make a new [`TranspositionTable`].
+    ///
+    /// The capacity is rounded up to a power of 2.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: vec![None; capacity.max(1).next_power_of_two()],
+        }
+    }
+
+    /// Remove all entries
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    fn get_index(&self, key: u64) -> usize {
+        // Only the lower bits are used, and the table size is at most usize::MAX.
+        (key as usize) & (self.entries.len() - 1)
+    }
+
+    fn get(&self, key: u64) -> Option<Entry> {
+        self.entries[self.get_index(key)].filter(|entry| entry.key == key)
+    }
+
+    fn insert(&mut self, entry: Entry) {
+        let index = self.get_index(entry.key);
+        self.entries[index] = Some(entry);
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+/// Score a board that was not searched to the end, relative to the current player.
+///
+/// This is the difference between the # of tiles that would complete a line for each team.
+fn evaluate(board: Board) -> i16 {
+    let turn = board.get_turn();
+    // There are only 42 tiles
+    let threats = board.get_threats(turn).count_ones() as i16;
+    let opponent_threats = board.get_threats(turn.inverse()).count_ones() as i16;
+
+    threats - opponent_threats
+}
+
+/// Run an alpha-beta search on a board, storing positions in the table.
+///
+/// This is negamax. The returned score is relative to the current player.
+/// Scores above [`WIN_SCORE`] are wins, and scores below -[`WIN_SCORE`] are losses.
+///
+/// # Returns
+/// Returns a tuple. The first element is the score. The second is the column to play.
+pub fn alpha_beta(board: Board, depth: u8, table: &mut TranspositionTable) -> (i16, u8) {
+    alpha_beta_impl(board, depth, -i16::MAX, i16::MAX, table)
+}
+
+fn alpha_beta_impl(
+    board: Board,
+    depth: u8,
+    mut alpha: i16,
+    mut beta: i16,
+    table: &mut TranspositionTable,
+) -> (i16, u8) {
+    // Only the last player can have won
+    if board.get_winner().is_some() {
+        return (-(WIN_SCORE + i16::from(depth)), 0);
+    }
+
+    if board.is_draw() {
+        return (0, 0);
+    }
+
+    if depth == 0 {
+        return (evaluate(board), 0);
+    }
+
+    let key = board.encode_u64();
+    let entry = table.get(key);
+    if let Some(entry) = entry.filter(|entry| entry.depth >= depth) {
+        match entry.bound {
+            Bound::Exact => return (entry.score, entry.best_move),
+            Bound::Lower => alpha = alpha.max(entry.score),
+            Bound::Upper => beta = beta.min(entry.score),
+        }
+
+        if alpha >= beta {
+            return (entry.score, entry.best_move);
+        }
+    }
+
+    // Try the best move from the last search first
+    let first_move = entry.map(|entry| entry.best_move);
+    let columns = first_move.into_iter().chain(
+        COLUMN_ORDER
+            .into_iter()
+            .filter(|column| Some(*column) != first_move),
+    );
+
+    let original_alpha = alpha;
+    let mut value = i16::MIN;
+    let mut best_move = 0;
+    for column in columns {
+        if !board.can_play(column) {
+            continue;
+        }
+
+        let (new_value, _column) =
+            alpha_beta_impl(board.play(column), depth - 1, -beta, -alpha, table);
+        let new_value = -new_value;
+
+        if new_value > value {
+            value = new_value;
+            best_move = column;
+        }
+
+        alpha = alpha.max(value);
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    let bound = if value <= original_alpha {
+        Bound::Upper
+    } else if value >= beta {
+        Bound::Lower
+    } else {
+        Bound::Exact
+    };
+    table.insert(Entry {
+        key,
+        score: value,
+        depth,
+        bound,
+        best_move,
+    });
+
+    (value, best_move)
+}
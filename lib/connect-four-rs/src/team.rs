@@ -0,0 +1,57 @@
+/// Failed to parse a [`Team`] from a [`str`].
+#[derive(Debug, Clone)]
+pub struct InvalidTeamError(pub String);
+
+impl std::fmt::Display for InvalidTeamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a valid Connect Four team", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTeamError {}
+
+/// A Connect Four Team
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Team {
+    Red,
+    Yellow,
+}
+
+impl Team {
+    /// Invert the teams
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Red => Self::Yellow,
+            Self::Yellow => Self::Red,
+        }
+    }
+
+    /// Get the name of this team, like "red"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Red => "red",
+            Self::Yellow => "yellow",
+        }
+    }
+}
+
+impl std::str::FromStr for Team {
+    type Err = InvalidTeamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("red") || s.eq_ignore_ascii_case("r") {
+            Ok(Self::Red)
+        } else if s.eq_ignore_ascii_case("yellow") || s.eq_ignore_ascii_case("y") {
+            Ok(Self::Yellow)
+        } else {
+            Err(InvalidTeamError(s.to_string()))
+        }
+    }
+}
+
+impl std::fmt::Display for Team {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
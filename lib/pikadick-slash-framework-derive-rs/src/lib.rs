@@ -6,14 +6,18 @@ use quote::{
     quote_spanned,
 };
 use syn::{
+    parse::Parse,
     parse_macro_input,
+    punctuated::Punctuated,
     spanned::Spanned,
     Data,
     DeriveInput,
     Error,
     Fields,
+    Lit,
     LitStr,
     Result,
+    Token,
 };
 
 #[proc_macro_derive(FromOptions, attributes(pikadick_slash_framework))]
@@ -48,6 +52,416 @@ pub fn derive_from_options(input: proc_macro::TokenStream) -> proc_macro::TokenS
     }
 }
 
+#[proc_macro_derive(FromOptionValue, attributes(pikadick_slash_framework))]
+pub fn derive_from_option_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = input.ident;
+    match extract_variants(&input.data) {
+        Ok(variants) => {
+            let match_recurse = variants.iter().map(|variant| {
+                let ident = variant.ident;
+                let value = variant.get_value_literal();
+                quote_spanned! {variant.span=>
+                    #value => Ok(Self::#ident),
+                }
+            });
+
+            let choices_recurse = variants.iter().map(|variant| {
+                let value = variant.get_value_literal();
+                let name = variant.get_name_literal();
+                quote_spanned! {variant.span=>
+                    ::pikadick_slash_framework::ArgumentChoice::new(#name, #value),
+                }
+            });
+
+            let expanded = quote! {
+                impl ::pikadick_slash_framework::FromOptionValue for #name {
+                    fn from_option_value(
+                        name: &'static str,
+                        option: &::serenity::model::application::CommandDataOptionValue,
+                        resolved: &::serenity::model::application::CommandDataResolved,
+                    ) -> ::std::result::Result<Self, ::pikadick_slash_framework::ConvertError> {
+                        let value = <::std::string::String as ::pikadick_slash_framework::FromOptionValue>::from_option_value(
+                            name,
+                            option,
+                            resolved,
+                        )?;
+
+                        match value.as_str() {
+                            #(#match_recurse)*
+                            _ => Err(::pikadick_slash_framework::ConvertError::UnknownChoice { name, value }),
+                        }
+                    }
+
+                    fn get_expected_data_type() -> ::pikadick_slash_framework::DataType {
+                        ::pikadick_slash_framework::DataType::String
+                    }
+
+                    fn get_choices() -> ::std::vec::Vec<::pikadick_slash_framework::ArgumentChoice> {
+                        ::std::vec![#(#choices_recurse)*]
+                    }
+                }
+            };
+
+            proc_macro::TokenStream::from(expanded)
+        }
+        Err(e) => Error::into_compile_error(e).into(),
+    }
+}
+
+/// Extract Variants from a derive object
+fn extract_variants(data: &syn::Data) -> Result<Vec<Variant<'_>>> {
+    let data = match data {
+        Data::Enum(data) => data,
+        Data::Struct(data) => {
+            return Err(Error::new(
+                data.struct_token.span(),
+                "structs are not supported",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "unions are not supported",
+            ))
+        }
+    };
+
+    data.variants
+        .iter()
+        .map(|variant| {
+            // Only use field-less variants
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new(
+                    variant.fields.span(),
+                    "variants with fields are not supported",
+                ));
+            }
+
+            let mut maybe_rename = None;
+            let mut maybe_name = None;
+
+            for attr in variant
+                .attrs
+                .iter()
+                .filter(|attr| matches!(attr.style, syn::AttrStyle::Outer))
+            {
+                if attr.path().is_ident("pikadick_slash_framework") {
+                    attr.parse_nested_meta(|meta| {
+                        for (attr_name, maybe_value) in
+                            [("rename", &mut maybe_rename), ("name", &mut maybe_name)]
+                        {
+                            if meta.path.is_ident(attr_name) {
+                                let value = meta.value()?;
+                                let value: syn::LitStr = value.parse()?;
+
+                                if maybe_value.is_some() {
+                                    return Err(Error::new(
+                                        attr.span(),
+                                        format!("duplicate {} attribute", attr_name),
+                                    ));
+                                }
+
+                                *maybe_value = Some(value);
+
+                                return Ok(());
+                            }
+                        }
+
+                        Err(Error::new(variant.ident.span(), "unexpected ident"))
+                    })?;
+                }
+            }
+
+            Ok(Variant {
+                ident: &variant.ident,
+                span: variant.span(),
+
+                rename: maybe_rename,
+                name: maybe_name,
+            })
+        })
+        .collect()
+}
+
+#[proc_macro_derive(FromModal, attributes(pikadick_slash_framework))]
+pub fn derive_from_modal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = input.ident;
+    match extract_modal_fields(&input.data) {
+        Ok(fields) => {
+            let from_modal_impl = gen_from_modal_impl(&fields);
+            let create_components_impl = gen_create_components_impl(&fields);
+
+            let expanded = quote! {
+                impl ::pikadick_slash_framework::FromModal for #name {
+                    fn from_modal(
+                        interaction: &::serenity::model::application::ModalInteraction
+                    ) -> ::std::result::Result<Self, ::pikadick_slash_framework::ConvertError> {
+                        #from_modal_impl
+                    }
+
+                    fn create_components() -> ::std::vec::Vec<::serenity::builder::CreateActionRow> {
+                        #create_components_impl
+                    }
+                }
+            };
+
+            proc_macro::TokenStream::from(expanded)
+        }
+        Err(e) => Error::into_compile_error(e).into(),
+    }
+}
+
+/// Extract ModalFields from a derive object
+fn extract_modal_fields(data: &syn::Data) -> Result<Vec<ModalField<'_>>> {
+    let fields = match data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(Error::new(
+                data.enum_token.span(),
+                "enums are not supported",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "unions are not supported",
+            ))
+        }
+    };
+
+    // Only use named fields
+    let fields = match fields {
+        Fields::Named(fields) => fields,
+        Fields::Unnamed(fields) => {
+            return Err(Error::new(
+                fields.span(),
+                "unnamed fields are not supported",
+            ))
+        }
+        Fields::Unit => return Err(Error::new(fields.span(), "unit structs are not supported")),
+    };
+
+    // Keep in sync with `pikadick_slash_framework::MAX_MODAL_INPUTS`
+    if fields.named.len() > 5 {
+        return Err(Error::new(
+            fields.span(),
+            "modals cannot have more than 5 fields",
+        ));
+    }
+
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field
+                .ident
+                .as_ref()
+                .expect("named struct fields should have names for all fields");
+
+            let mut maybe_rename = None;
+            let mut maybe_label = None;
+            let mut maybe_placeholder = None;
+            let mut paragraph = false;
+            let mut maybe_min_length = None;
+            let mut maybe_max_length = None;
+
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|field| matches!(field.style, syn::AttrStyle::Outer))
+            {
+                if attr.path().is_ident("pikadick_slash_framework") {
+                    attr.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("paragraph") {
+                            paragraph = true;
+                            return Ok(());
+                        }
+
+                        for (attr_name, maybe_value) in [
+                            ("rename", &mut maybe_rename),
+                            ("label", &mut maybe_label),
+                            ("placeholder", &mut maybe_placeholder),
+                        ] {
+                            if meta.path.is_ident(attr_name) {
+                                let value = meta.value()?;
+                                let value: syn::LitStr = value.parse()?;
+
+                                if maybe_value.is_some() {
+                                    return Err(Error::new(
+                                        attr.span(),
+                                        format!("duplicate {} attribute", attr_name),
+                                    ));
+                                }
+
+                                *maybe_value = Some(value);
+
+                                return Ok(());
+                            }
+                        }
+
+                        for (attr_name, maybe_value) in [
+                            ("min_length", &mut maybe_min_length),
+                            ("max_length", &mut maybe_max_length),
+                        ] {
+                            if meta.path.is_ident(attr_name) {
+                                let value = meta.value()?;
+                                let value: syn::Expr = value.parse()?;
+
+                                if maybe_value.is_some() {
+                                    return Err(Error::new(
+                                        attr.span(),
+                                        format!("duplicate {} attribute", attr_name),
+                                    ));
+                                }
+
+                                *maybe_value = Some(value);
+
+                                return Ok(());
+                            }
+                        }
+
+                        Err(Error::new(ident.span(), "unexpected ident"))
+                    })?;
+                }
+            }
+
+            Ok(ModalField {
+                ident,
+                span: field.span(),
+                ty: &field.ty,
+
+                rename: maybe_rename,
+                label: maybe_label,
+                placeholder: maybe_placeholder,
+                paragraph,
+                min_length: maybe_min_length,
+                max_length: maybe_max_length,
+            })
+        })
+        .collect()
+}
+
+fn gen_from_modal_impl(fields: &[ModalField]) -> TokenStream {
+    let optional_field_recurse = fields.iter().map(|field| {
+        let name = &field.ident;
+        quote_spanned! {field.span=>
+            let mut #name = ::std::option::Option::None;
+        }
+    });
+
+    let match_recurse = fields.iter().map(|field| {
+        let name = &field.ident;
+        let name_lit = field.get_name_literal();
+        let ty = &field.ty;
+        quote_spanned! {field.span=>
+            #name_lit => {
+                #name = Some(
+                    <#ty as ::pikadick_slash_framework::FromModalValue>::from_modal_value(value)
+                );
+            }
+        }
+    });
+
+    let unwrap_field_recurse = fields.iter().map(|field| {
+        let name = &field.ident;
+        let name_lit = field.get_name_literal();
+        let ty = &field.ty;
+        quote_spanned! {field.span=>
+            let #name = #name
+                .or_else(<#ty as ::pikadick_slash_framework::FromModalValue>::get_missing_default)
+                .ok_or(::pikadick_slash_framework::ConvertError::MissingRequiredField {
+                    name: #name_lit,
+                    expected: ::pikadick_slash_framework::DataType::String,
+                })?;
+        }
+    });
+
+    let recurse = fields.iter().map(|field| {
+        let name = &field.ident;
+        quote_spanned! {field.span=>
+            #name,
+        }
+    });
+
+    quote! {
+        #(#optional_field_recurse)*
+
+        let inputs = interaction
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter());
+        for input in inputs {
+            let input = match input {
+                ::serenity::model::application::ActionRowComponent::InputText(input) => input,
+                _ => continue,
+            };
+
+            // Empty inputs are treated as missing
+            let value = match input.value.as_deref() {
+                Some(value) if !value.is_empty() => value,
+                _ => continue,
+            };
+
+            match input.custom_id.as_str() {
+                #(#match_recurse)*
+                _ => {}
+            }
+        }
+
+        #(#unwrap_field_recurse)*
+
+        Ok(Self { #(#recurse)* })
+    }
+}
+
+fn gen_create_components_impl(fields: &[ModalField]) -> TokenStream {
+    let fields_len = fields.len();
+
+    let inputs = fields.iter().map(|field| {
+        let name_lit = field.get_name_literal();
+        let label = field.get_label();
+        let ty = &field.ty;
+        let style = if field.paragraph {
+            quote! { ::serenity::model::application::InputTextStyle::Paragraph }
+        } else {
+            quote! { ::serenity::model::application::InputTextStyle::Short }
+        };
+        let placeholder = field
+            .placeholder
+            .iter()
+            .map(|placeholder| quote! { .placeholder(#placeholder) });
+        let min_length = field
+            .min_length
+            .iter()
+            .map(|min_length| quote! { .min_length(#min_length) });
+        let max_length = field
+            .max_length
+            .iter()
+            .map(|max_length| quote! { .max_length(#max_length) });
+
+        quote_spanned! {field.span=>
+            ret.push(::serenity::builder::CreateActionRow::InputText(
+                ::serenity::builder::CreateInputText::new(#style, #label, #name_lit)
+                    .required(<#ty as ::pikadick_slash_framework::FromModalValue>::get_missing_default().is_none())
+                    #(#placeholder)*
+                    #(#min_length)*
+                    #(#max_length)*
+            ));
+        }
+    });
+
+    quote! {
+        let mut ret = ::std::vec::Vec::with_capacity(#fields_len);
+        #(#inputs)*
+        ret
+    }
+}
+
 /// Extract Field s from a derive object
 fn extract_fields(data: &syn::Data) -> Result<Vec<Field<'_>>> {
     let fields = match data {
@@ -89,6 +503,12 @@ fn extract_fields(data: &syn::Data) -> Result<Vec<Field<'_>>> {
 
             let mut maybe_rename = None;
             let mut maybe_description = None;
+            let mut choices = Vec::new();
+            let mut maybe_min = None;
+            let mut maybe_max = None;
+            let mut maybe_min_length = None;
+            let mut maybe_max_length = None;
+            let mut channel_types = Vec::new();
 
             for attr in field
                 .attrs
@@ -134,6 +554,62 @@ fn extract_fields(data: &syn::Data) -> Result<Vec<Field<'_>>> {
                             return Ok(());
                         }
 
+                        if meta.path.is_ident("choices") {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let values: Punctuated<Lit, Token![,]> =
+                                content.parse_terminated(Lit::parse, Token![,])?;
+
+                            for value in values {
+                                match value {
+                                    Lit::Str(_) | Lit::Int(_) | Lit::Float(_) => {
+                                        choices.push(value);
+                                    }
+                                    value => {
+                                        return Err(Error::new(
+                                            value.span(),
+                                            "choices must be strings, integers, or floats",
+                                        ));
+                                    }
+                                }
+                            }
+
+                            return Ok(());
+                        }
+
+                        if meta.path.is_ident("channel_types") {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let values: Punctuated<syn::Ident, Token![,]> =
+                                content.parse_terminated(syn::Ident::parse, Token![,])?;
+                            channel_types.extend(values);
+
+                            return Ok(());
+                        }
+
+                        for (attr_name, maybe_value) in [
+                            ("min", &mut maybe_min),
+                            ("max", &mut maybe_max),
+                            ("min_length", &mut maybe_min_length),
+                            ("max_length", &mut maybe_max_length),
+                        ] {
+                            if meta.path.is_ident(attr_name) {
+                                let value = meta.value()?;
+                                let value: syn::Expr = value.parse()?;
+
+                                if maybe_value.is_some() {
+                                    return Err(Error::new(
+                                        attr.span(),
+                                        format!("duplicate {} attribute", attr_name),
+                                    ));
+                                }
+
+                                *maybe_value = Some(value);
+
+                                return Ok(());
+                            }
+                        }
+
                         Err(Error::new(ident.span(), "unexpected ident"))
                     })?;
                 }
@@ -146,6 +622,12 @@ fn extract_fields(data: &syn::Data) -> Result<Vec<Field<'_>>> {
 
                 rename: maybe_rename,
                 description: maybe_description,
+                choices,
+                min: maybe_min,
+                max: maybe_max,
+                min_length: maybe_min_length,
+                max_length: maybe_max_length,
+                channel_types,
             })
         })
         .collect::<Result<Vec<Field>>>()
@@ -168,7 +650,8 @@ fn gen_from_options_impl(fields: &[Field]) -> Result<TokenStream> {
                 #name = Some(
                     <#ty as ::pikadick_slash_framework::FromOptionValue>::from_option_value(
                         #name_lit,
-                        &option.value
+                        &option.value,
+                        &interaction.data.resolved,
                     )?
                 );
             }
@@ -199,7 +682,7 @@ fn gen_from_options_impl(fields: &[Field]) -> Result<TokenStream> {
     Ok(quote! {
         #(#optional_field_recurse)*
 
-        for option in interaction.data.options.iter() {
+        for option in ::pikadick_slash_framework::leaf_options(&interaction.data.options).iter() {
             match option.name.as_str() {
                 #(#match_recurse)*
                 _ => {}
@@ -219,12 +702,46 @@ fn gen_get_argument_params_impl(fields: &[Field]) -> Result<TokenStream> {
         let name_lit = field.get_name_literal();
         let description = field.get_description();
         let ty = &field.ty;
+
+        let choices = field.choices.iter().map(|choice| match choice {
+            Lit::Str(value) => quote! { .choice(#value, #value) },
+            Lit::Int(value) => {
+                let name = LitStr::new(value.base10_digits(), value.span());
+                quote! { .choice(#name, ::pikadick_slash_framework::ChoiceValue::Integer(#value)) }
+            }
+            Lit::Float(value) => {
+                let name = LitStr::new(value.base10_digits(), value.span());
+                quote! { .choice(#name, ::pikadick_slash_framework::ChoiceValue::Number(#value)) }
+            }
+            _ => unreachable!("choices are validated while parsing"),
+        });
+        let min = field.min.iter().map(|min| quote! { .min_value((#min) as f64) });
+        let max = field.max.iter().map(|max| quote! { .max_value((#max) as f64) });
+        let min_length = field
+            .min_length
+            .iter()
+            .map(|min_length| quote! { .min_length(#min_length) });
+        let max_length = field
+            .max_length
+            .iter()
+            .map(|max_length| quote! { .max_length(#max_length) });
+        let channel_types = field.channel_types.iter().map(|channel_type| {
+            quote! { .channel_type(::serenity::model::channel::ChannelType::#channel_type) }
+        });
+
         quote_spanned! {field.span=>
             ret.push(
                 ::pikadick_slash_framework::ArgumentParamBuilder::new()
                     .name(#name_lit)
                     .description(#description)
                     .kind(<#ty as ::pikadick_slash_framework::FromOptionValue>::get_expected_data_type())
+                    .choices(<#ty as ::pikadick_slash_framework::FromOptionValue>::get_choices())
+                    #(#choices)*
+                    #(#min)*
+                    #(#max)*
+                    #(#min_length)*
+                    #(#max_length)*
+                    #(#channel_types)*
                     .build()?
             );
         }
@@ -249,6 +766,24 @@ struct Field<'a> {
     ///
     /// This is different from documentation.
     description: Option<(String, proc_macro2::Span)>,
+
+    /// Fixed choices
+    choices: Vec<Lit>,
+
+    /// The min value
+    min: Option<syn::Expr>,
+
+    /// The max value
+    max: Option<syn::Expr>,
+
+    /// The min length
+    min_length: Option<syn::Expr>,
+
+    /// The max length
+    max_length: Option<syn::Expr>,
+
+    /// The allowed channel types
+    channel_types: Vec<syn::Ident>,
 }
 
 impl Field<'_> {
@@ -281,3 +816,82 @@ impl Field<'_> {
         }
     }
 }
+
+struct Variant<'a> {
+    ident: &'a proc_macro2::Ident,
+    span: proc_macro2::Span,
+
+    /// The renamed value of this variant
+    rename: Option<LitStr>,
+
+    /// The name of this variant, as shown to the user
+    name: Option<LitStr>,
+}
+
+impl Variant<'_> {
+    /// Get the string literal value of this variant.
+    ///
+    /// This defaults to the lowercase variant name.
+    fn get_value_literal(&self) -> LitStr {
+        match &self.rename {
+            Some(rename) => rename.clone(),
+            None => LitStr::new(&self.ident.to_string().to_lowercase(), self.ident.span()),
+        }
+    }
+
+    /// Get the string literal name of this variant.
+    ///
+    /// This defaults to the variant name.
+    fn get_name_literal(&self) -> LitStr {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => LitStr::new(&self.ident.to_string(), self.ident.span()),
+        }
+    }
+}
+
+struct ModalField<'a> {
+    ident: &'a proc_macro2::Ident,
+    span: proc_macro2::Span,
+    ty: &'a syn::Type,
+
+    /// The renamed custom id of this field
+    rename: Option<LitStr>,
+
+    /// The label shown above the text input
+    label: Option<LitStr>,
+
+    /// The placeholder of the text input
+    placeholder: Option<LitStr>,
+
+    /// Whether the text input is multi-line
+    paragraph: bool,
+
+    /// The min length
+    min_length: Option<syn::Expr>,
+
+    /// The max length
+    max_length: Option<syn::Expr>,
+}
+
+impl ModalField<'_> {
+    /// Get the string literal custom id of this field.
+    ///
+    /// This will take into account field renames
+    fn get_name_literal(&self) -> LitStr {
+        match &self.rename {
+            Some(rename) => rename.clone(),
+            None => LitStr::new(&self.ident.to_string(), self.ident.span()),
+        }
+    }
+
+    /// Get the label of this field.
+    ///
+    /// This defaults to the field name.
+    fn get_label(&self) -> LitStr {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => LitStr::new(&self.ident.to_string(), self.ident.span()),
+        }
+    }
+}
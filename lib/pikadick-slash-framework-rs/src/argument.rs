@@ -1,17 +1,163 @@
 use crate::{
+    BoxError,
+    BoxFuture,
     BuilderError,
     DataType,
+    FromStates,
+    States,
+    Translations,
 };
+use serenity::{
+    builder::{
+        AutocompleteChoice,
+        CreateCommandOption,
+    },
+    client::Context,
+    model::{
+        application::{
+            CommandInteraction,
+            CommandOptionType,
+        },
+        channel::ChannelType,
+    },
+};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    future::Future,
+};
+
+/// The max number of choices an argument may have
+const MAX_CHOICES: usize = 25;
+
+type OnAutocompleteResult = Result<Vec<AutocompleteChoice>, BoxError>;
+pub type OnAutocompleteFuture = BoxFuture<'static, OnAutocompleteResult>;
+
+// Keep these types in sync.
+type OnAutocompleteFutureFn =
+    Box<dyn Fn(Context, CommandInteraction, String, States) -> OnAutocompleteFuture + Send + Sync>;
+type OnAutocompleteFutureFnPtr<F> = fn(Context, CommandInteraction, String) -> F;
+type OnAutocompleteWithStateFutureFnPtr<F, S> = fn(Context, CommandInteraction, String, S) -> F;
+
+/// The value of an [`ArgumentChoice`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChoiceValue {
+    /// A string
+    String(Box<str>),
+
+    /// An integer
+    Integer(i64),
+
+    /// A float
+    Number(f64),
+}
+
+impl ChoiceValue {
+    /// Get the datatype of this value
+    pub fn data_type(&self) -> DataType {
+        match self {
+            Self::String(_) => DataType::String,
+            Self::Integer(_) => DataType::Integer,
+            Self::Number(_) => DataType::Number,
+        }
+    }
+}
+
+impl From<&str> for ChoiceValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for ChoiceValue {
+    fn from(value: String) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<i64> for ChoiceValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for ChoiceValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+/// A fixed choice for an argument
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgumentChoice {
+    name: Box<str>,
+    value: ChoiceValue,
+}
+
+impl ArgumentChoice {
+    /// Make a new [`ArgumentChoice`].
+    ///
+    /// `name` is what the user sees, `value` is what the bot receives.
+    pub fn new(name: &str, value: impl Into<ChoiceValue>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Get the name of the choice
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the value of the choice
+    pub fn value(&self) -> &ChoiceValue {
+        &self.value
+    }
+}
 
 /// An argument.
 ///
 /// Specifically, this is a parameter, not a value.
-#[derive(Debug)]
 pub struct ArgumentParam {
     name: Box<str>,
     kind: DataType,
     description: Box<str>,
     required: bool,
+
+    /// Translated names, by locale
+    name_localizations: HashMap<Box<str>, Box<str>>,
+
+    /// Translated descriptions, by locale
+    description_localizations: HashMap<Box<str>, Box<str>>,
+
+    /// Fixed choices
+    choices: Box<[ArgumentChoice]>,
+
+    /// The min value, for integers and numbers
+    min_value: Option<f64>,
+
+    /// The max value, for integers and numbers
+    max_value: Option<f64>,
+
+    /// The min length, for strings
+    min_length: Option<u16>,
+
+    /// The max length, for strings
+    max_length: Option<u16>,
+
+    /// The allowed channel types, for channels.
+    ///
+    /// If empty, all channel types are allowed.
+    channel_types: Box<[ChannelType]>,
+
+    /// The autocomplete hook.
+    ///
+    /// It receives the partial value the user has typed so far.
+    on_autocomplete: Option<OnAutocompleteFutureFn>,
+
+    /// The type ids and type names of the states the on_autocomplete hook needs
+    required_states: Box<[(TypeId, &'static str)]>,
 }
 
 impl ArgumentParam {
@@ -34,15 +180,179 @@ impl ArgumentParam {
     pub fn required(&self) -> bool {
         self.required
     }
+
+    /// Get the translated names of the argument, by locale
+    pub fn name_localizations(&self) -> &HashMap<Box<str>, Box<str>> {
+        &self.name_localizations
+    }
+
+    /// Get the translated descriptions of the argument, by locale
+    pub fn description_localizations(&self) -> &HashMap<Box<str>, Box<str>> {
+        &self.description_localizations
+    }
+
+    /// Get the fixed choices of the argument
+    pub fn choices(&self) -> &[ArgumentChoice] {
+        &self.choices
+    }
+
+    /// Get the min value of the argument
+    pub fn min_value(&self) -> Option<f64> {
+        self.min_value
+    }
+
+    /// Get the max value of the argument
+    pub fn max_value(&self) -> Option<f64> {
+        self.max_value
+    }
+
+    /// Get the min length of the argument
+    pub fn min_length(&self) -> Option<u16> {
+        self.min_length
+    }
+
+    /// Get the max length of the argument
+    pub fn max_length(&self) -> Option<u16> {
+        self.max_length
+    }
+
+    /// Get the allowed channel types of the argument
+    pub fn channel_types(&self) -> &[ChannelType] {
+        &self.channel_types
+    }
+
+    /// Check if the argument has an autocomplete hook
+    pub fn has_autocomplete(&self) -> bool {
+        self.on_autocomplete.is_some()
+    }
+
+    /// Fire the on_autocomplete hook.
+    ///
+    /// `value` is the partial value the user has typed so far.
+    /// This returns no choices if this argument does not have an autocomplete hook.
+    pub async fn fire_on_autocomplete(
+        &self,
+        ctx: Context,
+        interaction: CommandInteraction,
+        value: String,
+        states: States,
+    ) -> Result<Vec<AutocompleteChoice>, BoxError> {
+        match self.on_autocomplete.as_ref() {
+            Some(on_autocomplete) => (on_autocomplete)(ctx, interaction, value, states).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the type ids and type names of the states the on_autocomplete hook needs
+    pub fn required_states(&self) -> &[(TypeId, &'static str)] {
+        &self.required_states
+    }
+
+    /// Make a command option from this argument, for registration.
+    pub(crate) fn create_option(&self) -> CreateCommandOption {
+        let option_kind = match self.kind() {
+            DataType::Boolean => CommandOptionType::Boolean,
+            DataType::String => CommandOptionType::String,
+            DataType::Integer => CommandOptionType::Integer,
+            DataType::User => CommandOptionType::User,
+            DataType::Channel => CommandOptionType::Channel,
+            DataType::Role => CommandOptionType::Role,
+            DataType::Mentionable => CommandOptionType::Mentionable,
+            DataType::Number => CommandOptionType::Number,
+            DataType::Attachment => CommandOptionType::Attachment,
+        };
+
+        let mut option = CreateCommandOption::new(option_kind, self.name(), self.description())
+            .required(self.required())
+            .set_autocomplete(self.has_autocomplete());
+
+        for (locale, name) in self.name_localizations().iter() {
+            option = option.name_localized(&**locale, &**name);
+        }
+        for (locale, description) in self.description_localizations().iter() {
+            option = option.description_localized(&**locale, &**description);
+        }
+
+        for choice in self.choices().iter() {
+            option = match choice.value() {
+                ChoiceValue::String(value) => option.add_string_choice(choice.name(), &**value),
+                ChoiceValue::Integer(value) => option.add_int_choice(
+                    choice.name(),
+                    i32::try_from(*value).expect("integer choice out of range"),
+                ),
+                ChoiceValue::Number(value) => option.add_number_choice(choice.name(), *value),
+            };
+        }
+
+        // Serenity only accepts unsigned integer bounds.
+        if let Some(min_value) = self.min_value() {
+            option = match self.kind() {
+                DataType::Integer if min_value >= 0.0 => option.min_int_value(min_value as u64),
+                _ => option.min_number_value(min_value),
+            };
+        }
+        if let Some(max_value) = self.max_value() {
+            option = match self.kind() {
+                DataType::Integer if max_value >= 0.0 => option.max_int_value(max_value as u64),
+                _ => option.max_number_value(max_value),
+            };
+        }
+
+        if let Some(min_length) = self.min_length() {
+            option = option.min_length(min_length);
+        }
+        if let Some(max_length) = self.max_length() {
+            option = option.max_length(max_length);
+        }
+
+        if !self.channel_types().is_empty() {
+            option = option.channel_types(self.channel_types().to_vec());
+        }
+
+        option
+    }
+}
+
+impl std::fmt::Debug for ArgumentParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgumentParam")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("description", &self.description)
+            .field("required", &self.required)
+            .field("name_localizations", &self.name_localizations)
+            .field("description_localizations", &self.description_localizations)
+            .field("choices", &self.choices)
+            .field("min_value", &self.min_value)
+            .field("max_value", &self.max_value)
+            .field("min_length", &self.min_length)
+            .field("max_length", &self.max_length)
+            .field("channel_types", &self.channel_types)
+            .field(
+                "on_autocomplete",
+                &self.on_autocomplete.as_ref().map(|_| "<func>"),
+            )
+            .finish()
+    }
 }
 
 /// An argument param builder
-#[derive(Debug)]
 pub struct ArgumentParamBuilder<'a, 'b> {
     name: Option<&'a str>,
     kind: Option<DataType>,
     description: Option<&'b str>,
     required: bool,
+    name_localizations: HashMap<Box<str>, Box<str>>,
+    description_localizations: HashMap<Box<str>, Box<str>>,
+    choices: Vec<ArgumentChoice>,
+    min_value: Option<f64>,
+    max_value: Option<f64>,
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+    channel_types: Vec<ChannelType>,
+
+    on_autocomplete: Option<OnAutocompleteFutureFn>,
+    required_states: Vec<(TypeId, &'static str)>,
 }
 
 impl<'a, 'b> ArgumentParamBuilder<'a, 'b> {
@@ -53,6 +363,17 @@ impl<'a, 'b> ArgumentParamBuilder<'a, 'b> {
             kind: None,
             description: None,
             required: false,
+            name_localizations: HashMap::new(),
+            description_localizations: HashMap::new(),
+            choices: Vec::new(),
+            min_value: None,
+            max_value: None,
+            min_length: None,
+            max_length: None,
+            channel_types: Vec::new(),
+
+            on_autocomplete: None,
+            required_states: Vec::new(),
         }
     }
 
@@ -80,6 +401,116 @@ impl<'a, 'b> ArgumentParamBuilder<'a, 'b> {
         self
     }
 
+    /// Add a translated name for a locale
+    pub fn name_localized(&mut self, locale: &str, name: &str) -> &mut Self {
+        self.name_localizations.insert(locale.into(), name.into());
+        self
+    }
+
+    /// Add a translated description for a locale
+    pub fn description_localized(&mut self, locale: &str, description: &str) -> &mut Self {
+        self.description_localizations
+            .insert(locale.into(), description.into());
+        self
+    }
+
+    /// Add translated names and descriptions from the "{key}.name" and "{key}.description" strings
+    pub fn localize(&mut self, translations: &Translations, key: &str) -> &mut Self {
+        for (locale, name) in translations.localizations(&format!("{key}.name")) {
+            self.name_localized(locale, name);
+        }
+        for (locale, description) in translations.localizations(&format!("{key}.description")) {
+            self.description_localized(locale, description);
+        }
+        self
+    }
+
+    /// Add a fixed choice.
+    ///
+    /// `name` is what the user sees, `value` is what the bot receives.
+    pub fn choice(&mut self, name: &str, value: impl Into<ChoiceValue>) -> &mut Self {
+        self.choices.push(ArgumentChoice::new(name, value));
+        self
+    }
+
+    /// Add fixed choices
+    pub fn choices(&mut self, choices: impl IntoIterator<Item = ArgumentChoice>) -> &mut Self {
+        self.choices.extend(choices);
+        self
+    }
+
+    /// Set the min value, for integers and numbers
+    pub fn min_value(&mut self, min_value: f64) -> &mut Self {
+        self.min_value = Some(min_value);
+        self
+    }
+
+    /// Set the max value, for integers and numbers
+    pub fn max_value(&mut self, max_value: f64) -> &mut Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// Set the min length, for strings
+    pub fn min_length(&mut self, min_length: u16) -> &mut Self {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    /// Set the max length, for strings
+    pub fn max_length(&mut self, max_length: u16) -> &mut Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Add an allowed channel type, for channels
+    pub fn channel_type(&mut self, channel_type: ChannelType) -> &mut Self {
+        self.channel_types.push(channel_type);
+        self
+    }
+
+    /// The on_autocomplete hook.
+    ///
+    /// This is called with the partial value the user has typed so far,
+    /// and should return the choices to suggest.
+    /// Discord will only show the first 25 choices.
+    pub fn on_autocomplete<F>(&mut self, on_autocomplete: OnAutocompleteFutureFnPtr<F>) -> &mut Self
+    where
+        F: Future<Output = Result<Vec<AutocompleteChoice>, BoxError>> + Send + 'static,
+    {
+        // Trampoline so user does not have to box manually
+        self.on_autocomplete = Some(Box::new(move |ctx, interaction, value, _states| {
+            Box::pin((on_autocomplete)(ctx, interaction, value))
+        }));
+        self.required_states.clear();
+
+        self
+    }
+
+    /// The on_autocomplete hook, with shared state from the framework.
+    ///
+    /// `S` is a [`State`](crate::State) or a tuple of them.
+    /// The framework fails to build if a state was not registered.
+    pub fn on_autocomplete_with_state<F, S>(
+        &mut self,
+        on_autocomplete: OnAutocompleteWithStateFutureFnPtr<F, S>,
+    ) -> &mut Self
+    where
+        F: Future<Output = Result<Vec<AutocompleteChoice>, BoxError>> + Send + 'static,
+        S: FromStates,
+    {
+        // Trampoline so user does not have to box manually or extract their states manually
+        self.on_autocomplete = Some(Box::new(move |ctx, interaction, value, states| {
+            Box::pin(async move {
+                let states = S::from_states(&states)?;
+                (on_autocomplete)(ctx, interaction, value, states).await
+            })
+        }));
+        self.required_states = S::required_states();
+
+        self
+    }
+
     /// Build the argument param
     pub fn build(&mut self) -> Result<ArgumentParam, BuilderError> {
         #[allow(clippy::or_fun_call)]
@@ -91,15 +522,109 @@ impl<'a, 'b> ArgumentParamBuilder<'a, 'b> {
             .description
             .ok_or(BuilderError::MissingField("description"))?;
 
+        let choices = std::mem::take(&mut self.choices);
+        if choices.len() > MAX_CHOICES {
+            return Err(BuilderError::Invalid(
+                "an argument cannot have more than 25 choices",
+            ));
+        }
+        for choice in choices.iter() {
+            if choice.value().data_type() != kind {
+                return Err(BuilderError::Invalid(
+                    "the type of a choice must match the argument kind",
+                ));
+            }
+
+            if let ChoiceValue::Integer(value) = choice.value() {
+                if i32::try_from(*value).is_err() {
+                    return Err(BuilderError::Invalid("integer choices must fit in an i32"));
+                }
+            }
+        }
+
+        let has_value_range = self.min_value.is_some() || self.max_value.is_some();
+        if has_value_range && !matches!(kind, DataType::Integer | DataType::Number) {
+            return Err(BuilderError::Invalid(
+                "only integer and number arguments can have a min or max value",
+            ));
+        }
+        if let (Some(min_value), Some(max_value)) = (self.min_value, self.max_value) {
+            if min_value > max_value {
+                return Err(BuilderError::Invalid(
+                    "the min value cannot be greater than the max value",
+                ));
+            }
+        }
+
+        let has_length_range = self.min_length.is_some() || self.max_length.is_some();
+        if has_length_range && kind != DataType::String {
+            return Err(BuilderError::Invalid(
+                "only string arguments can have a min or max length",
+            ));
+        }
+        if let (Some(min_length), Some(max_length)) = (self.min_length, self.max_length) {
+            if min_length > max_length {
+                return Err(BuilderError::Invalid(
+                    "the min length cannot be greater than the max length",
+                ));
+            }
+        }
+
+        if !self.channel_types.is_empty() && kind != DataType::Channel {
+            return Err(BuilderError::Invalid(
+                "only channel arguments can have channel types",
+            ));
+        }
+
+        if !choices.is_empty() && self.on_autocomplete.is_some() {
+            return Err(BuilderError::Invalid(
+                "an argument cannot have both choices and an autocomplete hook",
+            ));
+        }
+
         Ok(ArgumentParam {
             name: name.into(),
             kind,
             description: description.into(),
             required: self.required,
+            name_localizations: std::mem::take(&mut self.name_localizations),
+            description_localizations: std::mem::take(&mut self.description_localizations),
+            choices: choices.into_boxed_slice(),
+            min_value: self.min_value,
+            max_value: self.max_value,
+            min_length: self.min_length,
+            max_length: self.max_length,
+            channel_types: std::mem::take(&mut self.channel_types).into_boxed_slice(),
+
+            on_autocomplete: self.on_autocomplete.take(),
+            required_states: std::mem::take(&mut self.required_states).into_boxed_slice(),
         })
     }
 }
 
+impl std::fmt::Debug for ArgumentParamBuilder<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgumentParamBuilder")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("description", &self.description)
+            .field("required", &self.required)
+            .field("name_localizations", &self.name_localizations)
+            .field("description_localizations", &self.description_localizations)
+            .field("choices", &self.choices)
+            .field("min_value", &self.min_value)
+            .field("max_value", &self.max_value)
+            .field("min_length", &self.min_length)
+            .field("max_length", &self.max_length)
+            .field("channel_types", &self.channel_types)
+            .field(
+                "on_autocomplete",
+                &self.on_autocomplete.as_ref().map(|_| "<func>"),
+            )
+            .finish()
+    }
+}
+
 impl<'a, 'b> Default for ArgumentParamBuilder<'a, 'b> {
     fn default() -> Self {
         Self::new()
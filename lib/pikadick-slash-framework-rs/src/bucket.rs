@@ -0,0 +1,206 @@
+use crate::BuilderError;
+use serenity::model::id::{
+    ChannelId,
+    GuildId,
+    UserId,
+};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// What a bucket limits
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BucketScope {
+    /// Each user has their own limit
+    User,
+
+    /// Each channel has its own limit
+    Channel,
+
+    /// Each guild has its own limit.
+    ///
+    /// DMs fall back to a limit per channel.
+    Guild,
+}
+
+impl BucketScope {
+    /// Get the id of the thing this scope limits
+    fn get_key(self, user_id: UserId, channel_id: ChannelId, guild_id: Option<GuildId>) -> u64 {
+        match self {
+            Self::User => user_id.get(),
+            Self::Channel => channel_id.get(),
+            Self::Guild => guild_id
+                .map(|guild_id| guild_id.get())
+                .unwrap_or_else(|| channel_id.get()),
+        }
+    }
+}
+
+/// A rate limit bucket.
+///
+/// Each key may use the bucket `burst` times in a row,
+/// and regains a use every `delay`.
+pub struct Bucket {
+    scope: BucketScope,
+    delay: Duration,
+    burst: u32,
+
+    /// The time at which each key will have all of its uses back
+    state: Mutex<HashMap<u64, Instant>>,
+}
+
+impl Bucket {
+    /// Get the scope
+    pub fn scope(&self) -> BucketScope {
+        self.scope
+    }
+
+    /// Get the delay
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Get the burst
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Check if this bucket could be used by a user, without using it.
+    ///
+    /// # Errors
+    /// Returns the time until the bucket may be used again if it is limited.
+    pub fn check(
+        &self,
+        user_id: UserId,
+        channel_id: ChannelId,
+        guild_id: Option<GuildId>,
+    ) -> Result<(), Duration> {
+        let key = self.scope.get_key(user_id, channel_id, guild_id);
+        let now = Instant::now();
+
+        let state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        match state.get(&key) {
+            Some(full_at) => self.get_wait(*full_at, now),
+            None => Ok(()),
+        }
+    }
+
+    /// Try to use this bucket for a user.
+    ///
+    /// # Errors
+    /// Returns the time until the bucket may be used again if it is limited.
+    pub fn take(
+        &self,
+        user_id: UserId,
+        channel_id: ChannelId,
+        guild_id: Option<GuildId>,
+    ) -> Result<(), Duration> {
+        let key = self.scope.get_key(user_id, channel_id, guild_id);
+        let now = Instant::now();
+
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        if !state.contains_key(&key) {
+            // Drop keys that have all their uses back before adding more
+            state.retain(|_, full_at| *full_at > now);
+        }
+
+        let full_at = state.entry(key).or_insert(now);
+        self.get_wait(*full_at, now)?;
+        *full_at = (*full_at).max(now) + self.delay;
+
+        Ok(())
+    }
+
+    /// Get the time until a key that has all its uses back at `full_at` may use this bucket.
+    fn get_wait(&self, full_at: Instant, now: Instant) -> Result<(), Duration> {
+        let burst_time = self.delay * self.burst.saturating_sub(1);
+        let start = full_at.max(now);
+        let wait = (start - now).saturating_sub(burst_time);
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Bucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bucket")
+            .field("scope", &self.scope)
+            .field("delay", &self.delay)
+            .field("burst", &self.burst)
+            .finish()
+    }
+}
+
+/// A builder for a [`Bucket`].
+#[derive(Debug)]
+pub struct BucketBuilder {
+    scope: BucketScope,
+    delay: Option<Duration>,
+    burst: u32,
+}
+
+impl BucketBuilder {
+    /// Make a new [`BucketBuilder`].
+    ///
+    /// This limits per channel with a burst of 1 by default.
+    pub fn new() -> Self {
+        Self {
+            scope: BucketScope::Channel,
+            delay: None,
+            burst: 1,
+        }
+    }
+
+    /// What the bucket limits
+    pub fn scope(&mut self, scope: BucketScope) -> &mut Self {
+        self.scope = scope;
+        self
+    }
+
+    /// The time it takes to regain a use
+    pub fn delay(&mut self, delay: Duration) -> &mut Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// The number of uses in a row that are allowed
+    pub fn burst(&mut self, burst: u32) -> &mut Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Build the [`Bucket`]
+    pub fn build(&mut self) -> Result<Bucket, BuilderError> {
+        #[allow(clippy::or_fun_call)]
+        let delay = self
+            .delay
+            .take()
+            .ok_or(BuilderError::MissingField("delay"))?;
+
+        if self.burst == 0 {
+            return Err(BuilderError::Invalid("a bucket burst must be at least 1"));
+        }
+
+        Ok(Bucket {
+            scope: self.scope,
+            delay,
+            burst: self.burst,
+
+            state: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl Default for BucketBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
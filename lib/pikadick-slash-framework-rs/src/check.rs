@@ -1,10 +1,17 @@
 use crate::{
     BoxFuture,
     Command,
+    ComponentHandler,
+    ContextMenuCommand,
+    ModalHandler,
 };
 use serenity::{
     client::Context,
-    model::application::CommandInteraction,
+    model::application::{
+        CommandInteraction,
+        ComponentInteraction,
+        ModalInteraction,
+    },
 };
 
 pub type CheckFn = for<'a> fn(
@@ -13,6 +20,24 @@ pub type CheckFn = for<'a> fn(
     &'a Command,
 ) -> BoxFuture<'a, Result<(), Reason>>;
 
+pub type ComponentCheckFn = for<'a> fn(
+    &'a Context,
+    &'a ComponentInteraction,
+    &'a ComponentHandler,
+) -> BoxFuture<'a, Result<(), Reason>>;
+
+pub type ModalCheckFn = for<'a> fn(
+    &'a Context,
+    &'a ModalInteraction,
+    &'a ModalHandler,
+) -> BoxFuture<'a, Result<(), Reason>>;
+
+pub type ContextMenuCheckFn = for<'a> fn(
+    &'a Context,
+    &'a CommandInteraction,
+    &'a ContextMenuCommand,
+) -> BoxFuture<'a, Result<(), Reason>>;
+
 /// the reason a check failed
 pub struct Reason {
     /// The user-facing reason for a failure
@@ -1,4 +1,5 @@
 use crate::{
+    argument::OnAutocompleteFuture,
     ArgumentParam,
     BoxError,
     BoxFuture,
@@ -6,19 +7,29 @@ use crate::{
     CheckFn,
     DataType,
     FromOptions,
+    FromStates,
+    States,
+    Translations,
 };
 use serenity::{
     builder::{
+        AutocompleteChoice,
         CreateCommand,
         CreateCommandOption,
     },
     client::Context,
-    model::application::{
-        CommandInteraction,
-        CommandOptionType,
+    model::{
+        application::{
+            CommandDataOption,
+            CommandDataOptionValue,
+            CommandInteraction,
+            CommandOptionType,
+        },
+        permissions::Permissions,
     },
 };
 use std::{
+    any::TypeId,
     collections::HashMap,
     future::Future,
     sync::Arc,
@@ -28,8 +39,10 @@ type OnProcessResult = Result<(), BoxError>;
 pub type OnProcessFuture = BoxFuture<'static, OnProcessResult>;
 
 // Keep these types in sync.
-type OnProcessFutureFn = Box<dyn Fn(Context, CommandInteraction) -> OnProcessFuture + Send + Sync>;
+type OnProcessFutureFn =
+    Box<dyn Fn(Context, CommandInteraction, States) -> OnProcessFuture + Send + Sync>;
 type OnProcessFutureFnPtr<F, A> = fn(Context, CommandInteraction, A) -> F;
+type OnProcessWithStateFutureFnPtr<F, A, S> = fn(Context, CommandInteraction, A, S) -> F;
 
 type HelpOnProcessFutureFn = Box<
     dyn Fn(Context, CommandInteraction, Arc<HashMap<Box<str>, Command>>) -> OnProcessFuture
@@ -39,6 +52,19 @@ type HelpOnProcessFutureFn = Box<
 type HelpOnProcessFutureFnPtr<F, A> =
     fn(Context, CommandInteraction, Arc<HashMap<Box<str>, Command>>, A) -> F;
 
+type HelpOnAutocompleteFutureFn = Box<
+    dyn Fn(
+            Context,
+            CommandInteraction,
+            Arc<HashMap<Box<str>, Command>>,
+            String,
+        ) -> OnAutocompleteFuture
+        + Send
+        + Sync,
+>;
+type HelpOnAutocompleteFutureFnPtr<F> =
+    fn(Context, CommandInteraction, Arc<HashMap<Box<str>, Command>>, String) -> F;
+
 /// A slash framework command
 pub struct Command {
     /// The name of the command
@@ -47,14 +73,46 @@ pub struct Command {
     /// Description
     description: Box<str>,
 
+    /// Translated names, by locale
+    name_localizations: HashMap<Box<str>, Box<str>>,
+
+    /// Translated descriptions, by locale
+    description_localizations: HashMap<Box<str>, Box<str>>,
+
     /// Arguments
     arguments: Box<[ArgumentParam]>,
 
-    /// The main "process" func
-    on_process: OnProcessFutureFn,
+    /// The main "process" func.
+    ///
+    /// This is `None` if this command only contains subcommands.
+    on_process: Option<OnProcessFutureFn>,
 
     /// Checks that must pass before this command is run
     checks: Vec<CheckFn>,
+
+    /// The type ids and type names of the states the on_process hook needs
+    required_states: Box<[(TypeId, &'static str)]>,
+
+    /// Subcommands
+    subcommands: Box<[Command]>,
+
+    /// The permissions a member needs to see this command by default
+    default_member_permissions: Option<Permissions>,
+
+    /// Whether this command is available in DMs
+    dm_permission: bool,
+
+    /// Whether this command can only be used in age-restricted channels
+    nsfw: bool,
+
+    /// The name of the rate limit bucket of this command
+    bucket: Option<Box<str>>,
+
+    /// The category this command is listed under in help
+    category: Option<Box<str>>,
+
+    /// Usage examples
+    examples: Box<[Box<str>]>,
 }
 
 impl Command {
@@ -68,18 +126,87 @@ impl Command {
         &self.description
     }
 
+    /// Get the translated names of the command, by locale
+    pub fn name_localizations(&self) -> &HashMap<Box<str>, Box<str>> {
+        &self.name_localizations
+    }
+
+    /// Get the translated descriptions of the command, by locale
+    pub fn description_localizations(&self) -> &HashMap<Box<str>, Box<str>> {
+        &self.description_localizations
+    }
+
     /// Get the command arguments
     pub fn arguments(&self) -> &[ArgumentParam] {
         &self.arguments
     }
 
+    /// Get the subcommands
+    pub fn subcommands(&self) -> &[Command] {
+        &self.subcommands
+    }
+
+    /// Get a subcommand by name
+    pub fn subcommand(&self, name: &str) -> Option<&Command> {
+        self.subcommands
+            .iter()
+            .find(|command| command.name() == name)
+    }
+
+    /// Check if this command is made up of subcommands.
+    ///
+    /// Commands with subcommands cannot be run directly.
+    pub fn has_subcommands(&self) -> bool {
+        !self.subcommands.is_empty()
+    }
+
+    /// Resolve the path of commands that an interaction's options point to.
+    ///
+    /// The path starts with this command and ends with the command that should be run.
+    /// This walks through subcommand groups and subcommands.
+    ///
+    /// # Returns
+    /// Returns `None` if a subcommand could not be found.
+    pub fn resolve_path<'a>(
+        &'a self,
+        mut options: &[CommandDataOption],
+    ) -> Option<Vec<&'a Command>> {
+        let mut command = self;
+        let mut path = vec![command];
+
+        while command.has_subcommands() {
+            let option = options.first()?;
+            let sub_options = match &option.value {
+                CommandDataOptionValue::SubCommand(sub_options)
+                | CommandDataOptionValue::SubCommandGroup(sub_options) => sub_options,
+                _ => return None,
+            };
+
+            command = command.subcommand(&option.name)?;
+            path.push(command);
+            options = sub_options;
+        }
+
+        Some(path)
+    }
+
     /// Fire the on_process hook
     pub async fn fire_on_process(
         &self,
         ctx: Context,
         interaction: CommandInteraction,
+        states: States,
     ) -> Result<(), BoxError> {
-        (self.on_process)(ctx, interaction).await
+        let on_process = self
+            .on_process
+            .as_ref()
+            .ok_or_else(|| format!("command \"{}\" cannot be run directly", self.name()))?;
+        (on_process)(ctx, interaction, states).await
+    }
+
+    /// Get the type ids and type names of the states the on_process hook needs
+    pub fn required_states(&self) -> &[(TypeId, &'static str)] {
+        &self.required_states
     }
 
     /// Get the inner checks
@@ -87,24 +214,94 @@ impl Command {
         &self.checks
     }
 
+    /// Get the permissions a member needs to see this command by default
+    pub fn default_member_permissions(&self) -> Option<Permissions> {
+        self.default_member_permissions
+    }
+
+    /// Check if this command is available in DMs
+    pub fn dm_permission(&self) -> bool {
+        self.dm_permission
+    }
+
+    /// Check if this command can only be used in age-restricted channels
+    pub fn nsfw(&self) -> bool {
+        self.nsfw
+    }
+
+    /// Get the name of the rate limit bucket of this command
+    pub fn bucket(&self) -> Option<&str> {
+        self.bucket.as_deref()
+    }
+
+    /// Get the category this command is listed under in help
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// Get the usage examples
+    pub fn examples(&self) -> &[Box<str>] {
+        &self.examples
+    }
+
     /// Register this command
+    // `dm_permission` is deprecated in favor of contexts, but Discord still accepts it.
+    #[allow(deprecated)]
     pub fn register(&self, mut command: CreateCommand) -> CreateCommand {
-        command = command.name(self.name()).description(self.description());
+        command = command
+            .name(self.name())
+            .description(self.description())
+            .dm_permission(self.dm_permission())
+            .nsfw(self.nsfw());
+
+        if let Some(permissions) = self.default_member_permissions() {
+            command = command.default_member_permissions(permissions);
+        }
+
+        for (locale, name) in self.name_localizations().iter() {
+            command = command.name_localized(&**locale, &**name);
+        }
+        for (locale, description) in self.description_localizations().iter() {
+            command = command.description_localized(&**locale, &**description);
+        }
 
         for argument in self.arguments().iter() {
-            let option_kind = match argument.kind() {
-                DataType::Boolean => CommandOptionType::Boolean,
-                DataType::String => CommandOptionType::String,
-                DataType::Integer => CommandOptionType::Integer,
-            };
-            let option =
-                CreateCommandOption::new(option_kind, argument.name(), argument.description())
-                    .required(argument.required());
-            command = command.add_option(option);
+            command = command.add_option(argument.create_option());
+        }
+
+        for subcommand in self.subcommands().iter() {
+            command = command.add_option(subcommand.create_subcommand_option());
         }
 
         command
     }
+
+    /// Make a command option for this command, registering it as a subcommand or subcommand group.
+    fn create_subcommand_option(&self) -> CreateCommandOption {
+        let kind = if self.has_subcommands() {
+            CommandOptionType::SubCommandGroup
+        } else {
+            CommandOptionType::SubCommand
+        };
+        let mut option = CreateCommandOption::new(kind, self.name(), self.description());
+
+        for (locale, name) in self.name_localizations().iter() {
+            option = option.name_localized(&**locale, &**name);
+        }
+        for (locale, description) in self.description_localizations().iter() {
+            option = option.description_localized(&**locale, &**description);
+        }
+
+        for argument in self.arguments().iter() {
+            option = option.add_sub_option(argument.create_option());
+        }
+
+        for subcommand in self.subcommands().iter() {
+            option = option.add_sub_option(subcommand.create_subcommand_option());
+        }
+
+        option
+    }
 }
 
 impl std::fmt::Debug for Command {
@@ -112,8 +309,20 @@ impl std::fmt::Debug for Command {
         f.debug_struct("Command")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("name_localizations", &self.name_localizations)
+            .field("description_localizations", &self.description_localizations)
             .field("arguments", &self.arguments)
-            .field("on_process", &"<func>")
+            .field("on_process", &self.on_process.as_ref().map(|_| "<func>"))
+            .field("subcommands", &self.subcommands)
+            .field(
+                "default_member_permissions",
+                &self.default_member_permissions,
+            )
+            .field("dm_permission", &self.dm_permission)
+            .field("nsfw", &self.nsfw)
+            .field("bucket", &self.bucket)
+            .field("category", &self.category)
+            .field("examples", &self.examples)
             .finish()
     }
 }
@@ -122,10 +331,21 @@ impl std::fmt::Debug for Command {
 pub struct CommandBuilder<'a, 'b> {
     name: Option<&'a str>,
     description: Option<&'b str>,
+    name_localizations: HashMap<Box<str>, Box<str>>,
+    description_localizations: HashMap<Box<str>, Box<str>>,
     arguments: Vec<ArgumentParam>,
 
     on_process: Option<OnProcessFutureFn>,
     checks: Vec<CheckFn>,
+    required_states: Vec<(TypeId, &'static str)>,
+    subcommands: Vec<Command>,
+
+    default_member_permissions: Option<Permissions>,
+    dm_permission: bool,
+    nsfw: bool,
+    bucket: Option<Box<str>>,
+    category: Option<Box<str>>,
+    examples: Vec<Box<str>>,
 }
 
 impl<'a, 'b> CommandBuilder<'a, 'b> {
@@ -134,10 +354,21 @@ impl<'a, 'b> CommandBuilder<'a, 'b> {
         Self {
             name: None,
             description: None,
+            name_localizations: HashMap::new(),
+            description_localizations: HashMap::new(),
             arguments: Vec::new(),
 
             on_process: None,
             checks: Vec::new(),
+            required_states: Vec::new(),
+            subcommands: Vec::new(),
+
+            default_member_permissions: None,
+            dm_permission: true,
+            nsfw: false,
+            bucket: None,
+            category: None,
+            examples: Vec::new(),
         }
     }
 
@@ -153,6 +384,30 @@ impl<'a, 'b> CommandBuilder<'a, 'b> {
         self
     }
 
+    /// Add a translated name for a locale
+    pub fn name_localized(&mut self, locale: &str, name: &str) -> &mut Self {
+        self.name_localizations.insert(locale.into(), name.into());
+        self
+    }
+
+    /// Add a translated description for a locale
+    pub fn description_localized(&mut self, locale: &str, description: &str) -> &mut Self {
+        self.description_localizations
+            .insert(locale.into(), description.into());
+        self
+    }
+
+    /// Add translated names and descriptions from the "{key}.name" and "{key}.description" strings
+    pub fn localize(&mut self, translations: &Translations, key: &str) -> &mut Self {
+        for (locale, name) in translations.localizations(&format!("{key}.name")) {
+            self.name_localized(locale, name);
+        }
+        for (locale, description) in translations.localizations(&format!("{key}.description")) {
+            self.description_localized(locale, description);
+        }
+        self
+    }
+
     /// Add an argument
     pub fn argument(&mut self, argument: ArgumentParam) -> &mut Self {
         self.arguments.push(argument);
@@ -174,12 +429,39 @@ impl<'a, 'b> CommandBuilder<'a, 'b> {
         A: FromOptions + 'static,
     {
         // Trampoline so user does not have to box manually and parse their args manually
-        self.on_process = Some(Box::new(move |ctx, interaction| {
+        self.on_process = Some(Box::new(move |ctx, interaction, _states| {
             Box::pin(async move {
                 let args = A::from_options(&interaction)?;
                 (on_process)(ctx, interaction, args).await
             })
         }));
+        self.required_states.clear();
+
+        self
+    }
+
+    /// The on_process hook, with shared state from the framework.
+    ///
+    /// `S` is a [`State`](crate::State) or a tuple of them.
+    /// The framework fails to build if a state was not registered.
+    pub fn on_process_with_state<F, A, S>(
+        &mut self,
+        on_process: OnProcessWithStateFutureFnPtr<F, A, S>,
+    ) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        A: FromOptions + 'static,
+        S: FromStates,
+    {
+        // Trampoline so user does not have to box manually and parse their args or states manually
+        self.on_process = Some(Box::new(move |ctx, interaction, states| {
+            Box::pin(async move {
+                let args = A::from_options(&interaction)?;
+                let states = S::from_states(&states)?;
+                (on_process)(ctx, interaction, args, states).await
+            })
+        }));
+        self.required_states = S::required_states();
 
         self
     }
@@ -190,6 +472,66 @@ impl<'a, 'b> CommandBuilder<'a, 'b> {
         self
     }
 
+    /// Add a subcommand.
+    ///
+    /// Subcommands may have their own subcommands, making this command a subcommand group.
+    /// Discord only allows one level of groups.
+    /// A command with subcommands cannot have arguments or an on_process hook.
+    pub fn subcommand(&mut self, command: Command) -> &mut Self {
+        self.subcommands.push(command);
+        self
+    }
+
+    /// The permissions a member needs to see this command by default.
+    ///
+    /// Server admins can override this.
+    /// This only applies to top-level commands.
+    pub fn default_member_permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.default_member_permissions = Some(permissions);
+        self
+    }
+
+    /// Whether this command is available in DMs.
+    ///
+    /// This defaults to true.
+    /// This only applies to top-level commands.
+    pub fn dm_permission(&mut self, dm_permission: bool) -> &mut Self {
+        self.dm_permission = dm_permission;
+        self
+    }
+
+    /// Whether this command can only be used in age-restricted channels.
+    ///
+    /// Discord only hides top-level commands,
+    /// but the framework refuses nsfw subcommands in other channels as well.
+    pub fn nsfw(&mut self, nsfw: bool) -> &mut Self {
+        self.nsfw = nsfw;
+        self
+    }
+
+    /// The name of the rate limit bucket of this command.
+    ///
+    /// The bucket must be added to the framework.
+    /// Buckets of parent commands also apply to their subcommands.
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        self.bucket = Some(bucket.into());
+        self
+    }
+
+    /// The category this command is listed under in help
+    pub fn category(&mut self, category: &str) -> &mut Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Add a usage example, like "/rule34 query:cat".
+    ///
+    /// These are shown as-is in help.
+    pub fn example(&mut self, example: &str) -> &mut Self {
+        self.examples.push(example.into());
+        self
+    }
+
     /// Build the [`Command`]
     pub fn build(&mut self) -> Result<Command, BuilderError> {
         #[allow(clippy::or_fun_call)]
@@ -199,20 +541,66 @@ impl<'a, 'b> CommandBuilder<'a, 'b> {
             .description
             .take()
             .ok_or(BuilderError::MissingField("description"))?;
-        #[allow(clippy::or_fun_call)]
-        let on_process = self
-            .on_process
-            .take()
-            .ok_or(BuilderError::MissingField("on_process"))?;
+        let on_process = self.on_process.take();
         let checks = std::mem::take(&mut self.checks);
+        let arguments = std::mem::take(&mut self.arguments).into_boxed_slice();
+        let subcommands = std::mem::take(&mut self.subcommands).into_boxed_slice();
+
+        if subcommands.is_empty() {
+            if on_process.is_none() {
+                return Err(BuilderError::MissingField("on_process"));
+            }
+        } else {
+            if on_process.is_some() {
+                return Err(BuilderError::Invalid(
+                    "a command with subcommands cannot have an on_process hook",
+                ));
+            }
+
+            if !arguments.is_empty() {
+                return Err(BuilderError::Invalid(
+                    "a command with subcommands cannot have arguments",
+                ));
+            }
+
+            let too_deep = subcommands
+                .iter()
+                .flat_map(|subcommand| subcommand.subcommands().iter())
+                .any(|subcommand| subcommand.has_subcommands());
+            if too_deep {
+                return Err(BuilderError::Invalid(
+                    "subcommand groups cannot contain other subcommand groups",
+                ));
+            }
+
+            for (i, subcommand) in subcommands.iter().enumerate() {
+                let is_duplicate = subcommands[..i]
+                    .iter()
+                    .any(|other| other.name() == subcommand.name());
+                if is_duplicate {
+                    return Err(BuilderError::Duplicate(subcommand.name().into()));
+                }
+            }
+        }
 
         Ok(Command {
             name: name.into(),
             description: description.into(),
-            arguments: std::mem::take(&mut self.arguments).into_boxed_slice(),
+            name_localizations: std::mem::take(&mut self.name_localizations),
+            description_localizations: std::mem::take(&mut self.description_localizations),
+            arguments,
 
             on_process,
             checks,
+            required_states: std::mem::take(&mut self.required_states).into_boxed_slice(),
+            subcommands,
+
+            default_member_permissions: self.default_member_permissions.take(),
+            dm_permission: std::mem::replace(&mut self.dm_permission, true),
+            nsfw: std::mem::take(&mut self.nsfw),
+            bucket: self.bucket.take(),
+            category: self.category.take(),
+            examples: std::mem::take(&mut self.examples).into_boxed_slice(),
         })
     }
 }
@@ -222,8 +610,20 @@ impl std::fmt::Debug for CommandBuilder<'_, '_> {
         f.debug_struct("CommandBuilder")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("name_localizations", &self.name_localizations)
+            .field("description_localizations", &self.description_localizations)
             .field("arguments", &self.arguments)
             .field("on_process", &self.on_process.as_ref().map(|_| "<func>"))
+            .field("subcommands", &self.subcommands)
+            .field(
+                "default_member_permissions",
+                &self.default_member_permissions,
+            )
+            .field("dm_permission", &self.dm_permission)
+            .field("nsfw", &self.nsfw)
+            .field("bucket", &self.bucket)
+            .field("category", &self.category)
+            .field("examples", &self.examples)
             .finish()
     }
 }
@@ -244,6 +644,9 @@ pub struct HelpCommand {
 
     /// The main "process" func
     on_process: HelpOnProcessFutureFn,
+
+    /// The autocomplete hook for string arguments
+    on_autocomplete: Option<HelpOnAutocompleteFutureFn>,
 }
 
 impl HelpCommand {
@@ -267,19 +670,45 @@ impl HelpCommand {
         (self.on_process)(ctx, interaction, map).await
     }
 
+    /// Check if the help command has an autocomplete hook
+    pub fn has_autocomplete(&self) -> bool {
+        self.on_autocomplete.is_some()
+    }
+
+    /// Fire the on_autocomplete hook.
+    ///
+    /// Arguments with their own autocomplete hook take priority over this one.
+    /// This returns no choices if neither exists.
+    pub async fn fire_on_autocomplete(
+        &self,
+        ctx: Context,
+        interaction: CommandInteraction,
+        map: Arc<HashMap<Box<str>, Command>>,
+        argument: &ArgumentParam,
+        value: String,
+        states: States,
+    ) -> Result<Vec<AutocompleteChoice>, BoxError> {
+        if argument.has_autocomplete() {
+            return argument
+                .fire_on_autocomplete(ctx, interaction, value, states)
+                .await;
+        }
+
+        match self.on_autocomplete.as_ref() {
+            Some(on_autocomplete) => (on_autocomplete)(ctx, interaction, map, value).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Register this help command
     pub fn register(&self, mut command: CreateCommand) -> CreateCommand {
         command = command.name("help").description(self.description());
 
         for argument in self.arguments().iter() {
-            let option_kind = match argument.kind() {
-                DataType::Boolean => CommandOptionType::Boolean,
-                DataType::String => CommandOptionType::String,
-                DataType::Integer => CommandOptionType::Integer,
-            };
-            let option =
-                CreateCommandOption::new(option_kind, argument.name(), argument.description())
-                    .required(argument.required());
+            let mut option = argument.create_option();
+            if self.has_autocomplete() && argument.kind() == DataType::String {
+                option = option.set_autocomplete(true);
+            }
             command = command.add_option(option);
         }
 
@@ -293,6 +722,10 @@ impl std::fmt::Debug for HelpCommand {
             .field("description", &self.description)
             .field("arguments", &self.arguments)
             .field("on_process", &"<func>")
+            .field(
+                "on_autocomplete",
+                &self.on_autocomplete.as_ref().map(|_| "<func>"),
+            )
             .finish()
     }
 }
@@ -303,6 +736,7 @@ pub struct HelpCommandBuilder<'a> {
     arguments: Vec<ArgumentParam>,
 
     on_process: Option<HelpOnProcessFutureFn>,
+    on_autocomplete: Option<HelpOnAutocompleteFutureFn>,
 }
 
 impl<'a> HelpCommandBuilder<'a> {
@@ -313,6 +747,7 @@ impl<'a> HelpCommandBuilder<'a> {
             arguments: Vec::new(),
 
             on_process: None,
+            on_autocomplete: None,
         }
     }
 
@@ -345,6 +780,25 @@ impl<'a> HelpCommandBuilder<'a> {
         self
     }
 
+    /// The on_autocomplete hook.
+    ///
+    /// This is used for string arguments without their own autocomplete hook.
+    /// It is called with the partial value the user has typed so far.
+    pub fn on_autocomplete<F>(
+        &mut self,
+        on_autocomplete: HelpOnAutocompleteFutureFnPtr<F>,
+    ) -> &mut Self
+    where
+        F: Future<Output = Result<Vec<AutocompleteChoice>, BoxError>> + Send + 'static,
+    {
+        // Trampoline so user does not have to box manually
+        self.on_autocomplete = Some(Box::new(move |ctx, interaction, map, value| {
+            Box::pin((on_autocomplete)(ctx, interaction, map, value))
+        }));
+
+        self
+    }
+
     /// Build the [`HelpCommand`]
     pub fn build(&mut self) -> Result<HelpCommand, BuilderError> {
         #[allow(clippy::or_fun_call)]
@@ -363,6 +817,7 @@ impl<'a> HelpCommandBuilder<'a> {
             arguments: std::mem::take(&mut self.arguments).into_boxed_slice(),
 
             on_process,
+            on_autocomplete: self.on_autocomplete.take(),
         })
     }
 }
@@ -373,6 +828,10 @@ impl std::fmt::Debug for HelpCommandBuilder<'_> {
             .field("description", &self.description)
             .field("arguments", &self.arguments)
             .field("on_process", &self.on_process.as_ref().map(|_| "<func>"))
+            .field(
+                "on_autocomplete",
+                &self.on_autocomplete.as_ref().map(|_| "<func>"),
+            )
             .finish()
     }
 }
@@ -0,0 +1,312 @@
+use crate::{
+    BoxError,
+    BoxFuture,
+    BuilderError,
+    ComponentCheckFn,
+    FromStates,
+    States,
+};
+use serenity::{
+    client::Context,
+    model::application::ComponentInteraction,
+};
+use std::{
+    any::TypeId,
+    future::Future,
+};
+
+type OnProcessResult = Result<(), BoxError>;
+pub type OnComponentProcessFuture = BoxFuture<'static, OnProcessResult>;
+
+// Keep these types in sync.
+type OnProcessFutureFn =
+    Box<dyn Fn(Context, ComponentInteraction, States) -> OnComponentProcessFuture + Send + Sync>;
+type OnProcessFutureFnPtr<F, S> = fn(Context, ComponentInteraction, S) -> F;
+type OnProcessWithStateFutureFnPtr<F, S, T> = fn(Context, ComponentInteraction, S, T) -> F;
+
+/// The char that seperates the prefix and state of a custom id
+pub(crate) const CUSTOM_ID_SEPERATOR: char = ':';
+
+/// The max length of a custom id
+pub const MAX_CUSTOM_ID_LEN: usize = 100;
+
+/// State that is encoded in the custom id of a component
+pub trait ComponentState: Sized + Send + 'static {
+    /// Encode this state as a string
+    fn encode(&self) -> String;
+
+    /// Decode this state from a string
+    fn decode(state: &str) -> Result<Self, BoxError>;
+}
+
+// Allow handlers that need no state
+impl ComponentState for () {
+    fn encode(&self) -> String {
+        String::new()
+    }
+
+    fn decode(_state: &str) -> Result<Self, BoxError> {
+        Ok(())
+    }
+}
+
+impl ComponentState for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(state: &str) -> Result<Self, BoxError> {
+        Ok(state.into())
+    }
+}
+
+/// Make a custom id for a component that will be routed to the handler with the given prefix.
+///
+/// # Returns
+/// Returns `None` if the custom id would be longer than Discord allows.
+pub fn create_custom_id<S>(prefix: &str, state: &S) -> Option<String>
+where
+    S: ComponentState,
+{
+    let custom_id = format!("{prefix}{CUSTOM_ID_SEPERATOR}{}", state.encode());
+    if custom_id.chars().count() > MAX_CUSTOM_ID_LEN {
+        return None;
+    }
+
+    Some(custom_id)
+}
+
+/// Split a custom id into its prefix and state.
+///
+/// Custom ids without a seperator are treated as a prefix with an empty state.
+pub(crate) fn split_custom_id(custom_id: &str) -> (&str, &str) {
+    custom_id
+        .split_once(CUSTOM_ID_SEPERATOR)
+        .unwrap_or((custom_id, ""))
+}
+
+/// A handler for message components, like buttons and select menus.
+///
+/// Components are routed to a handler by the prefix of their custom id.
+pub struct ComponentHandler {
+    /// The custom id prefix
+    prefix: Box<str>,
+
+    /// The name of the command that sends these components, if any
+    command: Option<Box<str>>,
+
+    /// Whether this can only be used in age-restricted channels
+    nsfw: bool,
+
+    /// The main "process" func
+    on_process: OnProcessFutureFn,
+
+    /// The type ids and type names of the states the on_process hook needs
+    required_states: Box<[(TypeId, &'static str)]>,
+
+    /// Checks that must pass before this handler is run
+    checks: Vec<ComponentCheckFn>,
+}
+
+impl ComponentHandler {
+    /// Get the custom id prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Get the name of the command that sends these components.
+    ///
+    /// Checks can use this to treat the components like the command itself.
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+
+    /// Whether this can only be used in age-restricted channels
+    pub fn nsfw(&self) -> bool {
+        self.nsfw
+    }
+
+    /// Fire the on_process hook
+    pub async fn fire_on_process(
+        &self,
+        ctx: Context,
+        interaction: ComponentInteraction,
+        states: States,
+    ) -> Result<(), BoxError> {
+        (self.on_process)(ctx, interaction, states).await
+    }
+
+    /// Get the type ids and type names of the states the on_process hook needs
+    pub fn required_states(&self) -> &[(TypeId, &'static str)] {
+        &self.required_states
+    }
+
+    /// Get the checks
+    pub fn checks(&self) -> &[ComponentCheckFn] {
+        &self.checks
+    }
+}
+
+impl std::fmt::Debug for ComponentHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentHandler")
+            .field("prefix", &self.prefix)
+            .field("command", &self.command)
+            .field("nsfw", &self.nsfw)
+            .field("on_process", &"<func>")
+            .finish()
+    }
+}
+
+/// A builder for a [`ComponentHandler`].
+pub struct ComponentHandlerBuilder<'a> {
+    prefix: Option<&'a str>,
+    command: Option<&'a str>,
+    nsfw: bool,
+
+    on_process: Option<OnProcessFutureFn>,
+    required_states: Vec<(TypeId, &'static str)>,
+    checks: Vec<ComponentCheckFn>,
+}
+
+impl<'a> ComponentHandlerBuilder<'a> {
+    /// Make a new [`ComponentHandlerBuilder`].
+    pub fn new() -> Self {
+        Self {
+            prefix: None,
+            command: None,
+            nsfw: false,
+
+            on_process: None,
+            required_states: Vec::new(),
+            checks: Vec::new(),
+        }
+    }
+
+    /// The custom id prefix
+    pub fn prefix(&mut self, prefix: &'a str) -> &mut Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// The name of the command that sends these components.
+    ///
+    /// For subcommands, this is the full path of the command, like "tic-tac-toe::play".
+    pub fn command(&mut self, command: &'a str) -> &mut Self {
+        self.command = Some(command);
+        self
+    }
+
+    /// Whether this can only be used in age-restricted channels.
+    ///
+    /// Set this if the command that sends these components is nsfw,
+    /// since the messages can be forwarded to other channels.
+    pub fn nsfw(&mut self, nsfw: bool) -> &mut Self {
+        self.nsfw = nsfw;
+        self
+    }
+
+    /// The on_process hook.
+    ///
+    /// The state is decoded from the custom id of the component.
+    pub fn on_process<F, S>(&mut self, on_process: OnProcessFutureFnPtr<F, S>) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        S: ComponentState,
+    {
+        // Trampoline so user does not have to box manually and parse their state manually
+        self.on_process = Some(Box::new(move |ctx, interaction, _states| {
+            Box::pin(async move {
+                let (_prefix, state) = split_custom_id(&interaction.data.custom_id);
+                let state = S::decode(state)?;
+                (on_process)(ctx, interaction, state).await
+            })
+        }));
+        self.required_states.clear();
+
+        self
+    }
+
+    /// The on_process hook, with shared state from the framework.
+    ///
+    /// `T` is a [`State`](crate::State) or a tuple of them.
+    /// The framework fails to build if a state was not registered.
+    pub fn on_process_with_state<F, S, T>(
+        &mut self,
+        on_process: OnProcessWithStateFutureFnPtr<F, S, T>,
+    ) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        S: ComponentState,
+        T: FromStates,
+    {
+        // Trampoline so user does not have to box manually and parse their state or extract their states manually
+        self.on_process = Some(Box::new(move |ctx, interaction, states| {
+            Box::pin(async move {
+                let (_prefix, state) = split_custom_id(&interaction.data.custom_id);
+                let state = S::decode(state)?;
+                let states = T::from_states(&states)?;
+                (on_process)(ctx, interaction, state, states).await
+            })
+        }));
+        self.required_states = T::required_states();
+
+        self
+    }
+
+    /// Add a check to this handler
+    pub fn check(&mut self, check: ComponentCheckFn) -> &mut Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Build the [`ComponentHandler`]
+    pub fn build(&mut self) -> Result<ComponentHandler, BuilderError> {
+        #[allow(clippy::or_fun_call)]
+        let prefix = self
+            .prefix
+            .take()
+            .ok_or(BuilderError::MissingField("prefix"))?;
+        #[allow(clippy::or_fun_call)]
+        let on_process = self
+            .on_process
+            .take()
+            .ok_or(BuilderError::MissingField("on_process"))?;
+
+        if prefix.is_empty() {
+            return Err(BuilderError::Invalid("a component prefix cannot be empty"));
+        }
+        if prefix.contains(CUSTOM_ID_SEPERATOR) {
+            return Err(BuilderError::Invalid(
+                "a component prefix cannot contain ':'",
+            ));
+        }
+
+        Ok(ComponentHandler {
+            prefix: prefix.into(),
+            command: self.command.take().map(Into::into),
+            nsfw: std::mem::take(&mut self.nsfw),
+
+            on_process,
+            required_states: std::mem::take(&mut self.required_states).into_boxed_slice(),
+            checks: std::mem::take(&mut self.checks),
+        })
+    }
+}
+
+impl std::fmt::Debug for ComponentHandlerBuilder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentHandlerBuilder")
+            .field("prefix", &self.prefix)
+            .field("command", &self.command)
+            .field("nsfw", &self.nsfw)
+            .field("on_process", &self.on_process.as_ref().map(|_| "<func>"))
+            .finish()
+    }
+}
+
+impl Default for ComponentHandlerBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{
+    BoxError,
+    BoxFuture,
+    BuilderError,
+    ContextMenuCheckFn,
+    FromStates,
+    States,
+};
+use serenity::{
+    builder::CreateCommand,
+    client::Context,
+    model::{
+        application::{
+            CommandInteraction,
+            CommandType,
+            ResolvedTarget,
+        },
+        channel::Message,
+        user::User,
+    },
+};
+use std::{
+    any::TypeId,
+    future::Future,
+};
+
+type OnProcessResult = Result<(), BoxError>;
+pub type OnContextMenuProcessFuture = BoxFuture<'static, OnProcessResult>;
+
+// Keep these types in sync.
+type OnProcessFutureFn =
+    Box<dyn Fn(Context, CommandInteraction, States) -> OnContextMenuProcessFuture + Send + Sync>;
+type OnProcessFutureFnPtr<F, T> = fn(Context, CommandInteraction, T) -> F;
+type OnProcessWithStateFutureFnPtr<F, T, S> = fn(Context, CommandInteraction, T, S) -> F;
+
+/// The max length of a context menu command name
+const MAX_NAME_LEN: usize = 32;
+
+/// The kind of a context menu command
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ContextMenuKind {
+    /// Shown when right-clicking a user
+    User,
+
+    /// Shown when right-clicking a message
+    Message,
+}
+
+impl ContextMenuKind {
+    /// Get the kind of a [`CommandType`].
+    ///
+    /// Returns `None` if the command type is not a context menu.
+    pub fn from_command_type(kind: CommandType) -> Option<Self> {
+        match kind {
+            CommandType::User => Some(Self::User),
+            CommandType::Message => Some(Self::Message),
+            _ => None,
+        }
+    }
+
+    /// Get this as a [`CommandType`].
+    pub fn as_command_type(self) -> CommandType {
+        match self {
+            Self::User => CommandType::User,
+            Self::Message => CommandType::Message,
+        }
+    }
+}
+
+/// The target of a context menu command, the thing that was right-clicked
+pub trait ContextMenuTarget: Sized + Send + 'static {
+    /// The kind of context menu this target is from
+    const KIND: ContextMenuKind;
+
+    /// Get the target from an interaction
+    fn from_interaction(interaction: &CommandInteraction) -> Option<Self>;
+}
+
+impl ContextMenuTarget for User {
+    const KIND: ContextMenuKind = ContextMenuKind::User;
+
+    fn from_interaction(interaction: &CommandInteraction) -> Option<Self> {
+        match interaction.data.target()? {
+            ResolvedTarget::User(user, _member) => Some(user.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl ContextMenuTarget for Message {
+    const KIND: ContextMenuKind = ContextMenuKind::Message;
+
+    fn from_interaction(interaction: &CommandInteraction) -> Option<Self> {
+        match interaction.data.target()? {
+            ResolvedTarget::Message(message) => Some(message.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The target of a context menu interaction was missing
+#[derive(Debug, thiserror::Error)]
+#[error("missing context menu target")]
+pub struct MissingTargetError;
+
+/// A context menu command, shown when right-clicking a user or message.
+pub struct ContextMenuCommand {
+    /// The name, which is shown in the menu
+    name: Box<str>,
+
+    /// The kind
+    kind: ContextMenuKind,
+
+    /// Whether this can only be used in age-restricted channels
+    nsfw: bool,
+
+    /// The name of the rate limit bucket of this command
+    bucket: Option<Box<str>>,
+
+    /// The main "process" func
+    on_process: OnProcessFutureFn,
+
+    /// The type ids and type names of the states the on_process hook needs
+    required_states: Box<[(TypeId, &'static str)]>,
+
+    /// Checks that must pass before this command is run
+    checks: Vec<ContextMenuCheckFn>,
+}
+
+impl ContextMenuCommand {
+    /// Get the name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the kind
+    pub fn kind(&self) -> ContextMenuKind {
+        self.kind
+    }
+
+    /// Whether this can only be used in age-restricted channels
+    pub fn nsfw(&self) -> bool {
+        self.nsfw
+    }
+
+    /// Get the name of the rate limit bucket of this command
+    pub fn bucket(&self) -> Option<&str> {
+        self.bucket.as_deref()
+    }
+
+    /// Fire the on_process hook
+    pub async fn fire_on_process(
+        &self,
+        ctx: Context,
+        interaction: CommandInteraction,
+        states: States,
+    ) -> Result<(), BoxError> {
+        (self.on_process)(ctx, interaction, states).await
+    }
+
+    /// Get the type ids and type names of the states the on_process hook needs
+    pub fn required_states(&self) -> &[(TypeId, &'static str)] {
+        &self.required_states
+    }
+
+    /// Get the checks
+    pub fn checks(&self) -> &[ContextMenuCheckFn] {
+        &self.checks
+    }
+
+    /// Register this context menu command
+    pub fn register(&self, command: CreateCommand) -> CreateCommand {
+        command.kind(self.kind.as_command_type()).nsfw(self.nsfw)
+    }
+}
+
+impl std::fmt::Debug for ContextMenuCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContextMenuCommand")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("nsfw", &self.nsfw)
+            .field("bucket", &self.bucket)
+            .field("on_process", &"<func>")
+            .finish()
+    }
+}
+
+/// A builder for a [`ContextMenuCommand`].
+pub struct ContextMenuCommandBuilder<'a> {
+    name: Option<&'a str>,
+    kind: Option<ContextMenuKind>,
+    nsfw: bool,
+    bucket: Option<&'a str>,
+
+    on_process: Option<OnProcessFutureFn>,
+    required_states: Vec<(TypeId, &'static str)>,
+    checks: Vec<ContextMenuCheckFn>,
+}
+
+impl<'a> ContextMenuCommandBuilder<'a> {
+    /// Make a new [`ContextMenuCommandBuilder`].
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            kind: None,
+            nsfw: false,
+            bucket: None,
+
+            on_process: None,
+            required_states: Vec::new(),
+            checks: Vec::new(),
+        }
+    }
+
+    /// The name, which is shown in the menu
+    pub fn name(&mut self, name: &'a str) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Whether this can only be used in age-restricted channels
+    pub fn nsfw(&mut self, nsfw: bool) -> &mut Self {
+        self.nsfw = nsfw;
+        self
+    }
+
+    /// The name of the rate limit bucket of this command.
+    ///
+    /// The bucket must be added to the framework.
+    pub fn bucket(&mut self, bucket: &'a str) -> &mut Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// The on_process hook.
+    ///
+    /// The kind of the command is decided by the target type,
+    /// use [`User`] for user commands and [`Message`] for message commands.
+    pub fn on_process<F, T>(&mut self, on_process: OnProcessFutureFnPtr<F, T>) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        T: ContextMenuTarget,
+    {
+        self.kind = Some(T::KIND);
+
+        // Trampoline so user does not have to box manually and resolve their target manually
+        self.on_process = Some(Box::new(move |ctx, interaction, _states| {
+            Box::pin(async move {
+                let target = T::from_interaction(&interaction).ok_or(MissingTargetError)?;
+                (on_process)(ctx, interaction, target).await
+            })
+        }));
+        self.required_states.clear();
+
+        self
+    }
+
+    /// The on_process hook, with shared state from the framework.
+    ///
+    /// `S` is a [`State`](crate::State) or a tuple of them.
+    /// The framework fails to build if a state was not registered.
+    pub fn on_process_with_state<F, T, S>(
+        &mut self,
+        on_process: OnProcessWithStateFutureFnPtr<F, T, S>,
+    ) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        T: ContextMenuTarget,
+        S: FromStates,
+    {
+        self.kind = Some(T::KIND);
+
+        // Trampoline so user does not have to box manually and resolve their target or extract their states manually
+        self.on_process = Some(Box::new(move |ctx, interaction, states| {
+            Box::pin(async move {
+                let target = T::from_interaction(&interaction).ok_or(MissingTargetError)?;
+                let states = S::from_states(&states)?;
+                (on_process)(ctx, interaction, target, states).await
+            })
+        }));
+        self.required_states = S::required_states();
+
+        self
+    }
+
+    /// Add a check to this command
+    pub fn check(&mut self, check: ContextMenuCheckFn) -> &mut Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Build the [`ContextMenuCommand`]
+    pub fn build(&mut self) -> Result<ContextMenuCommand, BuilderError> {
+        #[allow(clippy::or_fun_call)]
+        let name = self.name.take().ok_or(BuilderError::MissingField("name"))?;
+        #[allow(clippy::or_fun_call)]
+        let kind = self.kind.take().ok_or(BuilderError::MissingField("kind"))?;
+        #[allow(clippy::or_fun_call)]
+        let on_process = self
+            .on_process
+            .take()
+            .ok_or(BuilderError::MissingField("on_process"))?;
+
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return Err(BuilderError::Invalid(
+                "a context menu name must be between 1 and 32 chars",
+            ));
+        }
+
+        Ok(ContextMenuCommand {
+            name: name.into(),
+            kind,
+            nsfw: std::mem::take(&mut self.nsfw),
+            bucket: self.bucket.take().map(Into::into),
+
+            on_process,
+            required_states: std::mem::take(&mut self.required_states).into_boxed_slice(),
+            checks: std::mem::take(&mut self.checks),
+        })
+    }
+}
+
+impl std::fmt::Debug for ContextMenuCommandBuilder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContextMenuCommandBuilder")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("nsfw", &self.nsfw)
+            .field("bucket", &self.bucket)
+            .field("on_process", &self.on_process.as_ref().map(|_| "<func>"))
+            .finish()
+    }
+}
+
+impl Default for ContextMenuCommandBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
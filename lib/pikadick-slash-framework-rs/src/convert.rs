@@ -1,10 +1,22 @@
 use crate::{
+    ArgumentChoice,
     ArgumentParam,
     BuilderError,
 };
-use serenity::model::application::{
-    CommandDataOptionValue,
-    CommandInteraction,
+use serenity::model::{
+    application::{
+        CommandDataOption,
+        CommandDataOptionValue,
+        CommandDataResolved,
+        CommandInteraction,
+    },
+    channel::Attachment,
+    id::{
+        ChannelId,
+        GenericId,
+        RoleId,
+        UserId,
+    },
 };
 
 /// Error while converting from an interaction
@@ -31,6 +43,40 @@ pub enum ConvertError {
         /// The expected datatype
         expected: DataType,
     },
+
+    /// The value is not one of the allowed choices
+    #[error("unknown choice for '{name}', got '{value}'")]
+    UnknownChoice {
+        /// the name of the field
+        name: &'static str,
+        /// The value that was received
+        value: String,
+    },
+
+    /// The resolved data for a field is missing
+    #[error("missing resolved data for '{name}', expected '{expected}'")]
+    MissingResolvedData {
+        /// the name of the field
+        name: &'static str,
+        /// The expected datatype
+        expected: DataType,
+    },
+}
+
+/// Get the options that were passed to the subcommand that was invoked.
+///
+/// This walks through subcommand groups and subcommands.
+/// If no subcommand was invoked, the options are returned as-is.
+pub fn leaf_options(mut options: &[CommandDataOption]) -> &[CommandDataOption] {
+    while let Some(
+        CommandDataOptionValue::SubCommand(sub_options)
+        | CommandDataOptionValue::SubCommandGroup(sub_options),
+    ) = options.first().map(|option| &option.value)
+    {
+        options = sub_options;
+    }
+
+    options
 }
 
 /// A trait that allows converting from an application command interaction
@@ -55,7 +101,7 @@ impl FromOptions for () {
 }
 
 /// A datatype
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DataType {
     /// A string
     String,
@@ -65,6 +111,24 @@ pub enum DataType {
 
     /// Bool
     Boolean,
+
+    /// A user
+    User,
+
+    /// A channel
+    Channel,
+
+    /// A role
+    Role,
+
+    /// A user or a role
+    Mentionable,
+
+    /// A float
+    Number,
+
+    /// An uploaded file
+    Attachment,
 }
 
 impl DataType {
@@ -74,6 +138,12 @@ impl DataType {
             Self::String => "String",
             Self::Integer => "i64",
             Self::Boolean => "bool",
+            Self::User => "UserId",
+            Self::Channel => "ChannelId",
+            Self::Role => "RoleId",
+            Self::Mentionable => "GenericId",
+            Self::Number => "f64",
+            Self::Attachment => "Attachment",
         }
     }
 
@@ -85,6 +155,12 @@ impl DataType {
             CommandDataOptionValue::String(_) => Some(DataType::String),
             CommandDataOptionValue::Integer(_) => Some(DataType::Integer),
             CommandDataOptionValue::Boolean(_) => Some(DataType::Boolean),
+            CommandDataOptionValue::User(_) => Some(DataType::User),
+            CommandDataOptionValue::Channel(_) => Some(DataType::Channel),
+            CommandDataOptionValue::Role(_) => Some(DataType::Role),
+            CommandDataOptionValue::Mentionable(_) => Some(DataType::Mentionable),
+            CommandDataOptionValue::Number(_) => Some(DataType::Number),
+            CommandDataOptionValue::Attachment(_) => Some(DataType::Attachment),
             _ => None,
         }
     }
@@ -98,10 +174,14 @@ impl std::fmt::Display for DataType {
 
 /// Convert from an option value
 pub trait FromOptionValue: Sized {
-    /// Parse from an option value
+    /// Parse from an option value.
+    ///
+    /// `resolved` is the resolved data of the interaction,
+    /// for types that need more than an id.
     fn from_option_value(
         name: &'static str,
         option: &CommandDataOptionValue,
+        resolved: &CommandDataResolved,
     ) -> Result<Self, ConvertError>;
 
     /// The expected data type
@@ -114,12 +194,20 @@ pub trait FromOptionValue: Sized {
     fn get_missing_default() -> Option<Self> {
         None
     }
+
+    /// Get the fixed choices of this type.
+    ///
+    /// Returns an empty vec if any value of the expected data type is allowed.
+    fn get_choices() -> Vec<ArgumentChoice> {
+        Vec::new()
+    }
 }
 
 impl FromOptionValue for bool {
     fn from_option_value(
         name: &'static str,
         option: &CommandDataOptionValue,
+        _resolved: &CommandDataResolved,
     ) -> Result<Self, ConvertError> {
         let expected = Self::get_expected_data_type();
 
@@ -142,6 +230,7 @@ impl FromOptionValue for String {
     fn from_option_value(
         name: &'static str,
         option: &CommandDataOptionValue,
+        _resolved: &CommandDataResolved,
     ) -> Result<Self, ConvertError> {
         let expected = Self::get_expected_data_type();
 
@@ -160,6 +249,68 @@ impl FromOptionValue for String {
     }
 }
 
+/// Impl [`FromOptionValue`] for a type that is copied out of a single [`CommandDataOptionValue`] variant
+macro_rules! impl_from_option_value_copy {
+    ($ty:ty, $variant:ident, $data_type:ident) => {
+        impl FromOptionValue for $ty {
+            fn from_option_value(
+                name: &'static str,
+                option: &CommandDataOptionValue,
+                _resolved: &CommandDataResolved,
+            ) -> Result<Self, ConvertError> {
+                let expected = Self::get_expected_data_type();
+
+                match option {
+                    CommandDataOptionValue::$variant(v) => Ok(*v),
+                    t => Err(ConvertError::UnexpectedType {
+                        name,
+                        expected,
+                        actual: DataType::from_data_option_value(t),
+                    }),
+                }
+            }
+
+            fn get_expected_data_type() -> DataType {
+                DataType::$data_type
+            }
+        }
+    };
+}
+
+impl_from_option_value_copy!(i64, Integer, Integer);
+impl_from_option_value_copy!(f64, Number, Number);
+impl_from_option_value_copy!(UserId, User, User);
+impl_from_option_value_copy!(ChannelId, Channel, Channel);
+impl_from_option_value_copy!(RoleId, Role, Role);
+impl_from_option_value_copy!(GenericId, Mentionable, Mentionable);
+
+impl FromOptionValue for Attachment {
+    fn from_option_value(
+        name: &'static str,
+        option: &CommandDataOptionValue,
+        resolved: &CommandDataResolved,
+    ) -> Result<Self, ConvertError> {
+        let expected = Self::get_expected_data_type();
+
+        match option {
+            CommandDataOptionValue::Attachment(id) => resolved
+                .attachments
+                .get(id)
+                .cloned()
+                .ok_or(ConvertError::MissingResolvedData { name, expected }),
+            t => Err(ConvertError::UnexpectedType {
+                name,
+                expected,
+                actual: DataType::from_data_option_value(t),
+            }),
+        }
+    }
+
+    fn get_expected_data_type() -> DataType {
+        DataType::Attachment
+    }
+}
+
 impl<T> FromOptionValue for Option<T>
 where
     T: FromOptionValue,
@@ -167,8 +318,9 @@ where
     fn from_option_value(
         name: &'static str,
         option: &CommandDataOptionValue,
+        resolved: &CommandDataResolved,
     ) -> Result<Self, ConvertError> {
-        T::from_option_value(name, option).map(Some)
+        T::from_option_value(name, option, resolved).map(Some)
     }
 
     fn get_missing_default() -> Option<Self> {
@@ -178,4 +330,8 @@ where
     fn get_expected_data_type() -> DataType {
         T::get_expected_data_type()
     }
+
+    fn get_choices() -> Vec<ArgumentChoice> {
+        T::get_choices()
+    }
 }
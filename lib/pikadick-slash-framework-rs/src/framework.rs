@@ -1,14 +1,30 @@
 use crate::{
+    component::split_custom_id,
+    register::RegisterPlan,
     BoxError,
+    BoxFuture,
+    Bucket,
     BuilderError,
     CheckFn,
     Command,
+    CommandError,
+    ComponentCheckFn,
+    ComponentHandler,
+    ContextMenuCheckFn,
+    ContextMenuCommand,
+    ContextMenuKind,
     HelpCommand,
+    ModalCheckFn,
+    ModalHandler,
+    States,
 };
 use serenity::{
     builder::{
+        Builder,
+        CreateAutocompleteResponse,
         CreateCommand,
         CreateInteractionResponse,
+        CreateInteractionResponseFollowup,
         CreateInteractionResponseMessage,
     },
     client::Context,
@@ -16,20 +32,211 @@ use serenity::{
         application::{
             Command as ApplicationCommand,
             CommandInteraction,
+            ComponentInteraction,
             Interaction,
+            ModalInteraction,
+        },
+        channel::{
+            Channel,
+            MessageFlags,
+            PartialChannel,
+        },
+        id::{
+            ChannelId,
+            GuildId,
+            InteractionId,
+            UserId,
         },
-        prelude::GuildId,
     },
 };
 use std::{
+    any::{
+        Any,
+        TypeId,
+    },
     collections::HashMap,
+    future::Future,
     sync::Arc,
+    time::Duration,
 };
 use tracing::{
     info,
     warn,
 };
 
+/// The maximum number of choices Discord accepts in an autocomplete response
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
+/// The reply when an nsfw command, component, or context menu is used outside of an age-restricted channel
+const NSFW_CHANNEL_ONLY: &str = "This command can only be used in age-restricted channels";
+
+pub type OnErrorFuture = BoxFuture<'static, ()>;
+
+// Keep these types in sync.
+type OnErrorFutureFn =
+    Box<dyn Fn(Context, Interaction, CommandError) -> OnErrorFuture + Send + Sync>;
+type OnErrorFutureFnPtr<F> = fn(Context, Interaction, CommandError) -> F;
+
+// Keep these types in sync.
+type OnUnknownCommandFutureFn =
+    Box<dyn Fn(Context, CommandInteraction) -> OnErrorFuture + Send + Sync>;
+type OnUnknownCommandFutureFnPtr<F> = fn(Context, CommandInteraction) -> F;
+
+/// Reply to an interaction ephemerally.
+///
+/// This sends a followup if the interaction already responded or deferred.
+/// It takes the id and token so it works with every kind of interaction.
+async fn reply_ephemeral(ctx: &Context, id: InteractionId, token: &str, content: &str) {
+    let response = CreateInteractionResponseMessage::new()
+        .content(content)
+        .ephemeral(true);
+    let create_response_result = CreateInteractionResponse::Message(response)
+        .execute(&ctx.http, (id, token))
+        .await;
+
+    // This fails if the interaction was already acknowledged.
+    if create_response_result.is_err() {
+        // The first followup of a deferred interaction replaces the loading message,
+        // and takes on its visibility.
+        // Delete a public loading message first, so the reply stays ephemeral.
+        match ctx.http.get_original_interaction_response(token).await {
+            Ok(message) => {
+                let is_public_loading_message = message.flags.is_some_and(|flags| {
+                    flags.contains(MessageFlags::LOADING)
+                        && !flags.contains(MessageFlags::EPHEMERAL)
+                });
+                if is_public_loading_message {
+                    if let Err(error) = ctx.http.delete_original_interaction_response(token).await {
+                        warn!("failed to delete loading message: {error}");
+                    }
+                }
+            }
+            Err(error) => {
+                warn!("failed to get original response: {error}");
+            }
+        }
+
+        let followup = CreateInteractionResponseFollowup::new()
+            .content(content)
+            .ephemeral(true);
+        if let Err(error) = followup.execute(&ctx.http, (None, token)).await {
+            warn!("failed to reply to interaction: {error}");
+        }
+    }
+}
+
+/// Check if the channel of an interaction is age-restricted.
+///
+/// DMs are always allowed.
+/// Threads use the setting of their parent channel.
+/// This takes the fields of the interaction so it works with every kind of interaction.
+/// If the channel cannot be fetched, it is treated as not age-restricted to be safe.
+async fn is_nsfw_channel(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    channel: Option<&PartialChannel>,
+) -> bool {
+    if guild_id.is_none() {
+        return true;
+    }
+
+    let channel_id = channel
+        .filter(|channel| channel.thread_metadata.is_some())
+        .and_then(|channel| channel.parent_id)
+        .unwrap_or(channel_id);
+
+    match channel_id.to_channel(ctx).await {
+        Ok(Channel::Guild(channel)) => channel.nsfw,
+        Ok(_) => true,
+        Err(error) => {
+            warn!("failed to get channel: {error}");
+            false
+        }
+    }
+}
+
+/// Tell a user how long to wait until a bucket allows them to use a command again
+fn format_bucket_wait(wait: Duration) -> String {
+    // Round up so users are never told to wait 0 seconds
+    let wait_secs = wait.as_secs() + u64::from(wait.subsec_nanos() != 0);
+    format!("Try again in {wait_secs}s")
+}
+
+/// Find a bucket used by a command or its subcommands that is not in `buckets`.
+fn find_unknown_bucket<'a>(
+    command: &'a Command,
+    buckets: &HashMap<Box<str>, Bucket>,
+) -> Option<&'a str> {
+    if let Some(bucket) = command.bucket() {
+        if !buckets.contains_key(bucket) {
+            return Some(bucket);
+        }
+    }
+
+    command
+        .subcommands()
+        .iter()
+        .find_map(|subcommand| find_unknown_bucket(subcommand, buckets))
+}
+
+/// Find a state in `required_states` that is not in `states`.
+fn find_missing_required_state(
+    required_states: &[(TypeId, &'static str)],
+    states: &States,
+) -> Option<&'static str> {
+    required_states
+        .iter()
+        .find(|(type_id, _name)| !states.contains(*type_id))
+        .map(|(_type_id, name)| *name)
+}
+
+/// Find a state needed by a command, its arguments, or its subcommands that is not in `states`.
+fn find_missing_state(command: &Command, states: &States) -> Option<&'static str> {
+    let missing_state =
+        find_missing_required_state(command.required_states(), states).or_else(|| {
+            command.arguments().iter().find_map(|argument| {
+                find_missing_required_state(argument.required_states(), states)
+            })
+        });
+    if missing_state.is_some() {
+        return missing_state;
+    }
+
+    command
+        .subcommands()
+        .iter()
+        .find_map(|subcommand| find_missing_state(subcommand, states))
+}
+
+/// The default error handler.
+///
+/// This logs the error and tells the user ephemerally.
+pub async fn default_on_error(ctx: Context, interaction: Interaction, error: CommandError) {
+    let content = match &error {
+        CommandError::Convert(error) => {
+            warn!("{error}");
+            format!("Invalid arguments: {error}")
+        }
+        CommandError::Process(error) => {
+            warn!("{error}");
+            format!("The command failed: {error}")
+        }
+    };
+
+    reply_ephemeral(&ctx, interaction.id(), interaction.token(), &content).await;
+}
+
+/// The default unknown command handler.
+///
+/// This logs the command and tells the user ephemerally.
+pub async fn default_on_unknown_command(ctx: Context, interaction: CommandInteraction) {
+    let command_name = interaction.data.name.as_str();
+    warn!("unknown command \"{command_name}\"");
+
+    reply_ephemeral(&ctx, interaction.id, &interaction.token, "Unknown command").await;
+}
+
 /// A wrapper for [`BoxError`] that impls error
 struct WrapBoxError(BoxError);
 
@@ -71,60 +278,523 @@ impl std::fmt::Display for FmtOptionsHelper<'_> {
     }
 }
 
+/// Format a path of commands as a space-seperated list of names
+struct FmtCommandPath<'a>(&'a [&'a Command]);
+
+impl std::fmt::Display for FmtCommandPath<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, command) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", command.name())?;
+        }
+
+        Ok(())
+    }
+}
+
 /// A framework
 #[derive(Clone)]
 pub struct Framework {
     commands: Arc<HashMap<Box<str>, Command>>,
     help_command: Option<Arc<HelpCommand>>,
     checks: Arc<[CheckFn]>,
+    buckets: Arc<HashMap<Box<str>, Bucket>>,
+    states: States,
+
+    component_handlers: Arc<HashMap<Box<str>, ComponentHandler>>,
+    component_checks: Arc<[ComponentCheckFn]>,
+
+    modal_handlers: Arc<HashMap<Box<str>, ModalHandler>>,
+    modal_checks: Arc<[ModalCheckFn]>,
+
+    context_menu_commands: Arc<HashMap<(ContextMenuKind, Box<str>), ContextMenuCommand>>,
+    context_menu_checks: Arc<[ContextMenuCheckFn]>,
+
+    on_error: Arc<OnErrorFutureFn>,
+    on_unknown_command: Arc<OnUnknownCommandFutureFn>,
 }
 
 impl Framework {
+    /// Get the commands, by name
+    pub fn commands(&self) -> &HashMap<Box<str>, Command> {
+        &self.commands
+    }
+
+    /// Get the context menu commands
+    pub fn context_menu_commands(&self) -> impl Iterator<Item = &ContextMenuCommand> {
+        self.context_menu_commands.values()
+    }
+
+    /// Make the local definitions of all commands, for registration.
+    fn create_commands(&self) -> Vec<CreateCommand> {
+        let mut create_commands =
+            Vec::with_capacity(self.commands.len() + self.context_menu_commands.len() + 1);
+        for framework_command in self.commands.values() {
+            let mut command_builder = CreateCommand::new(framework_command.name());
+            command_builder = framework_command.register(command_builder);
+            create_commands.push(command_builder);
+        }
+        if let Some(framework_command) = self.help_command.as_deref() {
+            let mut command_builder = CreateCommand::new("help");
+            command_builder = framework_command.register(command_builder);
+            create_commands.push(command_builder);
+        }
+        for framework_command in self.context_menu_commands.values() {
+            let mut command_builder = CreateCommand::new(framework_command.name());
+            command_builder = framework_command.register(command_builder);
+            create_commands.push(command_builder);
+        }
+
+        create_commands
+    }
+
     /// Register the framework.
     ///
+    /// This fetches the registered commands and only overwrites them if they differ from the local commands.
+    /// Commands that no longer exist locally are removed.
+    ///
     /// `test_guild_id` is an optional guild where the commands will be registered as guild commands,
     /// so they update faster for testing purposes.
+    ///
+    /// If `dry_run` is true, the planned changes are only logged.
     pub async fn register(
         &self,
         ctx: Context,
         test_guild_id: Option<GuildId>,
+        dry_run: bool,
     ) -> Result<(), serenity::Error> {
-        for framework_command in self.commands.values() {
-            let mut command_builder = CreateCommand::new(framework_command.name());
-            command_builder = framework_command.register(command_builder);
-            ApplicationCommand::create_global_command(&ctx.http, command_builder).await?;
-        }
+        let create_commands = self.create_commands();
 
-        if let Some(framework_command) = self.help_command.as_deref() {
-            let mut command_builder = CreateCommand::new("help");
-            command_builder = framework_command.register(command_builder);
-            ApplicationCommand::create_global_command(&ctx.http, command_builder).await?;
+        let global_commands =
+            ApplicationCommand::get_global_commands_with_localizations(&ctx.http).await?;
+        let plan = RegisterPlan::new(&create_commands, &global_commands)?;
+        if plan.is_empty() {
+            info!("global commands are up to date");
+        } else if dry_run {
+            info!("dry run, would update global commands: {plan}");
+        } else {
+            info!("updating global commands: {plan}");
+            ApplicationCommand::set_global_commands(&ctx.http, create_commands.clone()).await?;
         }
 
         if let Some(guild_id) = test_guild_id {
-            let mut create_commands = Vec::new();
-            for framework_command in self.commands.values() {
-                let mut command_builder = CreateCommand::new(framework_command.name());
-                command_builder = framework_command.register(command_builder);
-                create_commands.push(command_builder);
-            }
-            if let Some(framework_command) = self.help_command.as_deref() {
-                let mut command_builder = CreateCommand::new("help");
-                command_builder = framework_command.register(command_builder);
-                create_commands.push(command_builder);
+            let guild_commands = guild_id.get_commands_with_localizations(&ctx.http).await?;
+            let plan = RegisterPlan::new(&create_commands, &guild_commands)?;
+            if plan.is_empty() {
+                info!("guild commands for {guild_id} are up to date");
+            } else if dry_run {
+                info!("dry run, would update guild commands for {guild_id}: {plan}");
+            } else {
+                info!("updating guild commands for {guild_id}: {plan}");
+                guild_id.set_commands(&ctx.http, create_commands).await?;
             }
-
-            GuildId::set_commands(guild_id, &ctx.http, create_commands).await?;
         }
 
         Ok(())
     }
 
+    /// Get the commands on the path of a command name, like "tic-tac-toe::play".
+    fn get_command_path(&self, name: &str) -> Option<Vec<&Command>> {
+        let mut names = name.split("::");
+        let mut command = self.commands.get(names.next()?)?;
+        let mut command_path = vec![command];
+        for name in names {
+            command = command.subcommand(name)?;
+            command_path.push(command);
+        }
+
+        Some(command_path)
+    }
+
+    /// Use the buckets with the given names.
+    ///
+    /// Every bucket is checked before any are used,
+    /// so a limited subcommand does not use up the bucket of its parent.
+    fn take_buckets<'a, I>(
+        &self,
+        names: I,
+        user_id: UserId,
+        channel_id: ChannelId,
+        guild_id: Option<GuildId>,
+    ) -> Result<(), Duration>
+    where
+        I: Iterator<Item = &'a str> + Clone,
+    {
+        let mut buckets = names.map(|name| &self.buckets[name]);
+        for bucket in buckets.clone() {
+            bucket.check(user_id, channel_id, guild_id)?;
+        }
+
+        buckets.try_for_each(|bucket| bucket.take(user_id, channel_id, guild_id))
+    }
+
+    /// Use the buckets of the command of a component or modal handler.
+    fn take_handler_buckets(
+        &self,
+        command: Option<&str>,
+        user_id: UserId,
+        channel_id: ChannelId,
+        guild_id: Option<GuildId>,
+    ) -> Result<(), Duration> {
+        let command_path = command
+            .and_then(|command| self.get_command_path(command))
+            .unwrap_or_default();
+        self.take_buckets(
+            command_path.iter().filter_map(|command| command.bucket()),
+            user_id,
+            channel_id,
+            guild_id,
+        )
+    }
+
     /// Process an interaction create event
     pub async fn process_interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
-            self.process_interaction_create_application_command(ctx, command)
+        match interaction {
+            Interaction::Command(command) => {
+                self.process_interaction_create_application_command(ctx, command)
+                    .await
+            }
+            Interaction::Autocomplete(command) => {
+                self.process_interaction_create_autocomplete(ctx, command)
+                    .await
+            }
+            Interaction::Component(component) => {
+                self.process_interaction_create_component(ctx, component)
+                    .await
+            }
+            Interaction::Modal(modal) => self.process_interaction_create_modal(ctx, modal).await,
+            _ => {}
+        }
+    }
+
+    #[tracing::instrument(skip(self, ctx, modal), fields(id = %modal.id, author = %modal.user.id, guild = ?modal.guild_id, channel_id = %modal.channel_id))]
+    async fn process_interaction_create_modal(&self, ctx: Context, modal: ModalInteraction) {
+        let (prefix, _state) = split_custom_id(&modal.data.custom_id);
+        let handler = match self.modal_handlers.get(prefix) {
+            Some(handler) => handler,
+            None => {
+                // TODO: Unknown modal handler
+                warn!("unknown modal prefix \"{prefix}\"");
+                return;
+            }
+        };
+
+        // TODO: Consider making parallel
+        let mut check_result = Ok(());
+        for check in self.modal_checks.iter().chain(handler.checks().iter()) {
+            check_result = check_result.and(check(&ctx, &modal, handler).await);
+        }
+
+        match check_result {
+            Ok(()) => {
+                if let Err(wait) = self.take_handler_buckets(
+                    handler.command(),
+                    modal.user.id,
+                    modal.channel_id,
+                    modal.guild_id,
+                ) {
+                    let content = format_bucket_wait(wait);
+                    reply_ephemeral(&ctx, modal.id, &modal.token, &content).await;
+                    return;
+                }
+
+                info!(
+                    "processing modal \"{}\", custom_id=\"{}\"",
+                    handler.prefix(),
+                    modal.data.custom_id
+                );
+                if let Err(error) = handler
+                    .fire_on_process(ctx.clone(), modal.clone(), self.states.clone())
+                    .await
+                {
+                    (self.on_error)(ctx, Interaction::Modal(modal), error.into()).await;
+                }
+            }
+            Err(error) => {
+                let content = error
+                    .user
+                    .as_deref()
+                    .unwrap_or("check failed for unknown reason");
+
+                if let Some(log) = error.log {
+                    warn!("{log}");
+                }
+
+                reply_ephemeral(&ctx, modal.id, &modal.token, content).await;
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, ctx, component), fields(id = %component.id, author = %component.user.id, guild = ?component.guild_id, channel_id = %component.channel_id))]
+    async fn process_interaction_create_component(
+        &self,
+        ctx: Context,
+        component: ComponentInteraction,
+    ) {
+        let (prefix, _state) = split_custom_id(&component.data.custom_id);
+        let handler = match self.component_handlers.get(prefix) {
+            Some(handler) => handler,
+            None => {
+                // TODO: Unknown component handler
+                warn!("unknown component prefix \"{prefix}\"");
+                return;
+            }
+        };
+
+        if handler.nsfw()
+            && !is_nsfw_channel(
+                &ctx,
+                component.guild_id,
+                component.channel_id,
+                component.channel.as_ref(),
+            )
+            .await
+        {
+            reply_ephemeral(&ctx, component.id, &component.token, NSFW_CHANNEL_ONLY).await;
+            return;
+        }
+
+        // TODO: Consider making parallel
+        let mut check_result = Ok(());
+        for check in self.component_checks.iter().chain(handler.checks().iter()) {
+            check_result = check_result.and(check(&ctx, &component, handler).await);
+        }
+
+        match check_result {
+            Ok(()) => {
+                if let Err(wait) = self.take_handler_buckets(
+                    handler.command(),
+                    component.user.id,
+                    component.channel_id,
+                    component.guild_id,
+                ) {
+                    let content = format_bucket_wait(wait);
+                    reply_ephemeral(&ctx, component.id, &component.token, &content).await;
+                    return;
+                }
+
+                info!(
+                    "processing component \"{}\", custom_id=\"{}\"",
+                    handler.prefix(),
+                    component.data.custom_id
+                );
+                if let Err(error) = handler
+                    .fire_on_process(ctx.clone(), component.clone(), self.states.clone())
+                    .await
+                {
+                    (self.on_error)(ctx, Interaction::Component(component), error.into()).await;
+                }
+            }
+            Err(error) => {
+                let content = error
+                    .user
+                    .as_deref()
+                    .unwrap_or("check failed for unknown reason");
+
+                if let Some(log) = error.log {
+                    warn!("{log}");
+                }
+
+                // Only tell the user who clicked, the message is shared.
+                reply_ephemeral(&ctx, component.id, &component.token, content).await;
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, ctx, command), fields(id = %command.id, author = %command.user.id, guild = ?command.guild_id, channel_id = %command.channel_id))]
+    async fn process_interaction_create_autocomplete(
+        &self,
+        ctx: Context,
+        command: CommandInteraction,
+    ) {
+        let (focused_name, value) = match command.data.autocomplete() {
+            Some(option) => (option.name.to_string(), option.value.to_string()),
+            None => {
+                warn!("autocomplete interaction has no focused option");
+                return;
+            }
+        };
+
+        let result = if command.data.name.as_str() == "help" {
+            let help_command = match self.help_command.as_ref() {
+                Some(help_command) => help_command,
+                None => return,
+            };
+            let argument = match help_command
+                .arguments()
+                .iter()
+                .find(|argument| argument.name() == focused_name)
+            {
+                Some(argument) => argument,
+                None => {
+                    warn!("unknown help argument \"{focused_name}\"");
+                    return;
+                }
+            };
+
+            help_command
+                .fire_on_autocomplete(
+                    ctx.clone(),
+                    command.clone(),
+                    self.commands.clone(),
+                    argument,
+                    value,
+                    self.states.clone(),
+                )
+                .await
+        } else {
+            let command_name = command.data.name.as_str();
+            let command_path = match self
+                .commands
+                .get(command_name)
+                .and_then(|root_command| root_command.resolve_path(&command.data.options))
+            {
+                Some(command_path) => command_path,
+                None => {
+                    warn!("unknown command \"{command_name}\" for autocomplete");
+                    return;
+                }
+            };
+            let framework_command = *command_path
+                .last()
+                .expect("command path should contain at least the root command");
+
+            // Don't leak suggestions for nsfw commands
+            if command_path.iter().any(|command| command.nsfw())
+                && !is_nsfw_channel(
+                    &ctx,
+                    command.guild_id,
+                    command.channel_id,
+                    command.channel.as_ref(),
+                )
+                .await
+            {
+                let response = CreateAutocompleteResponse::new();
+                if let Err(error) = command
+                    .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+                    .await
+                {
+                    warn!("{error}");
+                }
+                return;
+            }
+            let argument = match framework_command
+                .arguments()
+                .iter()
+                .find(|argument| argument.name() == focused_name)
+            {
+                Some(argument) => argument,
+                None => {
+                    warn!("unknown argument \"{focused_name}\" for command \"{command_name}\"");
+                    return;
+                }
+            };
+
+            argument
+                .fire_on_autocomplete(ctx.clone(), command.clone(), value, self.states.clone())
                 .await
+        };
+
+        let mut choices = match result.map_err(WrapBoxError::new) {
+            Ok(choices) => choices,
+            Err(error) => {
+                warn!("{error}");
+                // Still respond, so the user is not stuck with a loading indicator.
+                Vec::new()
+            }
+        };
+        choices.truncate(MAX_AUTOCOMPLETE_CHOICES);
+
+        let response = CreateAutocompleteResponse::new().set_choices(choices);
+        if let Err(error) = command
+            .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+            .await
+        {
+            warn!("{error}");
+        }
+    }
+
+    async fn process_interaction_create_context_menu(
+        &self,
+        ctx: Context,
+        command: CommandInteraction,
+        kind: ContextMenuKind,
+    ) {
+        let key = (kind, Box::<str>::from(command.data.name.as_str()));
+        let framework_command = match self.context_menu_commands.get(&key) {
+            Some(framework_command) => framework_command,
+            None => {
+                (self.on_unknown_command)(ctx, command).await;
+                return;
+            }
+        };
+
+        if framework_command.nsfw()
+            && !is_nsfw_channel(
+                &ctx,
+                command.guild_id,
+                command.channel_id,
+                command.channel.as_ref(),
+            )
+            .await
+        {
+            reply_ephemeral(&ctx, command.id, &command.token, NSFW_CHANNEL_ONLY).await;
+            return;
+        }
+
+        // TODO: Consider making parallel
+        let mut check_result = Ok(());
+        for check in self
+            .context_menu_checks
+            .iter()
+            .chain(framework_command.checks().iter())
+        {
+            check_result = check_result.and(check(&ctx, &command, framework_command).await);
+        }
+
+        match check_result {
+            Ok(()) => {
+                if let Err(wait) = self.take_buckets(
+                    framework_command.bucket().into_iter(),
+                    command.user.id,
+                    command.channel_id,
+                    command.guild_id,
+                ) {
+                    let content = format_bucket_wait(wait);
+                    reply_ephemeral(&ctx, command.id, &command.token, &content).await;
+                    return;
+                }
+
+                info!(
+                    "processing {kind:?} context menu command \"{}\"",
+                    framework_command.name()
+                );
+                if let Err(error) = framework_command
+                    .fire_on_process(ctx.clone(), command.clone(), self.states.clone())
+                    .await
+                {
+                    (self.on_error)(
+                        ctx,
+                        Interaction::Command(command),
+                        CommandError::from(error),
+                    )
+                    .await;
+                }
+            }
+            Err(error) => {
+                let content = error
+                    .user
+                    .as_deref()
+                    .unwrap_or("check failed for unknown reason");
+
+                if let Some(log) = error.log {
+                    warn!("{log}");
+                }
+
+                reply_ephemeral(&ctx, command.id, &command.token, content).await;
+            }
         }
     }
 
@@ -134,6 +804,12 @@ impl Framework {
         ctx: Context,
         command: CommandInteraction,
     ) {
+        if let Some(kind) = ContextMenuKind::from_command_type(command.data.kind) {
+            self.process_interaction_create_context_menu(ctx, command, kind)
+                .await;
+            return;
+        }
+
         if command.data.name.as_str() == "help" {
             // Keep comments
             #[allow(clippy::single_match)]
@@ -144,12 +820,15 @@ impl Framework {
                         FmtOptionsHelper(&command)
                     );
                     if let Err(error) = framework_command
-                        .fire_on_process(ctx, command, self.commands.clone())
+                        .fire_on_process(ctx.clone(), command.clone(), self.commands.clone())
                         .await
-                        .map_err(WrapBoxError::new)
                     {
-                        // TODO: handle error with handler
-                        warn!("{error}");
+                        (self.on_error)(
+                            ctx,
+                            Interaction::Command(command),
+                            CommandError::from(error),
+                        )
+                        .await;
                     }
                 }
                 None => {
@@ -161,36 +840,78 @@ impl Framework {
             return;
         }
 
-        let framework_command = match self.commands.get(command.data.name.as_str()) {
+        let root_command = match self.commands.get(command.data.name.as_str()) {
             Some(command) => command,
             None => {
-                // TODO: Unknown command handler
+                (self.on_unknown_command)(ctx, command).await;
+                return;
+            }
+        };
+
+        let command_path = match root_command.resolve_path(&command.data.options) {
+            Some(command_path) => command_path,
+            None => {
                 let command_name = command.data.name.as_str();
-                warn!("unknown command \"{command_name}\"");
+                warn!("unknown subcommand for command \"{command_name}\"");
+                (self.on_unknown_command)(ctx, command).await;
                 return;
             }
         };
+        let framework_command = *command_path
+            .last()
+            .expect("command path should contain at least the root command");
+
+        if command_path.iter().any(|command| command.nsfw())
+            && !is_nsfw_channel(
+                &ctx,
+                command.guild_id,
+                command.channel_id,
+                command.channel.as_ref(),
+            )
+            .await
+        {
+            reply_ephemeral(&ctx, command.id, &command.token, NSFW_CHANNEL_ONLY).await;
+            return;
+        }
 
         // TODO: Consider making parallel
         let mut check_result = Ok(());
-        for check in self.checks.iter().chain(framework_command.checks().iter()) {
+        let command_checks = command_path
+            .iter()
+            .flat_map(|command| command.checks().iter());
+        for check in self.checks.iter().chain(command_checks) {
             check_result = check_result.and(check(&ctx, &command, framework_command).await);
         }
 
         match check_result {
             Ok(()) => {
-                let command_name = framework_command.name();
+                let bucket_result = self.take_buckets(
+                    command_path.iter().filter_map(|command| command.bucket()),
+                    command.user.id,
+                    command.channel_id,
+                    command.guild_id,
+                );
+                if let Err(wait) = bucket_result {
+                    let content = format_bucket_wait(wait);
+                    reply_ephemeral(&ctx, command.id, &command.token, &content).await;
+                    return;
+                }
+
+                let command_name = FmtCommandPath(&command_path);
                 info!(
                     "processing command \"{command_name}\", options={}",
                     FmtOptionsHelper(&command)
                 );
                 if let Err(error) = framework_command
-                    .fire_on_process(ctx, command)
+                    .fire_on_process(ctx.clone(), command.clone(), self.states.clone())
                     .await
-                    .map_err(WrapBoxError::new)
                 {
-                    // TODO: handle error with handler
-                    warn!("{error}");
+                    (self.on_error)(
+                        ctx,
+                        Interaction::Command(command),
+                        CommandError::from(error),
+                    )
+                    .await;
                 }
             }
             Err(error) => {
@@ -219,6 +940,11 @@ impl std::fmt::Debug for Framework {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Framework")
             .field("commands", &self.commands)
+            .field("buckets", &self.buckets)
+            .field("states", &self.states)
+            .field("component_handlers", &self.component_handlers)
+            .field("modal_handlers", &self.modal_handlers)
+            .field("context_menu_commands", &self.context_menu_commands)
             .finish()
     }
 }
@@ -228,6 +954,20 @@ pub struct FrameworkBuilder {
     commands: HashMap<Box<str>, Command>,
     help_command: Option<HelpCommand>,
     checks: Vec<CheckFn>,
+    buckets: HashMap<Box<str>, Bucket>,
+    states: HashMap<TypeId, (&'static str, Arc<dyn Any + Send + Sync>)>,
+
+    component_handlers: HashMap<Box<str>, ComponentHandler>,
+    component_checks: Vec<ComponentCheckFn>,
+
+    modal_handlers: HashMap<Box<str>, ModalHandler>,
+    modal_checks: Vec<ModalCheckFn>,
+
+    context_menu_commands: HashMap<(ContextMenuKind, Box<str>), ContextMenuCommand>,
+    context_menu_checks: Vec<ContextMenuCheckFn>,
+
+    on_error: Option<OnErrorFutureFn>,
+    on_unknown_command: Option<OnUnknownCommandFutureFn>,
 
     error: Option<BuilderError>,
 }
@@ -239,6 +979,20 @@ impl FrameworkBuilder {
             commands: HashMap::new(),
             help_command: None,
             checks: Vec::new(),
+            buckets: HashMap::new(),
+            states: HashMap::new(),
+
+            component_handlers: HashMap::new(),
+            component_checks: Vec::new(),
+
+            modal_handlers: HashMap::new(),
+            modal_checks: Vec::new(),
+
+            context_menu_commands: HashMap::new(),
+            context_menu_checks: Vec::new(),
+
+            on_error: None,
+            on_unknown_command: None,
 
             error: None,
         }
@@ -296,17 +1050,249 @@ impl FrameworkBuilder {
         self
     }
 
+    /// Add a rate limit bucket that commands can use by name
+    pub fn bucket(&mut self, name: &str, bucket: Bucket) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let name: Box<str> = name.into();
+
+        // Don't overwrite buckets
+        if self.buckets.contains_key(&name) {
+            self.error = Some(BuilderError::Duplicate(name));
+            return self;
+        }
+
+        self.buckets.insert(name, bucket);
+
+        self
+    }
+
+    /// Add shared state that commands can receive as a [`State`](crate::State).
+    ///
+    /// There can only be one state of each type.
+    pub fn state<T>(&mut self, state: T) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+    {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let name = std::any::type_name::<T>();
+
+        // Don't overwrite states
+        if self.states.contains_key(&TypeId::of::<T>()) {
+            self.error = Some(BuilderError::Duplicate(name.into()));
+            return self;
+        }
+
+        self.states
+            .insert(TypeId::of::<T>(), (name, Arc::new(state)));
+
+        self
+    }
+
+    /// Add a component handler
+    pub fn component_handler(&mut self, handler: ComponentHandler) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let prefix: Box<str> = handler.prefix().into();
+
+        // Don't overwrite handlers
+        if self.component_handlers.contains_key(&prefix) {
+            self.error = Some(BuilderError::Duplicate(prefix));
+            return self;
+        }
+
+        self.component_handlers.insert(prefix, handler);
+
+        self
+    }
+
+    /// Add a check for component handlers
+    pub fn component_check(&mut self, check: ComponentCheckFn) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.component_checks.push(check);
+        self
+    }
+
+    /// Add a modal handler
+    pub fn modal_handler(&mut self, handler: ModalHandler) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let prefix: Box<str> = handler.prefix().into();
+
+        // Don't overwrite handlers
+        if self.modal_handlers.contains_key(&prefix) {
+            self.error = Some(BuilderError::Duplicate(prefix));
+            return self;
+        }
+
+        self.modal_handlers.insert(prefix, handler);
+
+        self
+    }
+
+    /// Add a check for modal handlers
+    pub fn modal_check(&mut self, check: ModalCheckFn) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.modal_checks.push(check);
+        self
+    }
+
+    /// Add a context menu command
+    pub fn context_menu_command(&mut self, command: ContextMenuCommand) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let key = (command.kind(), Box::<str>::from(command.name()));
+
+        // Don't overwrite commands.
+        // User and message commands may share a name.
+        if self.context_menu_commands.contains_key(&key) {
+            self.error = Some(BuilderError::Duplicate(key.1));
+            return self;
+        }
+
+        self.context_menu_commands.insert(key, command);
+
+        self
+    }
+
+    /// Add a check for context menu commands
+    pub fn context_menu_check(&mut self, check: ContextMenuCheckFn) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.context_menu_checks.push(check);
+        self
+    }
+
+    /// The on_error hook.
+    ///
+    /// This is called when a command, component handler, or modal handler fails.
+    /// If this is not set, [`default_on_error`] is used.
+    pub fn on_error<F>(&mut self, on_error: OnErrorFutureFnPtr<F>) -> &mut Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        // Trampoline so user does not have to box manually
+        self.on_error = Some(Box::new(move |ctx, interaction, error| {
+            Box::pin((on_error)(ctx, interaction, error))
+        }));
+
+        self
+    }
+
+    /// The on_unknown_command hook.
+    ///
+    /// This is called when a command or subcommand is not registered with this framework.
+    /// If this is not set, [`default_on_unknown_command`] is used.
+    pub fn on_unknown_command<F>(
+        &mut self,
+        on_unknown_command: OnUnknownCommandFutureFnPtr<F>,
+    ) -> &mut Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        // Trampoline so user does not have to box manually
+        self.on_unknown_command = Some(Box::new(move |ctx, interaction| {
+            Box::pin((on_unknown_command)(ctx, interaction))
+        }));
+
+        self
+    }
+
     /// Build a framework
     pub fn build(&mut self) -> Result<Framework, BuilderError> {
         if let Some(error) = self.error.take() {
             return Err(error);
         }
 
+        let unknown_bucket = self
+            .commands
+            .values()
+            .find_map(|command| find_unknown_bucket(command, &self.buckets))
+            .or_else(|| {
+                self.context_menu_commands
+                    .values()
+                    .filter_map(|command| command.bucket())
+                    .find(|bucket| !self.buckets.contains_key(*bucket))
+            });
+        if let Some(bucket) = unknown_bucket {
+            return Err(BuilderError::UnknownBucket(bucket.into()));
+        }
+
+        let states = States::new(std::mem::take(&mut self.states));
+        let missing_state = self
+            .commands
+            .values()
+            .find_map(|command| find_missing_state(command, &states))
+            .or_else(|| {
+                self.help_command.as_ref().and_then(|help_command| {
+                    help_command.arguments().iter().find_map(|argument| {
+                        find_missing_required_state(argument.required_states(), &states)
+                    })
+                })
+            })
+            .or_else(|| {
+                self.component_handlers.values().find_map(|handler| {
+                    find_missing_required_state(handler.required_states(), &states)
+                })
+            })
+            .or_else(|| {
+                self.modal_handlers.values().find_map(|handler| {
+                    find_missing_required_state(handler.required_states(), &states)
+                })
+            })
+            .or_else(|| {
+                self.context_menu_commands.values().find_map(|command| {
+                    find_missing_required_state(command.required_states(), &states)
+                })
+            });
+        if let Some(state) = missing_state {
+            return Err(BuilderError::MissingState(state));
+        }
+
         Ok(Framework {
             commands: Arc::new(std::mem::take(&mut self.commands)),
             help_command: self.help_command.take().map(Arc::new),
 
             checks: std::mem::take(&mut self.checks).into(),
+            buckets: Arc::new(std::mem::take(&mut self.buckets)),
+            states,
+
+            component_handlers: Arc::new(std::mem::take(&mut self.component_handlers)),
+            component_checks: std::mem::take(&mut self.component_checks).into(),
+
+            modal_handlers: Arc::new(std::mem::take(&mut self.modal_handlers)),
+            modal_checks: std::mem::take(&mut self.modal_checks).into(),
+
+            context_menu_commands: Arc::new(std::mem::take(&mut self.context_menu_commands)),
+            context_menu_checks: std::mem::take(&mut self.context_menu_checks).into(),
+
+            on_error: Arc::new(self.on_error.take().unwrap_or_else(|| {
+                Box::new(|ctx, interaction, error| {
+                    Box::pin(default_on_error(ctx, interaction, error))
+                })
+            })),
+            on_unknown_command: Arc::new(self.on_unknown_command.take().unwrap_or_else(|| {
+                Box::new(|ctx, interaction| Box::pin(default_on_unknown_command(ctx, interaction)))
+            })),
         })
     }
 }
@@ -315,6 +1301,10 @@ impl std::fmt::Debug for FrameworkBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FrameworkBuilder")
             .field("commands", &self.commands)
+            .field("buckets", &self.buckets)
+            .field("component_handlers", &self.component_handlers)
+            .field("modal_handlers", &self.modal_handlers)
+            .field("context_menu_commands", &self.context_menu_commands)
             .finish()
     }
 }
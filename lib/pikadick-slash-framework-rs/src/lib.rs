@@ -1,16 +1,41 @@
 mod argument;
+mod bucket;
 mod check;
 mod command;
+mod component;
+mod context_menu;
 mod convert;
 mod framework;
+mod locale;
+mod modal;
+mod register;
+mod response;
+mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+// Let derives refer to this crate by name in tests
+#[cfg(test)]
+extern crate self as pikadick_slash_framework;
 
 pub use self::{
     argument::{
+        ArgumentChoice,
         ArgumentParam,
         ArgumentParamBuilder,
+        ChoiceValue,
+        OnAutocompleteFuture,
+    },
+    bucket::{
+        Bucket,
+        BucketBuilder,
+        BucketScope,
     },
     check::{
         CheckFn,
+        ComponentCheckFn,
+        ContextMenuCheckFn,
+        ModalCheckFn,
         Reason,
     },
     command::{
@@ -20,18 +45,70 @@ pub use self::{
         HelpCommandBuilder,
         OnProcessFuture,
     },
+    component::{
+        create_custom_id,
+        ComponentHandler,
+        ComponentHandlerBuilder,
+        ComponentState,
+        OnComponentProcessFuture,
+        MAX_CUSTOM_ID_LEN,
+    },
+    context_menu::{
+        ContextMenuCommand,
+        ContextMenuCommandBuilder,
+        ContextMenuKind,
+        ContextMenuTarget,
+        MissingTargetError,
+        OnContextMenuProcessFuture,
+    },
     convert::{
+        leaf_options,
         ConvertError,
         DataType,
         FromOptionValue,
+        FromOptions,
     },
     framework::{
+        default_on_error,
+        default_on_unknown_command,
         Framework,
         FrameworkBuilder,
+        OnErrorFuture,
+    },
+    locale::{
+        InteractionLocale,
+        Translations,
+        DEFAULT_LOCALE,
     },
+    modal::{
+        create_modal,
+        FromModal,
+        FromModalValue,
+        ModalHandler,
+        ModalHandlerBuilder,
+        OnModalProcessFuture,
+        MAX_MODAL_INPUTS,
+    },
+    register::RegisterPlan,
+    response::{
+        RespondableInteraction,
+        Response,
+        ResponseMessage,
+        ResponseState,
+        DEFER_AFTER,
+    },
+    state::{
+        FromStates,
+        MissingStateError,
+        State,
+        States,
+    },
+};
+pub use pikadick_slash_framework_derive::{
+    FromModal,
+    FromOptionValue,
+    FromOptions,
 };
-pub use crate::convert::FromOptions;
-pub use pikadick_slash_framework_derive::FromOptions;
 use std::{
     future::Future,
     pin::Pin,
@@ -55,4 +132,37 @@ pub enum BuilderError {
     /// Something was duplicated
     #[error("duplicate for key '{0}'")]
     Duplicate(Box<str>),
+
+    /// The builder was configured in a way that is not valid
+    #[error("{0}")]
+    Invalid(&'static str),
+
+    /// A command uses a bucket that does not exist
+    #[error("unknown bucket '{0}'")]
+    UnknownBucket(Box<str>),
+
+    /// A command needs a state that was not added
+    #[error("missing state '{0}'")]
+    MissingState(&'static str),
+}
+
+/// An error that occured while running a command
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    /// The arguments of the command could not be parsed
+    #[error("invalid arguments")]
+    Convert(#[from] ConvertError),
+
+    /// The command returned an error
+    #[error("{0}")]
+    Process(BoxError),
+}
+
+impl From<BoxError> for CommandError {
+    fn from(error: BoxError) -> Self {
+        match error.downcast::<ConvertError>() {
+            Ok(error) => Self::Convert(*error),
+            Err(error) => Self::Process(error),
+        }
+    }
 }
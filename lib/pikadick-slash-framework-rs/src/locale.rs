@@ -0,0 +1,143 @@
+use serenity::model::application::{
+    CommandInteraction,
+    ComponentInteraction,
+    ModalInteraction,
+};
+use std::collections::HashMap;
+
+/// The locale that strings fall back to
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// An interaction with the locale of the user that sent it
+pub trait InteractionLocale {
+    /// Get the locale of the user
+    fn locale(&self) -> &str;
+}
+
+impl InteractionLocale for CommandInteraction {
+    fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+impl InteractionLocale for ComponentInteraction {
+    fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+impl InteractionLocale for ModalInteraction {
+    fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+/// Translated strings, by locale and key.
+///
+/// Locales use Discord's locale codes, like "en-US" or "de".
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    locales: HashMap<Box<str>, HashMap<Box<str>, Box<str>>>,
+}
+
+impl Translations {
+    /// Make a new, empty [`Translations`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add strings for a locale.
+    ///
+    /// Existing strings with the same key are overwritten.
+    pub fn add_locale<I, K, V>(&mut self, locale: &str, strings: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Box<str>>,
+        V: Into<Box<str>>,
+    {
+        self.locales
+            .entry(locale.into())
+            .or_default()
+            .extend(strings.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Get the locales that have strings
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.keys().map(|locale| &**locale)
+    }
+
+    /// Get a string for a locale, without falling back.
+    pub fn get_exact(&self, locale: &str, key: &str) -> Option<&str> {
+        self.locales.get(locale)?.get(key).map(|value| &**value)
+    }
+
+    /// Get a string for a locale.
+    ///
+    /// This falls back to another locale with the same language,
+    /// then to [`DEFAULT_LOCALE`], then to the key itself.
+    pub fn get<'a>(&'a self, locale: &str, key: &'a str) -> &'a str {
+        if let Some(value) = self.get_exact(locale, key) {
+            return value;
+        }
+
+        let language = get_language(locale);
+        let same_language = self
+            .locales
+            .iter()
+            .filter(|(other, _)| get_language(other) == language)
+            .filter_map(|(other, strings)| Some((other, strings.get(key)?)))
+            .min_by_key(|(other, _)| *other)
+            .map(|(_, value)| &**value);
+        if let Some(value) = same_language {
+            return value;
+        }
+
+        self.get_exact(DEFAULT_LOCALE, key).unwrap_or(key)
+    }
+
+    /// Get a string in the locale of the user of an interaction.
+    pub fn get_for<'a, I>(&'a self, interaction: &I, key: &'a str) -> &'a str
+    where
+        I: InteractionLocale,
+    {
+        self.get(interaction.locale(), key)
+    }
+
+    /// Get the translations of a key in every locale except [`DEFAULT_LOCALE`].
+    ///
+    /// These are meant for Discord's localization maps.
+    pub fn localizations<'a>(&'a self, key: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.locales
+            .iter()
+            .filter(|(locale, _)| &***locale != DEFAULT_LOCALE)
+            .filter_map(move |(locale, strings)| Some((&**locale, &**strings.get(key)?)))
+    }
+}
+
+/// Get the language part of a locale, like "es" for "es-ES"
+fn get_language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_falls_back() {
+        let mut translations = Translations::new();
+        translations
+            .add_locale(DEFAULT_LOCALE, [("greeting", "Hello"), ("farewell", "Bye")])
+            .add_locale("es-ES", [("greeting", "Hola")]);
+
+        assert_eq!(translations.get("es-ES", "greeting"), "Hola");
+        assert_eq!(translations.get("es-419", "greeting"), "Hola");
+        assert_eq!(translations.get("es-ES", "farewell"), "Bye");
+        assert_eq!(translations.get("de", "greeting"), "Hello");
+        assert_eq!(translations.get("de", "missing"), "missing");
+
+        let localizations: Vec<_> = translations.localizations("greeting").collect();
+        assert_eq!(localizations, [("es-ES", "Hola")]);
+    }
+}
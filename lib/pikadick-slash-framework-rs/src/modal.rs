@@ -0,0 +1,306 @@
+use crate::{
+    component::{
+        split_custom_id,
+        CUSTOM_ID_SEPERATOR,
+    },
+    create_custom_id,
+    BoxError,
+    BoxFuture,
+    BuilderError,
+    ComponentState,
+    ConvertError,
+    FromStates,
+    ModalCheckFn,
+    States,
+};
+use serenity::{
+    builder::{
+        CreateActionRow,
+        CreateModal,
+    },
+    client::Context,
+    model::application::ModalInteraction,
+};
+use std::{
+    any::TypeId,
+    future::Future,
+};
+
+type OnProcessResult = Result<(), BoxError>;
+pub type OnModalProcessFuture = BoxFuture<'static, OnProcessResult>;
+
+// Keep these types in sync.
+type OnProcessFutureFn =
+    Box<dyn Fn(Context, ModalInteraction, States) -> OnModalProcessFuture + Send + Sync>;
+type OnProcessFutureFnPtr<F, S, M> = fn(Context, ModalInteraction, S, M) -> F;
+type OnProcessWithStateFutureFnPtr<F, S, M, T> = fn(Context, ModalInteraction, S, M, T) -> F;
+
+/// The max number of text inputs a modal may have
+pub const MAX_MODAL_INPUTS: usize = 5;
+
+/// A trait that allows converting from a modal submit interaction
+pub trait FromModal: std::fmt::Debug + Send
+where
+    Self: Sized,
+{
+    /// Make fields from a [`ModalInteraction`]
+    fn from_modal(interaction: &ModalInteraction) -> Result<Self, ConvertError>;
+
+    /// Get the text inputs of this modal
+    fn create_components() -> Vec<CreateActionRow>;
+}
+
+/// Convert from a text input value
+pub trait FromModalValue: Sized {
+    /// Parse from a text input value.
+    ///
+    /// Empty values are treated as missing, and are never passed here.
+    fn from_modal_value(value: &str) -> Self;
+
+    /// Get the default "missing" value if the input was empty.
+    ///
+    /// # Returns
+    /// Returns None if this type is not optional.
+    fn get_missing_default() -> Option<Self> {
+        None
+    }
+}
+
+impl FromModalValue for String {
+    fn from_modal_value(value: &str) -> Self {
+        value.into()
+    }
+}
+
+impl<T> FromModalValue for Option<T>
+where
+    T: FromModalValue,
+{
+    fn from_modal_value(value: &str) -> Self {
+        Some(T::from_modal_value(value))
+    }
+
+    fn get_missing_default() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// Make a modal that will be routed to the handler with the given prefix.
+///
+/// # Returns
+/// Returns `None` if the custom id would be longer than Discord allows.
+pub fn create_modal<M, S>(prefix: &str, state: &S, title: &str) -> Option<CreateModal>
+where
+    M: FromModal,
+    S: ComponentState,
+{
+    let custom_id = create_custom_id(prefix, state)?;
+    Some(CreateModal::new(custom_id, title).components(M::create_components()))
+}
+
+/// A handler for submitted modals.
+///
+/// Modals are routed to a handler by the prefix of their custom id.
+pub struct ModalHandler {
+    /// The custom id prefix
+    prefix: Box<str>,
+
+    /// The name of the command that opens this modal, if any
+    command: Option<Box<str>>,
+
+    /// The main "process" func
+    on_process: OnProcessFutureFn,
+
+    /// The type ids and type names of the states the on_process hook needs
+    required_states: Box<[(TypeId, &'static str)]>,
+
+    /// Checks that must pass before this handler is run
+    checks: Vec<ModalCheckFn>,
+}
+
+impl ModalHandler {
+    /// Get the custom id prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Get the name of the command that opens this modal.
+    ///
+    /// Checks can use this to treat the modal like the command itself.
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+
+    /// Fire the on_process hook
+    pub async fn fire_on_process(
+        &self,
+        ctx: Context,
+        interaction: ModalInteraction,
+        states: States,
+    ) -> Result<(), BoxError> {
+        (self.on_process)(ctx, interaction, states).await
+    }
+
+    /// Get the type ids and type names of the states the on_process hook needs
+    pub fn required_states(&self) -> &[(TypeId, &'static str)] {
+        &self.required_states
+    }
+
+    /// Get the checks
+    pub fn checks(&self) -> &[ModalCheckFn] {
+        &self.checks
+    }
+}
+
+impl std::fmt::Debug for ModalHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModalHandler")
+            .field("prefix", &self.prefix)
+            .field("command", &self.command)
+            .field("on_process", &"<func>")
+            .finish()
+    }
+}
+
+/// A builder for a [`ModalHandler`].
+pub struct ModalHandlerBuilder<'a> {
+    prefix: Option<&'a str>,
+    command: Option<&'a str>,
+
+    on_process: Option<OnProcessFutureFn>,
+    required_states: Vec<(TypeId, &'static str)>,
+    checks: Vec<ModalCheckFn>,
+}
+
+impl<'a> ModalHandlerBuilder<'a> {
+    /// Make a new [`ModalHandlerBuilder`].
+    pub fn new() -> Self {
+        Self {
+            prefix: None,
+            command: None,
+
+            on_process: None,
+            required_states: Vec::new(),
+            checks: Vec::new(),
+        }
+    }
+
+    /// The custom id prefix
+    pub fn prefix(&mut self, prefix: &'a str) -> &mut Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// The name of the command that opens this modal.
+    ///
+    /// For subcommands, this is the full path of the command, like "tic-tac-toe::play".
+    pub fn command(&mut self, command: &'a str) -> &mut Self {
+        self.command = Some(command);
+        self
+    }
+
+    /// The on_process hook.
+    ///
+    /// The state is decoded from the custom id of the modal,
+    /// and the fields are parsed from the submitted text inputs.
+    pub fn on_process<F, S, M>(&mut self, on_process: OnProcessFutureFnPtr<F, S, M>) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        S: ComponentState,
+        M: FromModal + 'static,
+    {
+        // Trampoline so user does not have to box manually and parse their fields manually
+        self.on_process = Some(Box::new(move |ctx, interaction, _states| {
+            Box::pin(async move {
+                let (_prefix, state) = split_custom_id(&interaction.data.custom_id);
+                let state = S::decode(state)?;
+                let fields = M::from_modal(&interaction)?;
+                (on_process)(ctx, interaction, state, fields).await
+            })
+        }));
+        self.required_states.clear();
+
+        self
+    }
+
+    /// The on_process hook, with shared state from the framework.
+    ///
+    /// `T` is a [`State`](crate::State) or a tuple of them.
+    /// The framework fails to build if a state was not registered.
+    pub fn on_process_with_state<F, S, M, T>(
+        &mut self,
+        on_process: OnProcessWithStateFutureFnPtr<F, S, M, T>,
+    ) -> &mut Self
+    where
+        F: Future<Output = Result<(), BoxError>> + Send + 'static,
+        S: ComponentState,
+        M: FromModal + 'static,
+        T: FromStates,
+    {
+        // Trampoline so user does not have to box manually and parse their fields or extract their states manually
+        self.on_process = Some(Box::new(move |ctx, interaction, states| {
+            Box::pin(async move {
+                let (_prefix, state) = split_custom_id(&interaction.data.custom_id);
+                let state = S::decode(state)?;
+                let fields = M::from_modal(&interaction)?;
+                let states = T::from_states(&states)?;
+                (on_process)(ctx, interaction, state, fields, states).await
+            })
+        }));
+        self.required_states = T::required_states();
+
+        self
+    }
+
+    /// Add a check to this handler
+    pub fn check(&mut self, check: ModalCheckFn) -> &mut Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Build the [`ModalHandler`]
+    pub fn build(&mut self) -> Result<ModalHandler, BuilderError> {
+        #[allow(clippy::or_fun_call)]
+        let prefix = self
+            .prefix
+            .take()
+            .ok_or(BuilderError::MissingField("prefix"))?;
+        #[allow(clippy::or_fun_call)]
+        let on_process = self
+            .on_process
+            .take()
+            .ok_or(BuilderError::MissingField("on_process"))?;
+
+        if prefix.is_empty() {
+            return Err(BuilderError::Invalid("a modal prefix cannot be empty"));
+        }
+        if prefix.contains(CUSTOM_ID_SEPERATOR) {
+            return Err(BuilderError::Invalid("a modal prefix cannot contain ':'"));
+        }
+
+        Ok(ModalHandler {
+            prefix: prefix.into(),
+            command: self.command.take().map(Into::into),
+
+            on_process,
+            required_states: std::mem::take(&mut self.required_states).into_boxed_slice(),
+            checks: std::mem::take(&mut self.checks),
+        })
+    }
+}
+
+impl std::fmt::Debug for ModalHandlerBuilder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModalHandlerBuilder")
+            .field("prefix", &self.prefix)
+            .field("command", &self.command)
+            .field("on_process", &self.on_process.as_ref().map(|_| "<func>"))
+            .finish()
+    }
+}
+
+impl Default for ModalHandlerBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
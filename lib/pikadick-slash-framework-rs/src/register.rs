@@ -0,0 +1,305 @@
+use serde_json::{
+    Map,
+    Value,
+};
+use serenity::{
+    builder::CreateCommand,
+    model::application::Command as ApplicationCommand,
+};
+use std::collections::BTreeMap;
+
+/// The changes needed to make the registered commands match the local commands
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RegisterPlan {
+    /// Commands that are not registered yet
+    pub added: Vec<String>,
+
+    /// Commands that are registered, but no longer exist locally
+    pub removed: Vec<String>,
+
+    /// Commands that are registered, but differ from the local definition
+    pub changed: Vec<String>,
+}
+
+impl RegisterPlan {
+    /// Compare local command definitions with registered commands
+    pub fn new(
+        local: &[CreateCommand],
+        remote: &[ApplicationCommand],
+    ) -> Result<Self, serde_json::Error> {
+        let local = local
+            .iter()
+            .map(|command| serde_json::to_value(command).map(|value| normalize_command(&value)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let remote = remote
+            .iter()
+            .map(|command| serde_json::to_value(command).map(|value| normalize_command(&value)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Commands are unique by type and name
+        let local: BTreeMap<_, _> = local
+            .iter()
+            .map(|value| (command_key(value), value))
+            .collect();
+        let remote: BTreeMap<_, _> = remote
+            .iter()
+            .map(|value| (command_key(value), value))
+            .collect();
+
+        let mut plan = Self::default();
+        for (key, local_value) in local.iter() {
+            match remote.get(key) {
+                Some(remote_value) if remote_value != local_value => {
+                    plan.changed.push(key.1.clone());
+                }
+                Some(_) => {}
+                None => {
+                    plan.added.push(key.1.clone());
+                }
+            }
+        }
+        for key in remote.keys() {
+            if !local.contains_key(key) {
+                plan.removed.push(key.1.clone());
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Check if the registered commands are up to date
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl std::fmt::Display for RegisterPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "added={:?}, removed={:?}, changed={:?}",
+            self.added, self.removed, self.changed
+        )
+    }
+}
+
+/// Get the unique key of a normalized command
+fn command_key(value: &Value) -> (u64, String) {
+    let kind = value["type"].as_u64().unwrap_or(1);
+    let name = value["name"].as_str().unwrap_or_default().to_string();
+    (kind, name)
+}
+
+/// Normalize a serialized command, so that local and registered commands can be compared.
+///
+/// This keeps only fields that we set, and fills in the defaults Discord uses for missing fields.
+fn normalize_command(value: &Value) -> Value {
+    let mut map = Map::new();
+    map.insert("type".into(), or_default(&value["type"], 1.into()));
+    map.insert("name".into(), value["name"].clone());
+    map.insert(
+        "description".into(),
+        or_default(&value["description"], "".into()),
+    );
+    normalize_localizations(&mut map, value, "name_localizations");
+    normalize_localizations(&mut map, value, "description_localizations");
+    map.insert(
+        "options".into(),
+        normalize_array(&value["options"], normalize_option),
+    );
+    map.insert(
+        "default_member_permissions".into(),
+        value["default_member_permissions"].clone(),
+    );
+    map.insert(
+        "dm_permission".into(),
+        or_default(&value["dm_permission"], true.into()),
+    );
+    map.insert("nsfw".into(), or_default(&value["nsfw"], false.into()));
+
+    Value::Object(map)
+}
+
+/// Normalize a serialized command option
+fn normalize_option(value: &Value) -> Value {
+    let mut map = Map::new();
+    map.insert("type".into(), value["type"].clone());
+    map.insert("name".into(), value["name"].clone());
+    map.insert("description".into(), value["description"].clone());
+    normalize_localizations(&mut map, value, "name_localizations");
+    normalize_localizations(&mut map, value, "description_localizations");
+    map.insert(
+        "required".into(),
+        or_default(&value["required"], false.into()),
+    );
+    map.insert(
+        "choices".into(),
+        normalize_array(&value["choices"], normalize_choice),
+    );
+    map.insert(
+        "options".into(),
+        normalize_array(&value["options"], normalize_option),
+    );
+    map.insert(
+        "channel_types".into(),
+        normalize_array(&value["channel_types"], Value::clone),
+    );
+    map.insert("min_value".into(), normalize_number(&value["min_value"]));
+    map.insert("max_value".into(), normalize_number(&value["max_value"]));
+    map.insert("min_length".into(), value["min_length"].clone());
+    map.insert("max_length".into(), value["max_length"].clone());
+    map.insert(
+        "autocomplete".into(),
+        or_default(&value["autocomplete"], false.into()),
+    );
+
+    Value::Object(map)
+}
+
+/// Normalize a serialized command option choice
+fn normalize_choice(value: &Value) -> Value {
+    let mut map = Map::new();
+    map.insert("name".into(), value["name"].clone());
+    normalize_localizations(&mut map, value, "name_localizations");
+    map.insert("value".into(), normalize_number(&value["value"]));
+
+    Value::Object(map)
+}
+
+/// Normalize a localization map, where a missing map is the same as an empty one
+fn normalize_localizations(map: &mut Map<String, Value>, value: &Value, key: &str) {
+    map.insert(
+        key.into(),
+        or_default(&value[key], Value::Object(Map::new())),
+    );
+}
+
+/// Normalize an array, where a missing array is the same as an empty one
+fn normalize_array(value: &Value, f: fn(&Value) -> Value) -> Value {
+    match value.as_array() {
+        Some(values) => Value::Array(values.iter().map(f).collect()),
+        None => Value::Array(Vec::new()),
+    }
+}
+
+/// Normalize a number, so that integers and floats with the same value compare equal
+fn normalize_number(value: &Value) -> Value {
+    match value.as_f64() {
+        Some(n) => n.into(),
+        None => value.clone(),
+    }
+}
+
+/// Use a default value if a value is null or missing
+fn or_default(value: &Value, default: Value) -> Value {
+    if value.is_null() {
+        default
+    } else {
+        value.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use serenity::{
+        builder::CreateCommandOption,
+        model::application::CommandOptionType,
+    };
+
+    /// Make a registered command, as Discord would return it
+    fn create_remote_command(id: u64, name: &str, description: &str) -> ApplicationCommand {
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "application_id": "1",
+            "version": "1",
+            "type": 1,
+            "name": name,
+            "name_localizations": null,
+            "description": description,
+            "description_localizations": null,
+            "default_member_permissions": null,
+            "dm_permission": true,
+            "nsfw": false,
+        }))
+        .expect("invalid command")
+    }
+
+    #[test]
+    fn plan_add_change_remove_unchanged() {
+        let local = [
+            CreateCommand::new("ping").description("Pong"),
+            CreateCommand::new("echo").description("Echo some text"),
+            CreateCommand::new("new").description("A new command"),
+        ];
+        let remote = [
+            create_remote_command(1, "ping", "Pong"),
+            create_remote_command(2, "echo", "Old description"),
+            create_remote_command(3, "old", "An old command"),
+        ];
+
+        let plan = RegisterPlan::new(&local, &remote).expect("failed to make plan");
+        assert_eq!(
+            plan,
+            RegisterPlan {
+                added: vec!["new".into()],
+                removed: vec!["old".into()],
+                changed: vec!["echo".into()],
+            }
+        );
+        assert!(!plan.is_empty());
+    }
+
+    #[test]
+    fn plan_unchanged_is_empty() {
+        let local = [CreateCommand::new("ping").description("Pong")];
+        let remote = [create_remote_command(1, "ping", "Pong")];
+
+        let plan = RegisterPlan::new(&local, &remote).expect("failed to make plan");
+        assert!(plan.is_empty(), "{plan}");
+    }
+
+    #[test]
+    fn normalize_localizations_missing_is_empty() {
+        let missing = normalize_command(&json!({ "name": "ping" }));
+        let null = normalize_command(&json!({
+            "name": "ping",
+            "name_localizations": null,
+            "description_localizations": {},
+        }));
+        assert_eq!(missing, null);
+        assert_eq!(missing["name_localizations"], json!({}));
+
+        let localized = normalize_command(&json!({
+            "name": "ping",
+            "name_localizations": { "de": "ping" },
+        }));
+        assert_ne!(missing, localized);
+    }
+
+    #[test]
+    fn normalize_option_defaults() {
+        let local =
+            CreateCommandOption::new(CommandOptionType::Integer, "times", "Times").min_int_value(1);
+        let local = normalize_option(&serde_json::to_value(local).expect("invalid option"));
+        let remote = normalize_option(&json!({
+            "type": 4,
+            "name": "times",
+            "description": "Times",
+            "required": false,
+            "min_value": 1.0,
+            "autocomplete": null,
+        }));
+        assert_eq!(local, remote);
+
+        let required = normalize_option(&json!({
+            "type": 4,
+            "name": "times",
+            "description": "Times",
+            "required": true,
+            "min_value": 1,
+        }));
+        assert_ne!(local, required);
+    }
+}
@@ -0,0 +1,305 @@
+use serenity::{
+    builder::{
+        Builder,
+        CreateActionRow,
+        CreateAttachment,
+        CreateEmbed,
+        CreateInteractionResponse,
+        CreateInteractionResponseFollowup,
+        CreateInteractionResponseMessage,
+        EditInteractionResponse,
+    },
+    client::Context,
+    model::{
+        application::{
+            CommandInteraction,
+            ComponentInteraction,
+            ModalInteraction,
+        },
+        id::InteractionId,
+    },
+};
+use std::{
+    future::Future,
+    time::Duration,
+};
+
+/// How long to wait for work to finish before deferring.
+///
+/// Discord requires a response within 3 seconds.
+pub const DEFER_AFTER: Duration = Duration::from_secs(2);
+
+/// The state of an interaction response
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResponseState {
+    /// Nothing was sent yet
+    Pending,
+
+    /// The interaction was deferred, the next message replaces the loading message.
+    ///
+    /// For updates, the next message edits the message of the component.
+    Deferred,
+
+    /// The interaction was responded to, the next message is a follow-up
+    Responded,
+}
+
+/// A message to send with a [`Response`].
+///
+/// Embeds and components are left as they are when editing a message, unless they are set.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseMessage {
+    content: Option<String>,
+    embeds: Vec<CreateEmbed>,
+    components: Option<Vec<CreateActionRow>>,
+    attachments: Vec<CreateAttachment>,
+}
+
+impl ResponseMessage {
+    /// Make a new, empty [`ResponseMessage`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Add an embed
+    pub fn embed(mut self, embed: CreateEmbed) -> Self {
+        self.embeds.push(embed);
+        self
+    }
+
+    /// Set the components
+    pub fn components(mut self, components: Vec<CreateActionRow>) -> Self {
+        self.components = Some(components);
+        self
+    }
+
+    /// Add an attachment to upload
+    pub fn attachment(mut self, attachment: CreateAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Make an initial response from this message
+    pub fn into_create_response(self, ephemeral: bool) -> CreateInteractionResponseMessage {
+        let mut builder = CreateInteractionResponseMessage::new()
+            .add_files(self.attachments)
+            .ephemeral(ephemeral);
+        if let Some(content) = self.content {
+            builder = builder.content(content);
+        }
+        if !self.embeds.is_empty() {
+            builder = builder.embeds(self.embeds);
+        }
+        if let Some(components) = self.components {
+            builder = builder.components(components);
+        }
+        builder
+    }
+
+    /// Make an edit of the original response from this message
+    pub fn into_edit_response(self) -> EditInteractionResponse {
+        let mut builder = EditInteractionResponse::new();
+        for attachment in self.attachments {
+            builder = builder.new_attachment(attachment);
+        }
+        if let Some(content) = self.content {
+            builder = builder.content(content);
+        }
+        if !self.embeds.is_empty() {
+            builder = builder.embeds(self.embeds);
+        }
+        if let Some(components) = self.components {
+            builder = builder.components(components);
+        }
+        builder
+    }
+
+    /// Make a follow-up from this message
+    pub fn into_followup(self, ephemeral: bool) -> CreateInteractionResponseFollowup {
+        let mut builder = CreateInteractionResponseFollowup::new()
+            .embeds(self.embeds)
+            .components(self.components.unwrap_or_default())
+            .add_files(self.attachments)
+            .ephemeral(ephemeral);
+        if let Some(content) = self.content {
+            builder = builder.content(content);
+        }
+        builder
+    }
+}
+
+/// An interaction that can be responded to with a message
+pub trait RespondableInteraction {
+    /// Get the id
+    fn id(&self) -> InteractionId;
+
+    /// Get the token
+    fn token(&self) -> &str;
+}
+
+impl RespondableInteraction for CommandInteraction {
+    fn id(&self) -> InteractionId {
+        self.id
+    }
+
+    fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl RespondableInteraction for ComponentInteraction {
+    fn id(&self) -> InteractionId {
+        self.id
+    }
+
+    fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl RespondableInteraction for ModalInteraction {
+    fn id(&self) -> InteractionId {
+        self.id
+    }
+
+    fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+/// A handle for responding to a command, component, or modal.
+///
+/// This tracks whether the interaction was deferred or responded to,
+/// and picks the right way to send each message.
+#[derive(Debug)]
+pub struct Response<'a> {
+    ctx: &'a Context,
+    id: InteractionId,
+    token: &'a str,
+    ephemeral: bool,
+    update: bool,
+    state: ResponseState,
+}
+
+impl<'a> Response<'a> {
+    /// Make a new [`Response`] for an interaction that was not responded to yet.
+    pub fn new<I>(ctx: &'a Context, interaction: &'a I) -> Self
+    where
+        I: RespondableInteraction,
+    {
+        Self {
+            ctx,
+            id: interaction.id(),
+            token: interaction.token(),
+            ephemeral: false,
+            update: false,
+            state: ResponseState::Pending,
+        }
+    }
+
+    /// Whether messages should only be shown to the user.
+    ///
+    /// The first message is only ephemeral if this is set before deferring.
+    pub fn ephemeral(&mut self, ephemeral: bool) -> &mut Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// Whether the first message updates the message of a component, instead of sending a new one.
+    ///
+    /// Deferring then only acknowledges the interaction, without a loading message.
+    pub fn update(&mut self, update: bool) -> &mut Self {
+        self.update = update;
+        self
+    }
+
+    /// Get the state
+    pub fn state(&self) -> ResponseState {
+        self.state
+    }
+
+    /// Defer the response, showing a loading message.
+    ///
+    /// This does nothing if the interaction was already deferred or responded to.
+    pub async fn defer(&mut self) -> Result<(), serenity::Error> {
+        if self.state != ResponseState::Pending {
+            return Ok(());
+        }
+
+        let response = if self.update {
+            CreateInteractionResponse::Acknowledge
+        } else {
+            let message_builder = CreateInteractionResponseMessage::new().ephemeral(self.ephemeral);
+            CreateInteractionResponse::Defer(message_builder)
+        };
+        response
+            .execute(&self.ctx.http, (self.id, self.token))
+            .await?;
+        self.state = ResponseState::Deferred;
+
+        Ok(())
+    }
+
+    /// Run some work, deferring the response if it takes longer than [`DEFER_AFTER`].
+    pub async fn defer_if_slow<F>(&mut self, future: F) -> Result<F::Output, serenity::Error>
+    where
+        F: Future,
+    {
+        let mut future = std::pin::pin!(future);
+        match tokio::time::timeout(DEFER_AFTER, &mut future).await {
+            Ok(output) => Ok(output),
+            Err(_elapsed) => {
+                self.defer().await?;
+                Ok(future.await)
+            }
+        }
+    }
+
+    /// Send a message.
+    ///
+    /// The first message is the response, or replaces the loading message if deferred.
+    /// Later messages are sent as follow-ups.
+    pub async fn send(&mut self, message: ResponseMessage) -> Result<(), serenity::Error> {
+        let http = &self.ctx.http;
+        match self.state {
+            ResponseState::Pending => {
+                let message_builder = message.into_create_response(self.ephemeral);
+                let response = if self.update {
+                    CreateInteractionResponse::UpdateMessage(message_builder)
+                } else {
+                    CreateInteractionResponse::Message(message_builder)
+                };
+                response.execute(http, (self.id, self.token)).await?;
+            }
+            ResponseState::Deferred => {
+                message
+                    .into_edit_response()
+                    .execute(http, self.token)
+                    .await?;
+            }
+            ResponseState::Responded => {
+                message
+                    .into_followup(self.ephemeral)
+                    .execute(http, (None, self.token))
+                    .await?;
+            }
+        }
+        self.state = ResponseState::Responded;
+
+        Ok(())
+    }
+
+    /// Send a message with only content.
+    pub async fn send_content(
+        &mut self,
+        content: impl Into<String>,
+    ) -> Result<(), serenity::Error> {
+        self.send(ResponseMessage::new().content(content)).await
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    any::{
+        Any,
+        TypeId,
+    },
+    collections::HashMap,
+    sync::Arc,
+};
+
+/// A type-erased state, with the name of its type
+type StateEntry = (&'static str, Arc<dyn Any + Send + Sync>);
+
+/// Shared state registered on a framework, by type
+#[derive(Clone, Default)]
+pub struct States(Arc<HashMap<TypeId, StateEntry>>);
+
+impl States {
+    /// Make a new [`States`] from a map of states
+    pub(crate) fn new(states: HashMap<TypeId, StateEntry>) -> Self {
+        Self(Arc::new(states))
+    }
+
+    /// Check if a state with the given type id exists
+    pub(crate) fn contains(&self, type_id: TypeId) -> bool {
+        self.0.contains_key(&type_id)
+    }
+
+    /// Get a state by type
+    pub fn get<T>(&self) -> Option<State<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let (_name, state) = self.0.get(&TypeId::of::<T>())?;
+        state.clone().downcast::<T>().ok().map(State)
+    }
+}
+
+impl std::fmt::Debug for States {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set()
+            .entries(self.0.values().map(|(name, _state)| name))
+            .finish()
+    }
+}
+
+/// Shared state, extracted from the framework for a command
+#[derive(Debug)]
+pub struct State<T>(Arc<T>);
+
+impl<T> State<T> {
+    /// Get the inner [`Arc`]
+    pub fn into_inner(self) -> Arc<T> {
+        self.0
+    }
+}
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> std::ops::Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A state was not registered with the framework
+#[derive(Debug, thiserror::Error)]
+#[error("missing state \"{0}\"")]
+pub struct MissingStateError(pub &'static str);
+
+/// Types that can be extracted from the states of a framework.
+///
+/// This is implemented for [`State`] and tuples of them.
+pub trait FromStates: Sized + Send + 'static {
+    /// Get the type ids and type names of the needed states
+    fn required_states() -> Vec<(TypeId, &'static str)>;
+
+    /// Extract from the states
+    fn from_states(states: &States) -> Result<Self, MissingStateError>;
+}
+
+impl FromStates for () {
+    fn required_states() -> Vec<(TypeId, &'static str)> {
+        Vec::new()
+    }
+
+    fn from_states(_states: &States) -> Result<Self, MissingStateError> {
+        Ok(())
+    }
+}
+
+impl<T> FromStates for State<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn required_states() -> Vec<(TypeId, &'static str)> {
+        vec![(TypeId::of::<T>(), std::any::type_name::<T>())]
+    }
+
+    fn from_states(states: &States) -> Result<Self, MissingStateError> {
+        states
+            .get::<T>()
+            .ok_or_else(|| MissingStateError(std::any::type_name::<T>()))
+    }
+}
+
+macro_rules! impl_from_states_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> FromStates for ($($name,)+)
+        where
+            $($name: FromStates,)+
+        {
+            fn required_states() -> Vec<(TypeId, &'static str)> {
+                let mut required_states = Vec::new();
+                $(required_states.extend($name::required_states());)+
+                required_states
+            }
+
+            fn from_states(states: &States) -> Result<Self, MissingStateError> {
+                Ok(($($name::from_states(states)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_states_tuple!(A);
+impl_from_states_tuple!(A, B);
+impl_from_states_tuple!(A, B, C);
+impl_from_states_tuple!(A, B, C, D);
@@ -0,0 +1,1223 @@
+//! An offline test harness for commands.
+//!
+//! This runs a [`Framework`] inside of a real client,
+//! connected to a fake gateway and a fake http api on localhost.
+//! Synthetic interactions are sent through the gateway,
+//! and the requests that would have been sent to Discord are captured instead.
+
+use crate::Framework;
+use serde_json::{
+    json,
+    Value,
+};
+use serenity::{
+    client::{
+        ClientBuilder,
+        Context,
+        EventHandler,
+    },
+    futures::{
+        SinkExt,
+        StreamExt,
+    },
+    http::HttpBuilder,
+    model::{
+        application::{
+            CommandOptionType,
+            Interaction,
+        },
+        gateway::GatewayIntents,
+        id::{
+            ApplicationId,
+            GuildId,
+            InteractionId,
+            UserId,
+        },
+    },
+    prelude::TypeMap,
+};
+use std::{
+    collections::{
+        hash_map::Entry,
+        HashMap,
+    },
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{
+        AsyncReadExt,
+        AsyncWriteExt,
+    },
+    net::{
+        TcpListener,
+        TcpStream,
+    },
+    sync::{
+        mpsc,
+        Mutex,
+        RwLock,
+    },
+    task::JoinHandle,
+};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+/// The application id of the fake bot
+const APPLICATION_ID: u64 = 1;
+
+/// The user id of the fake bot
+const BOT_USER_ID: u64 = 2;
+
+/// The default user id of the user that sends interactions
+const DEFAULT_USER_ID: u64 = 3;
+
+/// The default channel id that interactions are sent in
+const DEFAULT_CHANNEL_ID: u64 = 4;
+
+/// The message flags of a loading message
+const LOADING_FLAG: u64 = 1 << 7;
+
+/// How long to wait for an interaction to be processed
+const PROCESS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An error that occured while testing
+#[derive(Debug, thiserror::Error)]
+pub enum TestError {
+    /// Failed to bind a fake server
+    #[error("failed to bind fake server")]
+    Bind(#[source] std::io::Error),
+
+    /// The interaction payload was invalid
+    #[error("invalid interaction payload")]
+    InvalidInteraction(#[source] serde_json::Error),
+
+    /// The client shut down
+    #[error("the client shut down")]
+    ClientShutdown,
+
+    /// The interaction was not processed in time
+    #[error("timed out waiting for the interaction to be processed")]
+    Timeout,
+}
+
+/// A request that would have been sent to Discord
+#[derive(Debug, Clone)]
+pub struct CapturedRequest {
+    /// The http method
+    pub method: String,
+
+    /// The path, including the api version prefix
+    pub path: String,
+
+    /// The raw body
+    pub body: Vec<u8>,
+}
+
+impl CapturedRequest {
+    /// Parse the body as json.
+    ///
+    /// Returns `None` if the body is not json.
+    pub fn json(&self) -> Option<Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+
+    /// Get the message content of an interaction response, followup, or edit.
+    pub fn content(&self) -> Option<String> {
+        let json = self.json()?;
+        json.get("data")
+            .and_then(|data| data.get("content"))
+            .or_else(|| json.get("content"))?
+            .as_str()
+            .map(String::from)
+    }
+
+    /// Whether this is an initial interaction response
+    pub fn is_interaction_response(&self) -> bool {
+        self.method == "POST" && self.path.ends_with("/callback")
+    }
+}
+
+/// A builder for a synthetic interaction payload
+#[derive(Debug, Clone)]
+pub struct InteractionBuilder {
+    kind: u8,
+    name: String,
+    subcommands: Vec<(String, CommandOptionType)>,
+    options: Vec<Value>,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+}
+
+impl InteractionBuilder {
+    /// Make a builder for a chat input command with the given name
+    pub fn command(name: &str) -> Self {
+        Self {
+            kind: 2,
+            name: name.into(),
+            subcommands: Vec::new(),
+            options: Vec::new(),
+            guild_id: None,
+            user_id: UserId::new(DEFAULT_USER_ID),
+        }
+    }
+
+    /// Make a builder for an autocomplete request for a command with the given name.
+    ///
+    /// Use [`Self::focused_option`] to set the option being typed.
+    pub fn autocomplete(name: &str) -> Self {
+        Self {
+            kind: 4,
+            ..Self::command(name)
+        }
+    }
+
+    /// Make a builder for a button click with the given custom id.
+    ///
+    /// The name is used as the custom id.
+    pub fn component(custom_id: &str) -> Self {
+        Self {
+            kind: 3,
+            ..Self::command(custom_id)
+        }
+    }
+
+    /// Invoke a subcommand.
+    ///
+    /// Call this multiple times to walk through subcommand groups.
+    pub fn subcommand(&mut self, name: &str) -> &mut Self {
+        // Only the last level is a subcommand, the rest are groups.
+        if let Some((_name, kind)) = self.subcommands.last_mut() {
+            *kind = CommandOptionType::SubCommandGroup;
+        }
+        self.subcommands
+            .push((name.into(), CommandOptionType::SubCommand));
+        self
+    }
+
+    /// Add an option to the invoked command
+    pub fn option(&mut self, name: &str, kind: CommandOptionType, value: Value) -> &mut Self {
+        self.options.push(json!({
+            "name": name,
+            "type": u8::from(kind),
+            "value": value,
+        }));
+        self
+    }
+
+    /// Add a string option
+    pub fn string_option(&mut self, name: &str, value: &str) -> &mut Self {
+        self.option(name, CommandOptionType::String, value.into())
+    }
+
+    /// Add an integer option
+    pub fn integer_option(&mut self, name: &str, value: i64) -> &mut Self {
+        self.option(name, CommandOptionType::Integer, value.into())
+    }
+
+    /// Add a boolean option
+    pub fn boolean_option(&mut self, name: &str, value: bool) -> &mut Self {
+        self.option(name, CommandOptionType::Boolean, value.into())
+    }
+
+    /// Add a number option
+    pub fn number_option(&mut self, name: &str, value: f64) -> &mut Self {
+        self.option(name, CommandOptionType::Number, value.into())
+    }
+
+    /// Add the option that is being typed, for autocomplete
+    pub fn focused_option(&mut self, name: &str, value: &str) -> &mut Self {
+        self.options.push(json!({
+            "name": name,
+            "type": u8::from(CommandOptionType::String),
+            "value": value,
+            "focused": true,
+        }));
+        self
+    }
+
+    /// Send the interaction from a guild.
+    ///
+    /// By default, interactions are sent from a dm.
+    pub fn guild_id(&mut self, guild_id: GuildId) -> &mut Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+
+    /// Set the user that sends the interaction
+    pub fn user_id(&mut self, user_id: UserId) -> &mut Self {
+        self.user_id = user_id;
+        self
+    }
+
+    /// Make the json payload
+    fn build(&self, id: u64) -> Value {
+        let mut options = Value::Array(self.options.clone());
+        for (name, kind) in self.subcommands.iter().rev() {
+            options = json!([{
+                "name": name,
+                "type": u8::from(*kind),
+                "options": options,
+            }]);
+        }
+
+        let data = if self.kind == 3 {
+            json!({
+                "custom_id": self.name,
+                "component_type": 2,
+            })
+        } else {
+            json!({
+                "id": id.to_string(),
+                "name": self.name,
+                "type": 1,
+                "options": options,
+            })
+        };
+
+        let mut payload = json!({
+            "id": id.to_string(),
+            "application_id": APPLICATION_ID.to_string(),
+            "type": self.kind,
+            "data": data,
+            "channel_id": DEFAULT_CHANNEL_ID.to_string(),
+            "user": {
+                "id": self.user_id.to_string(),
+                "username": "test-user",
+                "global_name": null,
+                "avatar": null,
+            },
+            "token": format!("token-{id}"),
+            "version": 1,
+            "locale": "en-US",
+            "entitlements": [],
+        });
+        if let Some(guild_id) = self.guild_id {
+            payload["guild_id"] = guild_id.to_string().into();
+        }
+        if self.kind == 3 {
+            payload["message"] = create_fake_message(None);
+        }
+
+        payload
+    }
+}
+
+/// Notifies the harness when an interaction is done processing
+struct Handler {
+    framework: Framework,
+    processed_tx: mpsc::UnboundedSender<InteractionId>,
+}
+
+#[serenity::async_trait]
+impl EventHandler for Handler {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let id = interaction.id();
+        self.framework
+            .process_interaction_create(ctx, interaction)
+            .await;
+        let _ = self.processed_tx.send(id).is_ok();
+    }
+}
+
+/// An offline test harness for a [`Framework`].
+pub struct TestHarness {
+    data: Arc<RwLock<TypeMap>>,
+
+    captured: Arc<Mutex<Vec<CapturedRequest>>>,
+    event_tx: mpsc::UnboundedSender<Value>,
+    processed_rx: Mutex<mpsc::UnboundedReceiver<InteractionId>>,
+    next_id: AtomicU64,
+
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl TestHarness {
+    /// Start a client with the given framework, connected to fake Discord servers.
+    pub async fn new(framework: Framework) -> Result<Self, TestError> {
+        let http_listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(TestError::Bind)?;
+        let http_addr = http_listener.local_addr().map_err(TestError::Bind)?;
+        let gateway_listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(TestError::Bind)?;
+        let gateway_addr = gateway_listener.local_addr().map_err(TestError::Bind)?;
+
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let (processed_tx, processed_rx) = mpsc::unbounded_channel();
+
+        let mut tasks = Vec::with_capacity(3);
+        tasks.push(tokio::spawn(run_http_server(
+            http_listener,
+            format!("ws://{gateway_addr}"),
+            captured.clone(),
+        )));
+        tasks.push(tokio::spawn(run_gateway_server(gateway_listener, event_rx)));
+
+        let http = HttpBuilder::new("test-token")
+            .proxy(format!("http://{http_addr}"))
+            .ratelimiter_disabled(true)
+            .application_id(ApplicationId::new(APPLICATION_ID))
+            .build();
+        let mut client = ClientBuilder::new_with_http(http, GatewayIntents::empty())
+            .event_handler(Handler {
+                framework,
+                processed_tx,
+            })
+            .await
+            .map_err(|_error| TestError::ClientShutdown)?;
+        let data = client.data.clone();
+        tasks.push(tokio::spawn(async move {
+            let _ = client.start().await.is_ok();
+        }));
+
+        Ok(Self {
+            data,
+
+            captured,
+            event_tx,
+            processed_rx: Mutex::new(processed_rx),
+            next_id: AtomicU64::new(1_000),
+
+            tasks,
+        })
+    }
+
+    /// Get the client data, to insert state that commands expect
+    pub fn data(&self) -> Arc<RwLock<TypeMap>> {
+        self.data.clone()
+    }
+
+    /// Send an interaction and wait for it to be processed.
+    ///
+    /// # Returns
+    /// Returns the requests that would have been sent to Discord while processing it.
+    pub async fn run(
+        &self,
+        interaction: &InteractionBuilder,
+    ) -> Result<Vec<CapturedRequest>, TestError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let payload = interaction.build(id);
+
+        // Make sure the payload is valid before sending it,
+        // otherwise the client would drop it and we would time out.
+        serde_json::from_value::<Interaction>(payload.clone())
+            .map_err(TestError::InvalidInteraction)?;
+
+        // Interactions are processed one at a time, so only our requests are captured.
+        let mut processed_rx = self.processed_rx.lock().await;
+        self.captured.lock().await.clear();
+
+        self.event_tx
+            .send(payload)
+            .map_err(|_error| TestError::ClientShutdown)?;
+
+        let expected_id = InteractionId::new(id);
+        tokio::time::timeout(PROCESS_TIMEOUT, async {
+            loop {
+                match processed_rx.recv().await {
+                    Some(id) if id == expected_id => return Ok(()),
+                    Some(_id) => {}
+                    None => return Err(TestError::ClientShutdown),
+                }
+            }
+        })
+        .await
+        .map_err(|_error| TestError::Timeout)??;
+
+        Ok(std::mem::take(&mut *self.captured.lock().await))
+    }
+}
+
+impl Drop for TestHarness {
+    fn drop(&mut self) {
+        for task in self.tasks.iter() {
+            task.abort();
+        }
+    }
+}
+
+impl std::fmt::Debug for TestHarness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestHarness").finish_non_exhaustive()
+    }
+}
+
+/// Make a fake message, for endpoints that return one
+fn create_fake_message(content: Option<String>) -> Value {
+    json!({
+        "id": "5",
+        "channel_id": DEFAULT_CHANNEL_ID.to_string(),
+        "author": {
+            "id": BOT_USER_ID.to_string(),
+            "username": "test-bot",
+            "global_name": null,
+            "avatar": null,
+            "bot": true,
+        },
+        "content": content.unwrap_or_default(),
+        "timestamp": "2000-01-01T00:00:00.000000+00:00",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+    })
+}
+
+/// Run the fake http api, capturing requests
+async fn run_http_server(
+    listener: TcpListener,
+    gateway_url: String,
+    captured: Arc<Mutex<Vec<CapturedRequest>>>,
+) {
+    // The flags of the original response of each interaction, by token
+    let original_responses = Arc::new(Mutex::new(HashMap::new()));
+    while let Ok((stream, _addr)) = listener.accept().await {
+        let gateway_url = gateway_url.clone();
+        let captured = captured.clone();
+        let original_responses = original_responses.clone();
+        tokio::spawn(async move {
+            let _ = handle_http_connection(stream, &gateway_url, &captured, &original_responses)
+                .await
+                .is_ok();
+        });
+    }
+}
+
+/// Get the token of an interaction from the path of a callback or webhook request
+fn get_interaction_token(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "interactions" || *segment == "webhooks")?;
+    // Skip the interaction id or application id
+    segments.nth(1)
+}
+
+/// Handle a single http request, then close the connection
+async fn handle_http_connection(
+    mut stream: TcpStream,
+    gateway_url: &str,
+    captured: &Mutex<Vec<CapturedRequest>>,
+    original_responses: &Mutex<HashMap<String, u64>>,
+) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    let header_end = loop {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+
+        if let Some(i) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break i + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _value)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_name, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let mut body = buffer.split_off(header_end);
+    while body.len() < content_length {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    let request = CapturedRequest { method, path, body };
+    let (status, response_body) = if request.path.ends_with("/gateway") {
+        ("200 OK", json!({ "url": gateway_url }).to_string())
+    } else if request.path.ends_with("/callback") {
+        let token = get_interaction_token(&request.path)
+            .unwrap_or_default()
+            .to_string();
+        let json = request.json().unwrap_or_default();
+        let mut flags = json["data"]["flags"].as_u64().unwrap_or(0);
+        // Deferring shows a loading message
+        if json["type"].as_u64() == Some(5) {
+            flags |= LOADING_FLAG;
+        }
+
+        match original_responses.lock().await.entry(token) {
+            Entry::Occupied(_entry) => (
+                "400 Bad Request",
+                json!({
+                    "message": "Interaction has already been acknowledged.",
+                    "code": 40060,
+                })
+                .to_string(),
+            ),
+            Entry::Vacant(entry) => {
+                entry.insert(flags);
+                ("204 No Content", String::new())
+            }
+        }
+    } else if request.method == "GET" && request.path.ends_with("/messages/@original") {
+        let token = get_interaction_token(&request.path).unwrap_or_default();
+        match original_responses.lock().await.get(token) {
+            Some(flags) => {
+                let mut message = create_fake_message(None);
+                message["flags"] = (*flags).into();
+                ("200 OK", message.to_string())
+            }
+            None => (
+                "404 Not Found",
+                json!({ "message": "Unknown Webhook", "code": 10015 }).to_string(),
+            ),
+        }
+    } else if request.method == "DELETE" && request.path.contains("/webhooks/") {
+        ("204 No Content", String::new())
+    } else if request.path.contains("/webhooks/") {
+        let content = request.content();
+        ("200 OK", create_fake_message(content).to_string())
+    } else {
+        (
+            "404 Not Found",
+            json!({ "message": "Unknown route", "code": 0 }).to_string(),
+        )
+    };
+
+    if !request.path.ends_with("/gateway") {
+        captured.lock().await.push(request);
+    }
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response_body}",
+        response_body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+/// Run the fake gateway.
+///
+/// This only accepts one connection, and forwards events to it after it identifies.
+async fn run_gateway_server(listener: TcpListener, mut event_rx: mpsc::UnboundedReceiver<Value>) {
+    let stream = match listener.accept().await {
+        Ok((stream, _addr)) => stream,
+        Err(_error) => return,
+    };
+    let mut ws = match tokio_tungstenite::accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(_error) => return,
+    };
+
+    let hello = json!({ "op": 10, "d": { "heartbeat_interval": 45_000 } });
+    if ws.send(WsMessage::text(hello.to_string())).await.is_err() {
+        return;
+    }
+
+    let mut seq = 0;
+    let mut identified = false;
+    loop {
+        tokio::select! {
+            message = ws.next() => {
+                let message = match message {
+                    Some(Ok(WsMessage::Text(message))) => message,
+                    Some(Ok(_message)) => continue,
+                    Some(Err(_)) | None => return,
+                };
+                let message: Value = match serde_json::from_str(&message) {
+                    Ok(message) => message,
+                    Err(_error) => continue,
+                };
+
+                let response = match message["op"].as_u64() {
+                    // Heartbeat
+                    Some(1) => json!({ "op": 11 }),
+                    // Identify
+                    Some(2) => {
+                        identified = true;
+                        seq += 1;
+                        json!({
+                            "op": 0,
+                            "s": seq,
+                            "t": "READY",
+                            "d": {
+                                "v": 10,
+                                "user": {
+                                    "id": BOT_USER_ID.to_string(),
+                                    "username": "test-bot",
+                                    "global_name": null,
+                                    "avatar": null,
+                                    "bot": true,
+                                },
+                                "guilds": [],
+                                "session_id": "test-session",
+                                "resume_gateway_url": "ws://127.0.0.1",
+                                "application": {
+                                    "id": APPLICATION_ID.to_string(),
+                                    "flags": 0,
+                                },
+                            },
+                        })
+                    }
+                    _ => continue,
+                };
+
+                if ws.send(WsMessage::text(response.to_string())).await.is_err() {
+                    return;
+                }
+            }
+            event = event_rx.recv(), if identified => {
+                let event = match event {
+                    Some(event) => event,
+                    None => return,
+                };
+
+                seq += 1;
+                let dispatch = json!({
+                    "op": 0,
+                    "s": seq,
+                    "t": "INTERACTION_CREATE",
+                    "d": event,
+                });
+                if ws.send(WsMessage::text(dispatch.to_string())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        BoxFuture,
+        Bucket,
+        BucketBuilder,
+        BucketScope,
+        BuilderError,
+        Command,
+        CommandBuilder,
+        ComponentHandler,
+        ComponentHandlerBuilder,
+        FrameworkBuilder,
+        FromOptions,
+        Reason,
+        Response,
+        ResponseState,
+        State,
+    };
+    use serenity::{
+        builder::{
+            CreateInteractionResponse,
+            CreateInteractionResponseMessage,
+        },
+        model::application::CommandInteraction,
+    };
+
+    #[derive(Debug, crate::FromOptions)]
+    struct EchoOptions {
+        #[pikadick_slash_framework(description = "The text to echo")]
+        text: String,
+
+        #[pikadick_slash_framework(description = "The number of times to echo")]
+        times: Option<i64>,
+    }
+
+    fn create_echo_command() -> Command {
+        CommandBuilder::new()
+            .name("echo")
+            .description("Echo some text")
+            .arguments(
+                EchoOptions::get_argument_params()
+                    .expect("invalid arguments")
+                    .into_iter(),
+            )
+            .on_process(|ctx, interaction, args: EchoOptions| async move {
+                let times = usize::try_from(args.times.unwrap_or(1))?;
+                let message_builder =
+                    CreateInteractionResponseMessage::new().content(args.text.repeat(times));
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            })
+            .build()
+            .expect("failed to build echo command")
+    }
+
+    fn deny_check<'a>(
+        _ctx: &'a Context,
+        _interaction: &'a CommandInteraction,
+        _command: &'a Command,
+    ) -> BoxFuture<'a, Result<(), Reason>> {
+        Box::pin(async move { Err(Reason::new_user("denied")) })
+    }
+
+    #[tokio::test]
+    async fn command_responds() {
+        let framework = FrameworkBuilder::new()
+            .command(create_echo_command())
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(
+                InteractionBuilder::command("echo")
+                    .string_option("text", "hi")
+                    .integer_option("times", 3),
+            )
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].is_interaction_response());
+        assert_eq!(requests[0].content().as_deref(), Some("hihihi"));
+    }
+
+    #[tokio::test]
+    async fn invalid_arguments_call_on_error() {
+        let framework = FrameworkBuilder::new()
+            .command(create_echo_command())
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(InteractionBuilder::command("echo").integer_option("times", 3))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 1);
+        let content = requests[0].content().expect("missing content");
+        assert!(content.starts_with("Invalid arguments"), "{content}");
+    }
+
+    #[tokio::test]
+    async fn component_error_calls_on_error() {
+        let handler = ComponentHandlerBuilder::new()
+            .prefix("fail")
+            .on_process(
+                |_ctx, _interaction, _state: ()| async move { Err("component failed".into()) },
+            )
+            .build()
+            .expect("failed to build handler");
+        let framework = FrameworkBuilder::new()
+            .component_handler(handler)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::component("fail:"))
+            .await
+            .expect("failed to run component");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].content().as_deref(),
+            Some("The command failed: component failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn nsfw_component_needs_nsfw_channel() {
+        let handler = ComponentHandlerBuilder::new()
+            .prefix("nsfw")
+            .nsfw(true)
+            .on_process(|_ctx, _interaction, _state: ()| async move {
+                panic!("nsfw component ran outside of an nsfw channel")
+            })
+            .build()
+            .expect("failed to build handler");
+        let framework = FrameworkBuilder::new()
+            .component_handler(handler)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        // The fake api does not know the channel, so it is not age-restricted.
+        let requests = harness
+            .run(InteractionBuilder::component("nsfw:").guild_id(GuildId::new(10)))
+            .await
+            .expect("failed to run component");
+        let content = requests
+            .iter()
+            .find_map(|request| request.content())
+            .expect("missing reply");
+        assert_eq!(
+            content,
+            "This command can only be used in age-restricted channels"
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_check_stops_command() {
+        let framework = FrameworkBuilder::new()
+            .check(deny_check)
+            .command(create_echo_command())
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(InteractionBuilder::command("echo").string_option("text", "hi"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].content().as_deref(), Some("denied"));
+    }
+
+    #[tokio::test]
+    async fn bucket_limits_command() {
+        let command = CommandBuilder::new()
+            .name("ping")
+            .description("Pong")
+            .bucket("ping")
+            .on_process(|ctx, interaction, _args: ()| async move {
+                let message_builder = CreateInteractionResponseMessage::new().content("pong");
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            })
+            .build()
+            .expect("failed to build ping command");
+        let bucket = BucketBuilder::new()
+            .scope(BucketScope::User)
+            .delay(Duration::from_secs(60))
+            .burst(2)
+            .build()
+            .expect("failed to build bucket");
+        let framework = FrameworkBuilder::new()
+            .bucket("ping", bucket)
+            .command(command)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        for _ in 0..2 {
+            let requests = harness
+                .run(&InteractionBuilder::command("ping"))
+                .await
+                .expect("failed to run command");
+            assert_eq!(requests[0].content().as_deref(), Some("pong"));
+        }
+
+        let requests = harness
+            .run(&InteractionBuilder::command("ping"))
+            .await
+            .expect("failed to run command");
+        let content = requests[0].content().expect("missing content");
+        assert!(content.starts_with("Try again in"), "{content}");
+    }
+
+    fn create_pong_command(name: &str, bucket: &str) -> Command {
+        CommandBuilder::new()
+            .name(name)
+            .description("Pong")
+            .bucket(bucket)
+            .on_process(|ctx, interaction, _args: ()| async move {
+                let message_builder = CreateInteractionResponseMessage::new().content("pong");
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            })
+            .build()
+            .expect("failed to build pong command")
+    }
+
+    fn create_user_bucket(burst: u32) -> Bucket {
+        BucketBuilder::new()
+            .scope(BucketScope::User)
+            .delay(Duration::from_secs(60))
+            .burst(burst)
+            .build()
+            .expect("failed to build bucket")
+    }
+
+    #[tokio::test]
+    async fn bucket_limits_component() {
+        let handler = ComponentHandlerBuilder::new()
+            .prefix("again")
+            .command("ping")
+            .on_process(|ctx, interaction, _state: ()| async move {
+                let message_builder = CreateInteractionResponseMessage::new().content("pong");
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            })
+            .build()
+            .expect("failed to build again component handler");
+        let framework = FrameworkBuilder::new()
+            .bucket("ping", create_user_bucket(1))
+            .command(create_pong_command("ping", "ping"))
+            .component_handler(handler)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::component("again:"))
+            .await
+            .expect("failed to run component");
+        assert_eq!(requests[0].content().as_deref(), Some("pong"));
+
+        let requests = harness
+            .run(&InteractionBuilder::component("again:"))
+            .await
+            .expect("failed to run component");
+        let content = requests[0].content().expect("missing content");
+        assert!(content.starts_with("Try again in"), "{content}");
+    }
+
+    #[tokio::test]
+    async fn limited_subcommand_keeps_parent_bucket() {
+        let command = CommandBuilder::new()
+            .name("game")
+            .description("Play a game")
+            .bucket("game")
+            .subcommand(create_pong_command("play", "play"))
+            .subcommand(create_pong_command("stats", "stats"))
+            .build()
+            .expect("failed to build game command");
+        let framework = FrameworkBuilder::new()
+            .bucket("game", create_user_bucket(2))
+            .bucket("play", create_user_bucket(1))
+            .bucket("stats", create_user_bucket(1))
+            .command(command)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        for expected in [true, false] {
+            let requests = harness
+                .run(InteractionBuilder::command("game").subcommand("play"))
+                .await
+                .expect("failed to run command");
+            let content = requests[0].content().expect("missing content");
+            assert_eq!(content == "pong", expected, "{content}");
+        }
+
+        // The limited play did not use up the second use of the game bucket
+        let requests = harness
+            .run(InteractionBuilder::command("game").subcommand("stats"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests[0].content().as_deref(), Some("pong"));
+    }
+
+    #[tokio::test]
+    async fn response_sends_followups() {
+        let command = CommandBuilder::new()
+            .name("count")
+            .description("Count to 2")
+            .on_process(|ctx, interaction, _args: ()| async move {
+                let mut response = Response::new(&ctx, &interaction);
+                response.send_content("1").await?;
+                response.send_content("2").await?;
+                assert_eq!(response.state(), ResponseState::Responded);
+
+                Ok(())
+            })
+            .build()
+            .expect("failed to build count command");
+        let framework = FrameworkBuilder::new()
+            .command(command)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::command("count"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].is_interaction_response());
+        assert_eq!(requests[0].content().as_deref(), Some("1"));
+        assert!(!requests[1].is_interaction_response());
+        assert_eq!(requests[1].content().as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn error_after_defer_is_ephemeral() {
+        let command = CommandBuilder::new()
+            .name("slow")
+            .description("Fail after deferring")
+            .on_process(|ctx, interaction, _args: ()| async move {
+                let mut response = Response::new(&ctx, &interaction);
+                response.defer().await?;
+
+                Err("slow failed".into())
+            })
+            .build()
+            .expect("failed to build slow command");
+        let framework = FrameworkBuilder::new()
+            .command(command)
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::command("slow"))
+            .await
+            .expect("failed to run command");
+
+        // The public loading message is deleted before the error is sent
+        assert!(requests
+            .iter()
+            .any(|request| request.method == "DELETE" && request.path.ends_with("/@original")));
+        let followup = requests.last().expect("missing followup");
+        assert_eq!(followup.method, "POST");
+        assert!(!followup.is_interaction_response());
+        assert_eq!(
+            followup.content().as_deref(),
+            Some("The command failed: slow failed")
+        );
+        let flags = followup.json().expect("missing body")["flags"]
+            .as_u64()
+            .unwrap_or(0);
+        assert_eq!(flags & (1 << 6), 1 << 6, "followup is not ephemeral");
+    }
+
+    fn create_greet_command() -> Command {
+        CommandBuilder::new()
+            .name("greet")
+            .description("Greet someone")
+            .on_process_with_state(
+                |ctx, interaction, _args: (), greeting: State<String>| async move {
+                    let message_builder =
+                        CreateInteractionResponseMessage::new().content(greeting.as_str());
+                    let response = CreateInteractionResponse::Message(message_builder);
+                    interaction.create_response(&ctx.http, response).await?;
+
+                    Ok(())
+                },
+            )
+            .build()
+            .expect("failed to build greet command")
+    }
+
+    #[tokio::test]
+    async fn state_is_injected() {
+        let framework = FrameworkBuilder::new()
+            .state(String::from("hello"))
+            .command(create_greet_command())
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::command("greet"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests[0].content().as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn missing_state_fails_build() {
+        let result = FrameworkBuilder::new()
+            .command(create_greet_command())
+            .build();
+        assert!(matches!(result, Err(BuilderError::MissingState(_))));
+    }
+
+    fn create_greet_component_handler() -> ComponentHandler {
+        ComponentHandlerBuilder::new()
+            .prefix("greet")
+            .on_process_with_state(
+                |ctx, interaction, name: String, greeting: State<String>| async move {
+                    let content = format!("{} {name}", greeting.as_str());
+                    let message_builder = CreateInteractionResponseMessage::new().content(content);
+                    let response = CreateInteractionResponse::Message(message_builder);
+                    interaction.create_response(&ctx.http, response).await?;
+
+                    Ok(())
+                },
+            )
+            .build()
+            .expect("failed to build greet component handler")
+    }
+
+    #[tokio::test]
+    async fn component_state_is_injected() {
+        let framework = FrameworkBuilder::new()
+            .state(String::from("hello"))
+            .component_handler(create_greet_component_handler())
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::component("greet:world"))
+            .await
+            .expect("failed to run component");
+        assert_eq!(requests[0].content().as_deref(), Some("hello world"));
+    }
+
+    #[test]
+    fn missing_component_state_fails_build() {
+        let result = FrameworkBuilder::new()
+            .component_handler(create_greet_component_handler())
+            .build();
+        assert!(matches!(result, Err(BuilderError::MissingState(_))));
+    }
+
+    #[tokio::test]
+    async fn unknown_command() {
+        let framework = FrameworkBuilder::new()
+            .build()
+            .expect("failed to build framework");
+        let harness = TestHarness::new(framework)
+            .await
+            .expect("failed to start harness");
+
+        let requests = harness
+            .run(&InteractionBuilder::command("missing"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].content().as_deref(), Some("Unknown command"));
+    }
+}
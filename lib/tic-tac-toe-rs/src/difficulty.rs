@@ -0,0 +1,165 @@
+use crate::{
+    minimax,
+    Board,
+    NUM_TILES,
+};
+use rand::Rng;
+
+/// Failed to parse a [`Difficulty`] from a [`str`].
+#[derive(Debug, Clone)]
+pub struct InvalidDifficultyError(pub String);
+
+impl std::fmt::Display for InvalidDifficultyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a valid Tic-Tac-Toe difficulty", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDifficultyError {}
+
+/// The difficulty of a computer player
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Difficulty {
+    /// Only looks for immediate wins, and often makes mistakes
+    Easy,
+
+    /// Looks a few moves ahead, and sometimes makes mistakes
+    Medium,
+
+    /// Plays perfectly
+    #[default]
+    Hard,
+}
+
+impl Difficulty {
+    /// All difficulties, from easiest to hardest
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Medium, Self::Hard];
+
+    /// Get the name of this difficulty, like "easy"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Easy => "easy",
+            Self::Medium => "medium",
+            Self::Hard => "hard",
+        }
+    }
+
+    /// Get the depth of the [`minimax`] search
+    pub fn depth(self) -> u8 {
+        match self {
+            Self::Easy => 2,
+            Self::Medium => 4,
+            Self::Hard => NUM_TILES,
+        }
+    }
+
+    /// Get the weights of moves that lose, are unknown or draw, and win, in that order.
+    ///
+    /// Returns `None` if the best move is always picked.
+    fn weights(self) -> Option<[u32; 3]> {
+        match self {
+            Self::Easy => Some([1, 2, 4]),
+            Self::Medium => Some([1, 6, 36]),
+            Self::Hard => None,
+        }
+    }
+
+    /// Choose a move for the current player.
+    ///
+    /// Moves are scored with a depth-limited [`minimax`] and picked at random,
+    /// weighted towards better scores.
+    /// [`Difficulty::Hard`] always picks the best move.
+    ///
+    /// # Panics
+    /// Panics if the board has no open tiles.
+    pub fn choose_move<R>(self, board: Board, rng: &mut R) -> u8
+    where
+        R: Rng + ?Sized,
+    {
+        let weights = match self.weights() {
+            Some(weights) => weights,
+            None => return minimax(board, self.depth()).1,
+        };
+
+        let moves: Vec<(u8, u32)> = board
+            .iter_children()
+            .map(|(index, child)| {
+                let (score, _index) = minimax(child, self.depth() - 1);
+                let weight = match -score {
+                    ..=-1 => weights[0],
+                    0 => weights[1],
+                    1.. => weights[2],
+                };
+                (index, weight)
+            })
+            .collect();
+        assert!(!moves.is_empty(), "no moves to choose from");
+
+        let total: u32 = moves.iter().map(|(_index, weight)| weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for (index, weight) in moves.iter().copied() {
+            if roll < weight {
+                return index;
+            }
+            roll -= weight;
+        }
+
+        unreachable!("roll is less than the total weight")
+    }
+}
+
+impl std::str::FromStr for Difficulty {
+    type Err = InvalidDifficultyError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.as_str().eq_ignore_ascii_case(input))
+            .ok_or_else(|| InvalidDifficultyError(input.to_string()))
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Team;
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    #[test]
+    fn choose_move_is_legal() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let board = Board::new()
+            .set(0, Some(Team::X))
+            .set(4, Some(Team::O))
+            .set(8, Some(Team::X));
+
+        for difficulty in Difficulty::ALL {
+            for _ in 0..100 {
+                let index = difficulty.choose_move(board, &mut rng);
+                assert!(
+                    board.get(index).is_none(),
+                    "{difficulty} chose a taken tile"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hard_is_perfect() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let board = Board::new();
+        assert_eq!(
+            Difficulty::Hard.choose_move(board, &mut rng),
+            minimax(board, NUM_TILES).1
+        );
+    }
+}
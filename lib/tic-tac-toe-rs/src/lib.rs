@@ -1,6 +1,7 @@
 #![allow(clippy::uninlined_format_args)]
 
 pub mod board;
+pub mod difficulty;
 pub mod team;
 
 pub use self::{
@@ -9,6 +10,7 @@ pub use self::{
         WinType,
         WinnerInfo,
     },
+    difficulty::Difficulty,
     team::Team,
 };
 
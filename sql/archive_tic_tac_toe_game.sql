@@ -0,0 +1,25 @@
+INSERT INTO tic_tac_toe_history (
+    guild_id, 
+    x_player, 
+    o_player, 
+    board, 
+    moves, 
+    result, 
+    started_at, 
+    ended_at, 
+    difficulty
+) 
+SELECT 
+    guild_id, 
+    x_player, 
+    o_player, 
+    board, 
+    moves, 
+    ?, 
+    created_at, 
+    unixepoch(), 
+    difficulty 
+FROM 
+    tic_tac_toe_games 
+WHERE 
+    id = ?;
\ No newline at end of file
@@ -0,0 +1,11 @@
+SELECT 
+    red_player,
+    yellow_player
+FROM 
+    connect_four_games 
+WHERE 
+    guild_id = :guild_id AND 
+    (
+        red_player IN (:author, :opponent) OR 
+        yellow_player IN (:author, :opponent)
+    );
\ No newline at end of file
@@ -0,0 +1,16 @@
+UPDATE 
+    tic_tac_toe_games 
+SET 
+    warned = 1 
+WHERE 
+    warned = 0 AND 
+    last_move_at <= unixepoch() - ? 
+RETURNING 
+    id, 
+    guild_id, 
+    board, 
+    x_player, 
+    o_player, 
+    channel_id, 
+    last_move_at, 
+    difficulty;
\ No newline at end of file
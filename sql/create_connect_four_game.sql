@@ -0,0 +1,11 @@
+INSERT INTO connect_four_games (
+    board, 
+    red_player, 
+    yellow_player,
+    guild_id
+) VALUES (
+    ?, 
+    ?, 
+    ?, 
+    ?
+);
\ No newline at end of file
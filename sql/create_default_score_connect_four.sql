@@ -0,0 +1,7 @@
+INSERT OR IGNORE INTO connect_four_scores (
+    guild_id, 
+    player
+) VALUES (
+    ?, 
+    ?
+);
\ No newline at end of file
@@ -2,10 +2,20 @@ INSERT INTO tic_tac_toe_games (
     board, 
     x_player, 
     o_player,
-    guild_id
+    guild_id,
+    moves,
+    channel_id,
+    difficulty,
+    created_at,
+    last_move_at
 ) VALUES (
     ?, 
     ?, 
     ?, 
-    ?
+    ?,
+    ?,
+    ?,
+    ?,
+    unixepoch(),
+    unixepoch()
 );
\ No newline at end of file
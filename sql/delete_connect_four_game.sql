@@ -0,0 +1,2 @@
+DELETE FROM connect_four_games 
+WHERE id = ?;
\ No newline at end of file
@@ -0,0 +1,10 @@
+SELECT 
+    id,
+    board, 
+    red_player, 
+    yellow_player
+FROM 
+    connect_four_games 
+WHERE
+    guild_id = :guild_id AND 
+    (red_player = :user_id OR yellow_player = :user_id);
\ No newline at end of file
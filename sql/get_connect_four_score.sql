@@ -0,0 +1,9 @@
+SELECT 
+    wins, 
+    losses, 
+    ties, 
+    concedes 
+FROM 
+    connect_four_scores 
+WHERE 
+    guild_id = ? AND player = ?;
\ No newline at end of file
@@ -0,0 +1,13 @@
+SELECT 
+    id, 
+    guild_id, 
+    board, 
+    x_player, 
+    o_player, 
+    channel_id, 
+    last_move_at, 
+    difficulty 
+FROM 
+    tic_tac_toe_games 
+WHERE 
+    last_move_at <= unixepoch() - ?;
\ No newline at end of file
@@ -0,0 +1,17 @@
+SELECT 
+    SUM(rating * rated_games) / SUM(rated_games) AS global_rating, 
+    player, 
+    SUM(wins), 
+    SUM(losses), 
+    SUM(ties), 
+    SUM(concedes) 
+FROM 
+    tic_tac_toe_scores 
+GROUP BY 
+    player 
+HAVING 
+    SUM(rated_games) > 0 
+ORDER BY 
+    global_rating DESC
+LIMIT 
+    10;
\ No newline at end of file
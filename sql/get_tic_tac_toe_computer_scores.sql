@@ -0,0 +1,14 @@
+SELECT 
+    difficulty, 
+    SUM((result = 'x-win' AND x_player IS :player) OR (result = 'o-win' AND o_player IS :player)), 
+    SUM((result = 'x-win' AND o_player IS :player) OR (result = 'o-win' AND x_player IS :player)), 
+    SUM(result = 'draw'), 
+    SUM((result = 'x-concede' AND x_player IS :player) OR (result = 'o-concede' AND o_player IS :player)) 
+FROM 
+    tic_tac_toe_history 
+WHERE 
+    guild_id = :guild_id AND 
+    difficulty IS NOT NULL AND 
+    (x_player = :player OR o_player = :player) 
+GROUP BY 
+    difficulty;
\ No newline at end of file
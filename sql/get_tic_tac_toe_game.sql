@@ -2,7 +2,8 @@ SELECT
     id,
     board, 
     x_player, 
-    o_player
+    o_player, 
+    difficulty
 FROM 
     tic_tac_toe_games 
 WHERE
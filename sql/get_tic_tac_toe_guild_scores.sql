@@ -0,0 +1,15 @@
+SELECT 
+    guild_id, 
+    wins, 
+    losses, 
+    ties, 
+    concedes, 
+    rating, 
+    rated_games 
+FROM 
+    tic_tac_toe_scores 
+WHERE 
+    player = ? AND 
+    rated_games > 0 
+ORDER BY 
+    rated_games DESC;
\ No newline at end of file
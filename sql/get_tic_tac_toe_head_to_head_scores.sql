@@ -0,0 +1,18 @@
+SELECT 
+    CASE WHEN x_player = :player THEN o_player ELSE x_player END AS opponent, 
+    SUM((result IN ('x-win', 'o-concede') AND x_player = :player) OR (result IN ('o-win', 'x-concede') AND o_player = :player)), 
+    SUM((result = 'x-win' AND o_player = :player) OR (result = 'o-win' AND x_player = :player)), 
+    SUM(result = 'draw'), 
+    SUM((result = 'x-concede' AND x_player = :player) OR (result = 'o-concede' AND o_player = :player)) 
+FROM 
+    tic_tac_toe_history 
+WHERE 
+    x_player IS NOT NULL AND 
+    o_player IS NOT NULL AND 
+    (x_player = :player OR o_player = :player) 
+GROUP BY 
+    opponent 
+ORDER BY 
+    COUNT(*) DESC 
+LIMIT 
+    10;
\ No newline at end of file
@@ -0,0 +1,19 @@
+SELECT 
+    id, 
+    x_player, 
+    o_player, 
+    board, 
+    moves, 
+    result, 
+    started_at, 
+    ended_at, 
+    difficulty 
+FROM 
+    tic_tac_toe_history 
+WHERE 
+    guild_id = :guild_id AND 
+    (x_player = :player OR o_player = :player) 
+ORDER BY 
+    id DESC 
+LIMIT 
+    10;
\ No newline at end of file
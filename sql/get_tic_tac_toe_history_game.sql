@@ -0,0 +1,14 @@
+SELECT 
+    id, 
+    x_player, 
+    o_player, 
+    board, 
+    moves, 
+    result, 
+    started_at, 
+    ended_at, 
+    difficulty 
+FROM 
+    tic_tac_toe_history 
+WHERE 
+    id = ? AND guild_id = ?;
\ No newline at end of file
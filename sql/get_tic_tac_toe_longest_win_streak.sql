@@ -0,0 +1,32 @@
+WITH results AS (
+    SELECT 
+        id, 
+        (result IN ('x-win', 'o-concede') AND x_player = :player) OR (result IN ('o-win', 'x-concede') AND o_player = :player) AS won 
+    FROM 
+        tic_tac_toe_history 
+    WHERE 
+        x_player IS NOT NULL AND 
+        o_player IS NOT NULL AND 
+        (x_player = :player OR o_player = :player)
+), 
+-- Every loss, tie or concede starts a new streak, 
+-- so wins with the same # of earlier non-wins are part of the same streak.
+streaks AS (
+    SELECT 
+        won, 
+        SUM(NOT won) OVER (ORDER BY id) AS streak 
+    FROM 
+        results
+) 
+SELECT 
+    COUNT(*) AS length 
+FROM 
+    streaks 
+WHERE 
+    won 
+GROUP BY 
+    streak 
+ORDER BY 
+    length DESC 
+LIMIT 
+    1;
\ No newline at end of file
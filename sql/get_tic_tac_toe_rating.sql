@@ -0,0 +1,6 @@
+SELECT 
+    rating 
+FROM 
+    tic_tac_toe_scores 
+WHERE 
+    guild_id = ? AND player = ?;
\ No newline at end of file
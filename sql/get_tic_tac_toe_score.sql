@@ -2,7 +2,8 @@ SELECT
     wins, 
     losses, 
     ties, 
-    concedes 
+    concedes, 
+    rating 
 FROM 
     tic_tac_toe_scores 
 WHERE 
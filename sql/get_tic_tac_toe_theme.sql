@@ -0,0 +1,6 @@
+SELECT 
+    theme 
+FROM 
+    tic_tac_toe_guild_settings 
+WHERE 
+    guild_id = ?;
\ No newline at end of file
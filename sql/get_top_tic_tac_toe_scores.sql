@@ -1,5 +1,5 @@
 SELECT 
-    (wins - (losses + concedes)) as score, 
+    rating, 
     player, 
     wins, 
     losses, 
@@ -8,8 +8,9 @@ SELECT
 FROM 
     tic_tac_toe_scores 
 WHERE 
-    guild_id = ? 
+    guild_id = ? AND 
+    rated_games > 0 
 ORDER BY 
-    score DESC
+    rating DESC
 LIMIT 
     10;
\ No newline at end of file
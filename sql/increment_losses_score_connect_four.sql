@@ -0,0 +1,6 @@
+UPDATE 
+    connect_four_scores 
+SET 
+    losses = losses + 1 
+WHERE 
+    guild_id = ? AND player = ?;
\ No newline at end of file
@@ -0,0 +1,6 @@
+UPDATE 
+    connect_four_scores 
+SET 
+    ties = ties + 1 
+WHERE 
+    guild_id = ? AND player IN (?, ?);
\ No newline at end of file
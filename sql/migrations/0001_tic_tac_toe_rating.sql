@@ -0,0 +1,15 @@
+ALTER TABLE tic_tac_toe_scores ADD COLUMN rating REAL NOT NULL DEFAULT 1500.0 CHECK(TYPEOF(rating) = 'real');
+
+-- The number of games that changed the rating, including games against the computer.
+ALTER TABLE tic_tac_toe_scores ADD COLUMN rated_games INTEGER NOT NULL DEFAULT 0 CHECK(TYPEOF(rated_games) = 'integer');
+
+-- Backfill ratings from existing scores.
+-- Game order was not recorded, so this uses the performance rating against an average opponent,
+-- counting concedes as losses.
+UPDATE 
+    tic_tac_toe_scores 
+SET 
+    rating = 1500.0 + ((400.0 * (wins - (losses + concedes))) / (wins + losses + concedes + ties)), 
+    rated_games = wins + losses + concedes + ties 
+WHERE 
+    (wins + losses + concedes + ties) > 0;
@@ -0,0 +1,19 @@
+-- Moves are stored as a string of tile indices, in order.
+-- Games from before this migration have no recorded moves or start time.
+ALTER TABLE tic_tac_toe_games ADD COLUMN moves TEXT NOT NULL DEFAULT '' CHECK(TYPEOF(moves) = 'text');
+ALTER TABLE tic_tac_toe_games ADD COLUMN created_at INTEGER NULL CHECK(TYPEOF(created_at) IN ('integer', 'null'));
+
+CREATE TABLE tic_tac_toe_history (
+    id INTEGER PRIMARY KEY NOT NULL,
+    guild_id TEXT NOT NULL,
+    x_player INTEGER NULL,
+    o_player INTEGER NULL,
+    board INTEGER NOT NULL,
+    moves TEXT NOT NULL,
+    result TEXT NOT NULL CHECK(result IN ('x-win', 'o-win', 'draw', 'x-concede', 'o-concede')),
+    started_at INTEGER NULL,
+    ended_at INTEGER NOT NULL
+) STRICT;
+
+CREATE INDEX tic_tac_toe_history_x_player_index ON tic_tac_toe_history (guild_id, x_player);
+CREATE INDEX tic_tac_toe_history_o_player_index ON tic_tac_toe_history (guild_id, o_player);
@@ -0,0 +1,10 @@
+-- The channel of the last move, where inactivity warnings are sent.
+-- Games from before this migration have no channel, so their players are sent DMs.
+ALTER TABLE tic_tac_toe_games ADD COLUMN channel_id INTEGER NULL CHECK(TYPEOF(channel_id) IN ('integer', 'null'));
+ALTER TABLE tic_tac_toe_games ADD COLUMN last_move_at INTEGER NOT NULL DEFAULT 0 CHECK(TYPEOF(last_move_at) = 'integer');
+ALTER TABLE tic_tac_toe_games ADD COLUMN warned INTEGER NOT NULL DEFAULT 0 CHECK(warned IN (0, 1));
+
+-- Give existing games the full timeout, starting now
+UPDATE tic_tac_toe_games SET last_move_at = unixepoch();
+
+CREATE INDEX tic_tac_toe_games_last_move_at_index ON tic_tac_toe_games (last_move_at);
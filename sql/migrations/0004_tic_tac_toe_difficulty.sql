@@ -0,0 +1,7 @@
+-- The difficulty of the computer, or NULL for games between users
+ALTER TABLE tic_tac_toe_games ADD COLUMN difficulty TEXT NULL CHECK(difficulty IN ('easy', 'medium', 'hard'));
+ALTER TABLE tic_tac_toe_history ADD COLUMN difficulty TEXT NULL CHECK(difficulty IN ('easy', 'medium', 'hard'));
+
+-- The computer always played perfectly before difficulties were added
+UPDATE tic_tac_toe_games SET difficulty = 'hard' WHERE x_player IS NULL OR o_player IS NULL;
+UPDATE tic_tac_toe_history SET difficulty = 'hard' WHERE x_player IS NULL OR o_player IS NULL;
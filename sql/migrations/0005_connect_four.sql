@@ -0,0 +1,19 @@
+CREATE TABLE connect_four_games (
+    id INTEGER PRIMARY KEY NOT NULL,
+    guild_id TEXT NOT NULL,
+    board INTEGER NOT NULL,
+    red_player INTEGER NULL,
+    yellow_player INTEGER NULL,
+    UNIQUE (guild_id, red_player),
+    UNIQUE (guild_id, yellow_player)
+) STRICT;
+
+CREATE TABLE connect_four_scores (
+    guild_id TEXT NOT NULL,
+    player INTEGER NOT NULL,
+    wins INTEGER NOT NULL DEFAULT 0,
+    losses INTEGER NOT NULL DEFAULT 0,
+    ties INTEGER NOT NULL DEFAULT 0,
+    concedes INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, player)
+) STRICT;
@@ -0,0 +1,5 @@
+-- The Tic-Tac-Toe board theme of each server
+CREATE TABLE tic_tac_toe_guild_settings (
+    guild_id TEXT NOT NULL PRIMARY KEY UNIQUE,
+    theme TEXT NOT NULL CHECK(theme IN ('classic', 'dark', 'light', 'minimal', 'large'))
+) STRICT;
@@ -0,0 +1,4 @@
+-- Profiles and the global scoreboard look up players across every server
+CREATE INDEX tic_tac_toe_scores_player_index ON tic_tac_toe_scores (player);
+CREATE INDEX tic_tac_toe_history_x_player_global_index ON tic_tac_toe_history (x_player);
+CREATE INDEX tic_tac_toe_history_o_player_global_index ON tic_tac_toe_history (o_player);
@@ -0,0 +1,7 @@
+UPDATE 
+    tic_tac_toe_scores 
+SET 
+    rating = ?, 
+    rated_games = rated_games + 1 
+WHERE 
+    guild_id = ? AND player = ?;
\ No newline at end of file
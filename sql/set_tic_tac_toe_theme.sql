@@ -0,0 +1,7 @@
+INSERT OR REPLACE INTO tic_tac_toe_guild_settings (
+    guild_id, 
+    theme
+) VALUES (
+    ?, 
+    ?
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+UPDATE connect_four_games 
+SET 
+    board = ? 
+WHERE id = ?;
\ No newline at end of file
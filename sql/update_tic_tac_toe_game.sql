@@ -1,3 +1,8 @@
 UPDATE tic_tac_toe_games 
-SET board = ? 
+SET 
+    board = ?, 
+    moves = moves || ?, 
+    channel_id = ?, 
+    last_move_at = unixepoch(), 
+    warned = 0 
 WHERE id = ?;
\ No newline at end of file
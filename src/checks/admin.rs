@@ -1,6 +1,7 @@
 use pikadick_slash_framework::{
     BoxFuture,
     Command,
+    ModalHandler,
     Reason as SlashReason,
 };
 use serenity::{
@@ -12,7 +13,10 @@ use serenity::{
         Reason,
     },
     model::{
-        application::CommandInteraction,
+        application::{
+            CommandInteraction,
+            ModalInteraction,
+        },
         prelude::*,
     },
 };
@@ -62,36 +66,50 @@ pub fn create_slash_check<'a>(
     interaction: &'a CommandInteraction,
     _command: &'a Command,
 ) -> BoxFuture<'a, Result<(), SlashReason>> {
-    Box::pin(async move {
-        match interaction.guild_id {
-            Some(id) => id,
-            None => {
-                // Let's not care about dms for now.
-                // They'll probably need special handling anyways.
-                // This will also probably only be useful in Group DMs,
-                // which I don't think bots can participate in anyways.
-                return Ok(());
-            }
-        };
+    Box::pin(
+        async move { check_member_is_admin(interaction.guild_id, interaction.member.as_deref()) },
+    )
+}
 
-        match interaction
-            .member
-            .as_ref()
-            .and_then(|member| member.permissions)
-        {
-            Some(permissions) => {
-                if permissions.contains(Permissions::ADMINISTRATOR) {
-                    Ok(())
-                } else {
-                    Err(SlashReason::new_user("Not Admin.".to_string()))
-                }
-            }
-            None => {
-                // Failed to get member permissions.
-                // I don't think this matters since I think this is only absent in dms.
-                warn!("failed to get member permissions");
-                Err(SlashReason::new_unknown())
+/// Ensure a user is admin, for settings modals.
+///
+/// Anyone could submit a modal with a forged custom id, so they need the same check as their command.
+pub fn create_modal_check<'a>(
+    _ctx: &'a Context,
+    interaction: &'a ModalInteraction,
+    _handler: &'a ModalHandler,
+) -> BoxFuture<'a, Result<(), SlashReason>> {
+    Box::pin(
+        async move { check_member_is_admin(interaction.guild_id, interaction.member.as_ref()) },
+    )
+}
+
+/// Ensure the member of an interaction is admin
+fn check_member_is_admin(
+    guild_id: Option<GuildId>,
+    member: Option<&Member>,
+) -> Result<(), SlashReason> {
+    if guild_id.is_none() {
+        // Let's not care about dms for now.
+        // They'll probably need special handling anyways.
+        // This will also probably only be useful in Group DMs,
+        // which I don't think bots can participate in anyways.
+        return Ok(());
+    }
+
+    match member.and_then(|member| member.permissions) {
+        Some(permissions) => {
+            if permissions.contains(Permissions::ADMINISTRATOR) {
+                Ok(())
+            } else {
+                Err(SlashReason::new_user("Not Admin.".to_string()))
             }
         }
-    })
+        None => {
+            // Failed to get member permissions.
+            // I don't think this matters since I think this is only absent in dms.
+            warn!("failed to get member permissions");
+            Err(SlashReason::new_unknown())
+        }
+    }
 }
@@ -1,8 +1,14 @@
-use crate::ClientDataKey;
+use crate::{
+    database::Database,
+    ClientDataKey,
+};
 use parking_lot::Mutex;
 use pikadick_slash_framework::{
     BoxFuture,
     Command,
+    ComponentHandler,
+    ContextMenuCommand,
+    ModalHandler,
     Reason as SlashReason,
 };
 use serenity::{
@@ -16,66 +22,68 @@ use serenity::{
         Reason,
     },
     model::{
-        application::CommandInteraction,
+        application::{
+            CommandDataOptionValue,
+            CommandInteraction,
+            ComponentInteraction,
+            ModalInteraction,
+        },
         prelude::*,
     },
 };
 use std::{
-    collections::HashMap,
+    collections::{
+        BTreeSet,
+        HashMap,
+    },
     sync::Arc,
 };
 use tracing::error;
 
-type MutexGuard<'a, T> = parking_lot::lock_api::MutexGuard<'a, parking_lot::RawMutex, T>;
+/// The seperator between the names of a command and its subcommands in a canonical name
+const NAME_SEPERATOR: &str = "::";
 
+/// A registry of the commands that can be enabled or disabled.
+///
+/// Commands are keyed by a canonical name, which is the command path joined by "::", like "tic-tac-toe::play".
+/// Prefix and slash commands with the same path share a canonical name,
+/// so they are enabled and disabled together.
 #[derive(Debug, Default, Clone)]
 pub struct EnabledCheckData {
-    /// The set of all commands as strings.
-    command_name_cache: Arc<Mutex<Vec<String>>>,
+    /// The canonical names of all commands
+    command_names: Arc<Mutex<BTreeSet<String>>>,
 
-    /// A way to look up commands by CommandOptions and fn addr.
+    /// Canonical names of prefix commands, by the address of their [`CommandOptions`].
     ///
     /// XXX MASSIVE HACK XXX
-    /// This uses the addresses of the `names` field of [`CommandOptions`] impls in order to compare commands.
-    /// This is necessary as this is all serenity gives to [`Check`] functions.
-    /// The only reason this works is because the serenity macro for making commands is used as the only way to make commands,
-    /// as it recreates each names array for each command uniquely.
-    command_lookup: Arc<Mutex<HashMap<usize, String>>>,
+    /// The options are all serenity gives to [`Check`] functions to identify a command,
+    /// and they have no unique id.
+    /// Names can't be used as keys, as subcommands of different commands may share them.
+    /// The only reason this works is because the serenity command macro is the only way commands are made,
+    /// and it makes a separate `static` of options for each command.
+    prefix_command_lookup: Arc<Mutex<HashMap<usize, String>>>,
 }
 
 impl EnabledCheckData {
     /// Make a new [`EnabledCheckData`].
     pub fn new() -> Self {
-        EnabledCheckData {
-            command_name_cache: Arc::new(Mutex::new(Vec::new())),
-            command_lookup: Arc::new(Mutex::new(HashMap::new())),
-        }
+        Self::default()
     }
 
-    /// Add a group to have its commands enabled/disabled.
-    pub fn add_groups(&self, groups: &[&CommandGroup]) {
+    /// Add the commands of prefix groups that have the enabled check.
+    ///
+    /// # Errors
+    /// Fails if a command is reachable from two different command paths, as they could not be told apart.
+    pub fn add_groups(&self, groups: &[&CommandGroup]) -> anyhow::Result<()> {
         let mut names = Vec::with_capacity(4);
         let mut queue = Vec::new();
-        let mut command_name_cache = self.command_name_cache.lock();
-        let mut command_lookup = self.command_lookup.lock();
+        let mut command_names = self.command_names.lock();
+        let mut prefix_command_lookup = self.prefix_command_lookup.lock();
 
         for group in groups.iter() {
-            command_name_cache.reserve(group.options.commands.len());
-            queue.reserve(group.options.commands.len());
-
             queue.extend(group.options.commands.iter().map(|command| (0, command)));
 
             while let Some((depth, command)) = queue.pop() {
-                let has_enabled_check = command
-                    .options
-                    .checks
-                    .iter()
-                    .any(|&check| checks_are_same(check, &ENABLED_CHECK));
-
-                if !has_enabled_check {
-                    continue;
-                }
-
                 names.truncate(depth);
 
                 let command_name = command
@@ -83,14 +91,7 @@ impl EnabledCheckData {
                     .names
                     .first()
                     .expect("command does not have a name");
-
                 names.push(*command_name);
-                let command_name = names.join("::");
-                command_lookup.insert(
-                    command.options.names.as_ptr() as usize,
-                    command_name.clone(),
-                );
-                command_name_cache.push(command_name);
 
                 queue.extend(
                     command
@@ -99,23 +100,141 @@ impl EnabledCheckData {
                         .iter()
                         .map(|command| (depth + 1, command)),
                 );
+
+                let has_enabled_check = command
+                    .options
+                    .checks
+                    .iter()
+                    .any(|&check| checks_are_same(check, &ENABLED_CHECK));
+                if !has_enabled_check {
+                    continue;
+                }
+
+                let command_name = names.join(NAME_SEPERATOR);
+                if let Some(other_name) = prefix_command_lookup.insert(
+                    get_command_options_key(command.options),
+                    command_name.clone(),
+                ) {
+                    anyhow::ensure!(
+                        other_name == command_name,
+                        "prefix command \"{command_name}\" is also registered as \"{other_name}\""
+                    );
+                }
+                command_names.insert(command_name);
             }
         }
+
+        Ok(())
     }
 
+    /// Add the commands, subcommands and context menu commands of a slash framework.
+    pub fn add_slash_framework(&self, framework: &pikadick_slash_framework::Framework) {
+        let mut command_names = self.command_names.lock();
+
+        let mut queue: Vec<(String, &Command)> = framework
+            .commands()
+            .values()
+            .map(|command| (command.name().to_string(), command))
+            .collect();
+        while let Some((command_name, command)) = queue.pop() {
+            queue.extend(command.subcommands().iter().map(|subcommand| {
+                (
+                    format!("{command_name}{NAME_SEPERATOR}{}", subcommand.name()),
+                    subcommand,
+                )
+            }));
+            command_names.insert(command_name);
+        }
+
+        command_names.extend(
+            framework
+                .context_menu_commands()
+                .map(|command| command.name().to_string()),
+        );
+    }
+
+    /// Get the canonical name of a prefix command.
+    ///
+    /// Returns `None` if the command does not have the enabled check.
     pub fn get_command_name_from_options(&self, options: &CommandOptions) -> Option<String> {
-        self.command_lookup
+        self.prefix_command_lookup
             .lock()
-            .get(&(options.names.as_ptr() as usize))
+            .get(&get_command_options_key(options))
             .cloned()
     }
 
-    /// Returns a mutex guard to the list of command names.
-    pub fn get_command_names(&self) -> MutexGuard<'_, Vec<String>> {
-        self.command_name_cache.lock()
+    /// Check if a canonical name belongs to a command that can be enabled or disabled
+    pub fn contains(&self, name: &str) -> bool {
+        self.command_names.lock().contains(name)
+    }
+
+    /// Get the canonical names of all commands, sorted.
+    pub fn get_command_names(&self) -> Vec<String> {
+        self.command_names.lock().iter().cloned().collect()
+    }
+
+    /// Enable or disable a command in a guild.
+    ///
+    /// # Returns
+    /// Returns the old setting.
+    ///
+    /// # Errors
+    /// Fails if the name is not the canonical name of a command, so typos do not end up in the database.
+    pub async fn set_command_disabled(
+        &self,
+        db: &Database,
+        guild_id: GuildId,
+        name: &str,
+        disable: bool,
+    ) -> anyhow::Result<bool> {
+        anyhow::ensure!(self.contains(name), "unknown command \"{name}\"");
+        db.set_disabled_command(guild_id, name, disable).await
     }
 }
 
+/// Get the key of the options of a prefix command in the prefix command lookup.
+///
+/// This is the address of the options, which is only an identity and not a name.
+/// See the XXX HACK note on [`EnabledCheckData::prefix_command_lookup`].
+fn get_command_options_key(options: &CommandOptions) -> usize {
+    options as *const CommandOptions as usize
+}
+
+/// Check if a command or any of its parents is disabled in a guild
+pub async fn is_command_disabled(
+    db: &Database,
+    guild_id: GuildId,
+    name: &str,
+) -> anyhow::Result<bool> {
+    let parent_ends = name.match_indices(NAME_SEPERATOR).map(|(i, _)| i);
+    for end in parent_ends.chain(std::iter::once(name.len())) {
+        if db.is_command_disabled(guild_id, &name[..end]).await? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Get the canonical name of the slash command of an interaction
+fn get_slash_command_name(interaction: &CommandInteraction) -> String {
+    let mut name = interaction.data.name.clone();
+    let mut options = &interaction.data.options;
+    while let Some(option) = options.first() {
+        match &option.value {
+            CommandDataOptionValue::SubCommand(sub_options)
+            | CommandDataOptionValue::SubCommandGroup(sub_options) => {
+                name.push_str(NAME_SEPERATOR);
+                name.push_str(&option.name);
+                options = sub_options;
+            }
+            _ => break,
+        }
+    }
+
+    name
+}
+
 /// Check if 2 [`Check`]s are the same.
 ///
 /// This includes their function pointers, though the argument references do not necessarily have to point to the same check.
@@ -169,7 +288,7 @@ pub async fn enabled_check(
         }
     };
 
-    match db.is_command_disabled(guild_id, &command_name).await {
+    match is_command_disabled(&db, guild_id, &command_name).await {
         Ok(true) => Err(Reason::User("Command Disabled".to_string())),
         Ok(false) => Ok(()),
         Err(e) => {
@@ -181,42 +300,176 @@ pub async fn enabled_check(
     }
 }
 
+/// Check if a command is enabled in the guild of an interaction via slash framework.
+///
+/// `name` is the canonical name of the command.
+async fn check_command_enabled(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    name: &str,
+) -> Result<(), SlashReason> {
+    let guild_id = match guild_id {
+        Some(id) => id,
+        None => {
+            // Let's not care about dms for now.
+            // They'll probably need special handling anyways.
+            // This will also probably only be useful in Group DMs,
+            // which I don't think bots can participate in anyways.
+            return Ok(());
+        }
+    };
+
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    match is_command_disabled(&db, guild_id, name).await {
+        Ok(true) => Err(SlashReason::new_user("Command Disabled.".to_string())),
+        Ok(false) => Ok(()),
+        Err(e) => {
+            error!("failed to read disabled commands: {}", e);
+            // DB failure, return false to be safe.
+            // Avoid being specific with error to prevent users from spamming knowingly.
+            Err(SlashReason::new_unknown())
+        }
+    }
+}
+
 /// Check if a command is enabled via slash framework
 pub fn create_slash_check<'a>(
     ctx: &'a Context,
     interaction: &'a CommandInteraction,
-    command: &'a Command,
+    _command: &'a Command,
 ) -> BoxFuture<'a, Result<(), SlashReason>> {
     Box::pin(async move {
-        let guild_id = match interaction.guild_id {
-            Some(id) => id,
-            None => {
-                // Let's not care about dms for now.
-                // They'll probably need special handling anyways.
-                // This will also probably only be useful in Group DMs,
-                // which I don't think bots can participate in anyways.
-                return Ok(());
+        let command_name = get_slash_command_name(interaction);
+        check_command_enabled(ctx, interaction.guild_id, &command_name).await
+    })
+}
+
+/// Check if the command of a component handler is enabled via slash framework.
+///
+/// Components are disabled with the command that sends them.
+/// Handlers that are not part of a command cannot be disabled.
+pub fn create_component_check<'a>(
+    ctx: &'a Context,
+    interaction: &'a ComponentInteraction,
+    handler: &'a ComponentHandler,
+) -> BoxFuture<'a, Result<(), SlashReason>> {
+    Box::pin(async move {
+        match handler.command() {
+            Some(command_name) => {
+                check_command_enabled(ctx, interaction.guild_id, command_name).await
             }
-        };
-
-        let data_lock = ctx.data.read().await;
-        let client_data = data_lock
-            .get::<ClientDataKey>()
-            .expect("missing client data");
-        let db = client_data.db.clone();
-        drop(data_lock);
-
-        let command_name = command.name();
-
-        match db.is_command_disabled(guild_id, command_name).await {
-            Ok(true) => Err(SlashReason::new_user("Command Disabled.".to_string())),
-            Ok(false) => Ok(()),
-            Err(e) => {
-                error!("failed to read disabled commands: {}", e);
-                // DB failure, return false to be safe.
-                // Avoid being specific with error to prevent users from spamming knowingly.
-                Err(SlashReason::new_unknown())
+            None => Ok(()),
+        }
+    })
+}
+
+/// Check if the command of a modal handler is enabled via slash framework.
+///
+/// Modals are disabled with the command that opens them.
+/// Handlers that are not part of a command cannot be disabled.
+pub fn create_modal_check<'a>(
+    ctx: &'a Context,
+    interaction: &'a ModalInteraction,
+    handler: &'a ModalHandler,
+) -> BoxFuture<'a, Result<(), SlashReason>> {
+    Box::pin(async move {
+        match handler.command() {
+            Some(command_name) => {
+                check_command_enabled(ctx, interaction.guild_id, command_name).await
             }
+            None => Ok(()),
         }
     })
 }
+
+/// Check if a context menu command is enabled via slash framework
+pub fn create_context_menu_check<'a>(
+    ctx: &'a Context,
+    interaction: &'a CommandInteraction,
+    command: &'a ContextMenuCommand,
+) -> BoxFuture<'a, Result<(), SlashReason>> {
+    Box::pin(async move { check_command_enabled(ctx, interaction.guild_id, command.name()).await })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serenity::framework::standard::{
+        macros::{
+            command,
+            group,
+        },
+        CommandResult,
+    };
+
+    #[command("stats")]
+    #[checks(Enabled)]
+    async fn first_stats(_ctx: &Context, _msg: &Message, _args: Args) -> CommandResult {
+        Ok(())
+    }
+
+    #[command]
+    #[checks(Enabled)]
+    #[sub_commands(first_stats)]
+    async fn first(_ctx: &Context, _msg: &Message, _args: Args) -> CommandResult {
+        Ok(())
+    }
+
+    #[command("stats")]
+    #[checks(Enabled)]
+    async fn second_stats(_ctx: &Context, _msg: &Message, _args: Args) -> CommandResult {
+        Ok(())
+    }
+
+    #[command]
+    #[checks(Enabled)]
+    #[sub_commands(second_stats)]
+    async fn second(_ctx: &Context, _msg: &Message, _args: Args) -> CommandResult {
+        Ok(())
+    }
+
+    #[group]
+    #[commands(first)]
+    struct First;
+
+    #[group]
+    #[commands(second)]
+    struct Second;
+
+    #[test]
+    fn subcommands_with_same_name() {
+        let data = EnabledCheckData::new();
+        data.add_groups(&[&FIRST_GROUP, &SECOND_GROUP])
+            .expect("failed to add groups");
+
+        assert_eq!(
+            data.get_command_names(),
+            ["first", "first::stats", "second", "second::stats"]
+        );
+        assert_eq!(
+            data.get_command_name_from_options(FIRST_STATS_COMMAND.options)
+                .as_deref(),
+            Some("first::stats")
+        );
+        assert_eq!(
+            data.get_command_name_from_options(SECOND_STATS_COMMAND.options)
+                .as_deref(),
+            Some("second::stats")
+        );
+    }
+
+    #[test]
+    fn same_group_twice() {
+        let data = EnabledCheckData::new();
+        data.add_groups(&[&FIRST_GROUP, &FIRST_GROUP])
+            .expect("failed to add groups");
+
+        assert_eq!(data.get_command_names(), ["first", "first::stats"]);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     checks::EnabledCheckData,
     commands::{
+        connect_four::ConnectFourData,
         deviantart::DeviantartClient,
         fml::FmlClient,
         iqdb::IqdbClient,
@@ -12,7 +13,10 @@ use crate::{
         rule34::Rule34Client,
         sauce_nao::SauceNaoClient,
         shift::ShiftClient,
-        tic_tac_toe::TicTacToeData,
+        tic_tac_toe::{
+            TicTacToeData,
+            TicTacToeSweeper,
+        },
         tiktok_embed::TikTokData,
         urban::UrbanClient,
     },
@@ -21,7 +25,10 @@ use crate::{
     util::EncoderTask,
 };
 use anyhow::Context;
-use serenity::gateway::ShardManager;
+use serenity::{
+    gateway::ShardManager,
+    http::Http,
+};
 use std::{
     collections::BTreeMap,
     fmt::Debug,
@@ -66,6 +73,60 @@ pub trait CacheStatsProvider {
     fn publish_cache_stats(&self, cache_stats_builder: &mut CacheStatsBuilder);
 }
 
+/// Data shared between the [`ClientData`] and the slash framework.
+///
+/// This is made before the client, so that it can be added to the slash framework as state.
+#[derive(Debug, Clone)]
+pub struct SharedData {
+    /// The client for nekos
+    pub nekos_client: NekosClient,
+    /// The r6tracker client
+    pub r6tracker_client: R6TrackerClient,
+    /// The rule34 client
+    pub rule34_client: Rule34Client,
+    /// The reddit embed data
+    pub reddit_embed_data: RedditEmbedData,
+    /// The tic tac toe data
+    pub tic_tac_toe_data: TicTacToeData,
+    /// The iqdb client
+    pub iqdb_client: IqdbClient,
+    /// The sauce nao client
+    pub sauce_nao_client: SauceNaoClient,
+    /// TikTokData
+    pub tiktok_data: TikTokData,
+    /// Encoder Task
+    pub encoder_task: EncoderTask,
+
+    /// The database
+    pub db: Database,
+}
+
+impl SharedData {
+    /// Init the shared data
+    pub async fn init(config: &Config, db: Database) -> anyhow::Result<Self> {
+        let cache_dir = config.cache_dir();
+        let encoder_task = EncoderTask::new();
+
+        let tiktok_data = TikTokData::new(&cache_dir, encoder_task.clone())
+            .await
+            .context("failed to init tiktok data")?;
+
+        Ok(Self {
+            nekos_client: Default::default(),
+            r6tracker_client: Default::default(),
+            rule34_client: Default::default(),
+            reddit_embed_data: Default::default(),
+            tic_tac_toe_data: Default::default(),
+            iqdb_client: Default::default(),
+            sauce_nao_client: SauceNaoClient::new(config.sauce_nao.api_key.as_str()),
+            tiktok_data,
+            encoder_task,
+
+            db,
+        })
+    }
+}
+
 /// The [`ClientData`].
 #[derive(Debug)]
 pub struct ClientData {
@@ -100,6 +161,10 @@ pub struct ClientData {
     pub xkcd_client: xkcd::Client,
     /// The tic tac toe data
     pub tic_tac_toe_data: TicTacToeData,
+    /// The task that expires inactive tic tac toe games
+    pub tic_tac_toe_sweeper: TicTacToeSweeper,
+    /// The connect four data
+    pub connect_four_data: ConnectFourData,
     /// The iqdb client
     pub iqdb_client: IqdbClient,
     /// The sauce nao client
@@ -124,40 +189,51 @@ impl ClientData {
     /// Init this client data
     pub async fn init(
         shard_manager: Arc<ShardManager>,
+        http: Arc<Http>,
         config: Arc<Config>,
-        db: Database,
+        shared_data: SharedData,
     ) -> anyhow::Result<Self> {
         // TODO: Standardize an async init system with allocated data per command somehow. Maybe boxes?
 
-        let cache_dir = config.cache_dir();
-        let encoder_task = EncoderTask::new();
+        let SharedData {
+            nekos_client,
+            r6tracker_client,
+            rule34_client,
+            reddit_embed_data,
+            tic_tac_toe_data,
+            iqdb_client,
+            sauce_nao_client,
+            tiktok_data,
+            encoder_task,
+            db,
+        } = shared_data;
 
         let deviantart_client = DeviantartClient::new(&db)
             .await
             .context("failed to init deviantart client")?;
-        let tiktok_data = TikTokData::new(&cache_dir, encoder_task.clone())
-            .await
-            .context("failed to init tiktok data")?;
+        let tic_tac_toe_sweeper = TicTacToeSweeper::new(http, db.clone(), config.tic_tac_toe);
 
         Ok(ClientData {
             shard_manager,
 
-            nekos_client: Default::default(),
+            nekos_client,
             r6stats_client: Default::default(),
-            r6tracker_client: Default::default(),
-            rule34_client: Default::default(),
+            r6tracker_client,
+            rule34_client,
             quizizz_client: Default::default(),
             fml_client: FmlClient::new(config.fml.key.to_string()),
             shift_client: ShiftClient::new(),
-            reddit_embed_data: Default::default(),
+            reddit_embed_data,
             enabled_check_data: Default::default(),
             insta_client: insta::Client::new(),
             deviantart_client,
             urban_client: Default::default(),
             xkcd_client: Default::default(),
-            tic_tac_toe_data: Default::default(),
-            iqdb_client: Default::default(),
-            sauce_nao_client: SauceNaoClient::new(config.sauce_nao.api_key.as_str()),
+            tic_tac_toe_data,
+            tic_tac_toe_sweeper,
+            connect_four_data: Default::default(),
+            iqdb_client,
+            sauce_nao_client,
             open_ai_client: open_ai::Client::new(config.open_ai.api_key.as_str()),
             yodaspeak: yodaspeak::Client::new(),
             tiktok_data,
@@ -203,5 +279,9 @@ impl ClientData {
         if let Err(e) = self.encoder_task.shutdown().await {
             error!("{:?}", e);
         }
+
+        if let Err(e) = self.tic_tac_toe_sweeper.shutdown().await {
+            error!("{:?}", e);
+        }
     }
 }
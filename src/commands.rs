@@ -1,7 +1,9 @@
 pub mod cache_stats;
 pub mod chat;
 pub mod cmd;
+pub mod connect_four;
 pub mod deviantart;
+pub mod embed_links;
 pub mod fml;
 pub mod insta_dl;
 pub mod invite;
@@ -32,6 +34,7 @@ pub mod zalgo;
 pub use crate::commands::{
     cache_stats::CACHE_STATS_COMMAND,
     cmd::CMD_COMMAND,
+    connect_four::CONNECT_FOUR_COMMAND,
     deviantart::DEVIANTART_COMMAND,
     fml::FML_COMMAND,
     insta_dl::INSTA_DL_COMMAND,
@@ -54,12 +57,48 @@ pub use crate::commands::{
     zalgo::ZALGO_COMMAND,
 };
 use anyhow::Context;
-use pikadick_slash_framework::FromOptions;
-use serenity::builder::{
-    CreateEmbed,
-    CreateInteractionResponse,
-    CreateInteractionResponseMessage,
+use pikadick_slash_framework::{
+    ArgumentParam,
+    BoxError,
+    Command,
+    ComponentState,
+    DataType,
+    FromOptions,
 };
+use serenity::{
+    builder::{
+        AutocompleteChoice,
+        CreateActionRow,
+        CreateButton,
+        CreateEmbed,
+        CreateEmbedFooter,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    model::{
+        application::ButtonStyle,
+        id::UserId,
+    },
+};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+};
+
+/// The embed color of help messages
+const HELP_COLOR: u32 = 0xF4D665;
+
+/// The custom id prefix of the help page buttons
+const HELP_PAGE_PREFIX: &str = "help-page";
+
+/// The max number of commands on a help page
+const HELP_PAGE_SIZE: usize = 10;
+
+/// The category of commands without one
+const DEFAULT_CATEGORY: &str = "Other";
+
+/// The max length of an embed field value
+const MAX_FIELD_LEN: usize = 1024;
 
 /// Help Options
 #[derive(Debug, FromOptions)]
@@ -68,6 +107,256 @@ pub struct HelpCommandOptions {
     pub command: Option<String>,
 }
 
+/// Get a user-facing name for an argument type
+fn data_type_name(kind: DataType) -> &'static str {
+    match kind {
+        DataType::String => "text",
+        DataType::Integer => "integer",
+        DataType::Boolean => "true/false",
+        DataType::User => "user",
+        DataType::Channel => "channel",
+        DataType::Role => "role",
+        DataType::Mentionable => "user or role",
+        DataType::Number => "number",
+        DataType::Attachment => "file",
+    }
+}
+
+/// Format a min and max as a range, if either exists
+fn format_range<T>(min: Option<T>, max: Option<T>) -> Option<String>
+where
+    T: Display,
+{
+    match (min, max) {
+        (Some(min), Some(max)) => Some(format!("{min} to {max}")),
+        (Some(min), None) => Some(format!("at least {min}")),
+        (None, Some(max)) => Some(format!("at most {max}")),
+        (None, None) => None,
+    }
+}
+
+/// Format arguments as a list, with their type, whether they are required, and their constraints
+fn format_arguments(arguments: &[ArgumentParam]) -> String {
+    let mut formatted = String::with_capacity(256);
+    for argument in arguments.iter() {
+        let required = if argument.required() {
+            "required"
+        } else {
+            "optional"
+        };
+        formatted.push_str(&format!(
+            "- `{}` ({}, {required}): {}\n",
+            argument.name(),
+            data_type_name(argument.kind()),
+            argument.description()
+        ));
+
+        if !argument.choices().is_empty() {
+            let choices: Vec<String> = argument
+                .choices()
+                .iter()
+                .map(|choice| format!("`{}`", choice.name()))
+                .collect();
+            formatted.push_str(&format!("  - choices: {}\n", choices.join(", ")));
+        }
+        if let Some(range) = format_range(argument.min_value(), argument.max_value()) {
+            formatted.push_str(&format!("  - value: {range}\n"));
+        }
+        if let Some(range) = format_range(argument.min_length(), argument.max_length()) {
+            formatted.push_str(&format!("  - length: {range} characters\n"));
+        }
+    }
+
+    formatted
+}
+
+/// Cut a field value down to the max length Discord allows
+fn truncate_field(mut value: String) -> String {
+    if value.len() > MAX_FIELD_LEN {
+        let mut end = MAX_FIELD_LEN - "...".len();
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push_str("...");
+    }
+
+    value
+}
+
+/// Make the help embed for a single command
+fn create_command_help_embed(command: &Command) -> CreateEmbed {
+    let mut embed_builder = CreateEmbed::new()
+        .color(HELP_COLOR)
+        .title(format!("/{}", command.name()))
+        .description(command.description())
+        .field(
+            "Category",
+            command.category().unwrap_or(DEFAULT_CATEGORY),
+            true,
+        );
+
+    if command.nsfw() {
+        embed_builder = embed_builder.field("Age-restricted", "Yes", true);
+    }
+
+    if !command.arguments().is_empty() {
+        embed_builder = embed_builder.field(
+            "Arguments",
+            truncate_field(format_arguments(command.arguments())),
+            false,
+        );
+    }
+
+    // Subcommand groups are flattened, as Discord only allows one level of them
+    let mut subcommands = Vec::new();
+    for subcommand in command.subcommands().iter() {
+        if subcommand.has_subcommands() {
+            for nested in subcommand.subcommands().iter() {
+                subcommands.push((format!("{} {}", subcommand.name(), nested.name()), nested));
+            }
+        } else {
+            subcommands.push((subcommand.name().to_string(), subcommand));
+        }
+    }
+    for (path, subcommand) in subcommands {
+        let mut value = String::with_capacity(256);
+        value.push_str(subcommand.description());
+        value.push('\n');
+        value.push_str(&format_arguments(subcommand.arguments()));
+
+        embed_builder = embed_builder.field(
+            format!("/{} {path}", command.name()),
+            truncate_field(value),
+            false,
+        );
+    }
+
+    let examples: Vec<&str> = command
+        .examples()
+        .iter()
+        .chain(
+            command
+                .subcommands()
+                .iter()
+                .flat_map(|subcommand| subcommand.examples().iter()),
+        )
+        .map(|example| &**example)
+        .collect();
+    if !examples.is_empty() {
+        let mut value = String::with_capacity(256);
+        for example in examples {
+            value.push('`');
+            value.push_str(example);
+            value.push_str("`\n");
+        }
+        embed_builder = embed_builder.field("Examples", truncate_field(value), false);
+    }
+
+    embed_builder
+}
+
+/// The state of a help page button
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct HelpPageState {
+    /// The user who ran the help command.
+    ///
+    /// Only they may change the page.
+    user_id: UserId,
+
+    /// The page to show
+    page: usize,
+}
+
+impl ComponentState for HelpPageState {
+    fn encode(&self) -> String {
+        format!("{}-{}", self.user_id, self.page)
+    }
+
+    fn decode(state: &str) -> Result<Self, BoxError> {
+        let (user_id, page) = state.split_once('-').ok_or("missing page")?;
+        Ok(Self {
+            user_id: UserId::new(user_id.parse()?),
+            page: page.parse()?,
+        })
+    }
+}
+
+/// Make the custom id of a help page button
+fn create_help_page_custom_id(user_id: UserId, page: usize) -> String {
+    pikadick_slash_framework::create_custom_id(HELP_PAGE_PREFIX, &HelpPageState { user_id, page })
+        .expect("help page custom id should fit")
+}
+
+/// Make a page of the command list for a user.
+///
+/// Commands are grouped by category. Pages past the end show the last page.
+fn create_help_page(
+    map: &HashMap<Box<str>, Command>,
+    user_id: UserId,
+    page: usize,
+) -> CreateInteractionResponseMessage {
+    let mut commands: Vec<&Command> = map.values().collect();
+    commands.sort_unstable_by_key(|command| {
+        (
+            command.category().unwrap_or(DEFAULT_CATEGORY),
+            command.name(),
+        )
+    });
+
+    let num_pages = commands.len().div_ceil(HELP_PAGE_SIZE).max(1);
+    let page = page.min(num_pages - 1);
+
+    let mut categories: Vec<(&str, String)> = Vec::new();
+    for command in commands
+        .iter()
+        .skip(page * HELP_PAGE_SIZE)
+        .take(HELP_PAGE_SIZE)
+    {
+        let category = command.category().unwrap_or(DEFAULT_CATEGORY);
+        let line = format!("`/{}`: {}\n", command.name(), command.description());
+        match categories.last_mut() {
+            Some((last_category, lines)) if *last_category == category => lines.push_str(&line),
+            _ => categories.push((category, line)),
+        }
+    }
+
+    let mut embed_builder = CreateEmbed::new()
+        .color(HELP_COLOR)
+        .title("Help")
+        .description("Use `/help command:<name>` for more information about a command.");
+    for (category, lines) in categories {
+        embed_builder = embed_builder.field(category, truncate_field(lines), false);
+    }
+
+    let mut message_builder = CreateInteractionResponseMessage::new();
+    if num_pages > 1 {
+        embed_builder = embed_builder.footer(CreateEmbedFooter::new(format!(
+            "Page {}/{num_pages}",
+            page + 1
+        )));
+
+        let previous_button =
+            CreateButton::new(create_help_page_custom_id(user_id, page.saturating_sub(1)))
+                .label("Previous")
+                .style(ButtonStyle::Secondary)
+                .disabled(page == 0);
+        let next_button = CreateButton::new(create_help_page_custom_id(
+            user_id,
+            (page + 1).min(num_pages - 1),
+        ))
+        .label("Next")
+        .style(ButtonStyle::Secondary)
+        .disabled(page + 1 == num_pages);
+        message_builder = message_builder.components(vec![CreateActionRow::Buttons(vec![
+            previous_button,
+            next_button,
+        ])]);
+    }
+
+    message_builder.embed(embed_builder)
+}
+
 /// Create a slash help command
 pub fn create_slash_help_command() -> anyhow::Result<pikadick_slash_framework::HelpCommand> {
     pikadick_slash_framework::HelpCommandBuilder::new()
@@ -79,52 +368,33 @@ pub fn create_slash_help_command() -> anyhow::Result<pikadick_slash_framework::H
                 .kind(pikadick_slash_framework::ArgumentKind::String)
                 .build()?,
         )
+        .on_autocomplete(|_ctx, _interaction, map, value| async move {
+            let mut names: Vec<&str> = map
+                .keys()
+                .map(|name| &**name)
+                .filter(|name| name.starts_with(value.as_str()))
+                .collect();
+            names.sort_unstable();
+
+            Ok(names.into_iter().map(AutocompleteChoice::from).collect())
+        })
         .on_process(
             |ctx, interaction, map, args: HelpCommandOptions| async move {
-                let mut embed_builder = CreateEmbed::new().color(0xF4D665_u32);
-                if let Some(command) = args.command {
-                    let maybe_command = map.get(command.as_str());
-
-                    match maybe_command {
-                        Some(command) => {
-                            embed_builder = embed_builder
-                                .title(command.name())
-                                .description(command.description());
-
-                            if !command.arguments().is_empty() {
-                                let mut arguments = String::with_capacity(256);
-                                for argument in command.arguments().iter() {
-                                    arguments.push_str("**");
-                                    arguments.push_str(argument.name());
-                                    arguments.push_str("**");
-
-                                    arguments.push_str(": ");
-                                    arguments.push_str(argument.description());
-                                }
-                                embed_builder = embed_builder.field("Arguments", &arguments, false);
-                            }
-                        }
-                        None => {
-                            embed_builder = embed_builder
+                let message_builder = match args.command {
+                    Some(command) => {
+                        let command = command.trim_start_matches('/');
+                        let embed_builder = match map.get(command) {
+                            Some(command) => create_command_help_embed(command),
+                            None => CreateEmbed::new()
+                                .color(HELP_COLOR)
                                 .title("Unknown Command")
-                                .description(format!("Command \"{command}\" was not found."));
-                        }
-                    }
-                } else {
-                    embed_builder = embed_builder.title("Help");
-
-                    let mut description = String::with_capacity(256);
-                    for name in map.keys() {
-                        description.push('`');
-                        description.push_str(name);
-                        description.push('`');
-                        description.push('\n');
+                                .description(format!("Command \"{command}\" was not found.")),
+                        };
+                        CreateInteractionResponseMessage::new().embed(embed_builder)
                     }
+                    None => create_help_page(&map, interaction.user.id, 0),
+                };
 
-                    embed_builder = embed_builder.description(description);
-                }
-
-                let message_builder = CreateInteractionResponseMessage::new().embed(embed_builder);
                 let response_builder = CreateInteractionResponse::Message(message_builder);
                 interaction
                     .create_response(&ctx.http, response_builder)
@@ -136,3 +406,106 @@ pub fn create_slash_help_command() -> anyhow::Result<pikadick_slash_framework::H
         .build()
         .context("failed to build help command")
 }
+
+/// Create the handler for the help page buttons
+pub fn create_help_page_component_handler(
+) -> anyhow::Result<pikadick_slash_framework::ComponentHandler> {
+    pikadick_slash_framework::ComponentHandlerBuilder::new()
+        .prefix(HELP_PAGE_PREFIX)
+        .on_process(|ctx, interaction, state: HelpPageState| async move {
+            if interaction.user.id != state.user_id {
+                let message_builder = CreateInteractionResponseMessage::new()
+                    .content("Only the user who asked for help can change the page. Use `/help` to get your own.")
+                    .ephemeral(true);
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+                return Ok(());
+            }
+
+            let data_lock = ctx.data.read().await;
+            let framework = data_lock
+                .get::<crate::SlashFrameworkKey>()
+                .expect("missing slash framework")
+                .clone();
+            drop(data_lock);
+
+            let message_builder =
+                create_help_page(framework.commands(), state.user_id, state.page);
+            let response = CreateInteractionResponse::UpdateMessage(message_builder);
+            interaction.create_response(&ctx.http, response).await?;
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build help page component handler")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pikadick_slash_framework::ArgumentParamBuilder;
+
+    #[test]
+    fn format_arguments_lists_constraints() {
+        let arguments = [
+            ArgumentParamBuilder::new()
+                .name("query")
+                .description("The search query")
+                .kind(DataType::String)
+                .required(true)
+                .min_length(1)
+                .max_length(100)
+                .build()
+                .expect("failed to build query argument"),
+            ArgumentParamBuilder::new()
+                .name("count")
+                .description("How many")
+                .kind(DataType::Integer)
+                .choice("One", 1_i64)
+                .choice("Two", 2_i64)
+                .build()
+                .expect("failed to build count argument"),
+            ArgumentParamBuilder::new()
+                .name("scale")
+                .description("The scale")
+                .kind(DataType::Number)
+                .min_value(0.5)
+                .build()
+                .expect("failed to build scale argument"),
+        ];
+
+        assert_eq!(
+            format_arguments(&arguments),
+            "- `query` (text, required): The search query\n  - length: 1 to 100 characters\n- `count` (integer, optional): How many\n  - choices: `One`, `Two`\n- `scale` (number, optional): The scale\n  - value: at least 0.5\n"
+        );
+        assert_eq!(format_arguments(&[]), "");
+    }
+
+    #[test]
+    fn truncate_field_keeps_short_values() {
+        let value = "a".repeat(MAX_FIELD_LEN);
+        assert_eq!(truncate_field(value.clone()), value);
+    }
+
+    #[test]
+    fn truncate_field_cuts_long_values() {
+        let truncated = truncate_field("a".repeat(MAX_FIELD_LEN + 1));
+        assert_eq!(truncated.len(), MAX_FIELD_LEN);
+        assert!(truncated.ends_with("..."));
+
+        // Multi-byte chars must not be split
+        let truncated = truncate_field("é".repeat(MAX_FIELD_LEN));
+        assert!(truncated.len() <= MAX_FIELD_LEN);
+        assert!(truncated.ends_with("..."));
+    }
+
+    #[test]
+    fn help_page_state_round_trip() {
+        let state = HelpPageState {
+            user_id: UserId::new(123),
+            page: 4,
+        };
+        let decoded = HelpPageState::decode(&state.encode()).expect("failed to decode state");
+        assert_eq!(decoded, state);
+    }
+}
@@ -3,131 +3,174 @@ use anyhow::{
     ensure,
     Context as _,
 };
-use serenity::builder::{
-    CreateEmbed,
-    EditInteractionResponse,
+use pikadick_slash_framework::{
+    Response,
+    ResponseMessage,
+};
+use serenity::{
+    builder::{
+        CreateEmbed,
+        CreateInteractionResponse,
+    },
+    client::Context,
 };
 use tracing::{
     error,
     info,
 };
 
+/// The custom id prefix of the chat modal
+const MODAL_PREFIX: &str = "chat";
+
 const R6_TRACKER_PROMPT: &str = "When a user asks for rainbox six siege statistics for a person, respond only with \"!r6tracker <playername>\".";
 
 /// Options
 #[derive(Debug, pikadick_slash_framework::FromOptions)]
 pub struct Options {
     // The message
+    message: Option<String>,
+}
+
+/// Modal fields, for messages that do not fit in an option
+#[derive(Debug, pikadick_slash_framework::FromModal)]
+pub struct ChatModal {
+    #[pikadick_slash_framework(label = "Message", paragraph, max_length = 4000)]
     message: String,
 }
 
+/// Get a chat response for a message
+async fn create_chat_response(ctx: &Context, message: String) -> ResponseMessage {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let client = client_data.open_ai_client.clone();
+    let r6_tracker_client = client_data.r6tracker_client.clone();
+    drop(data_lock);
+
+    info!("requesting completion for chat message \"{message}\"");
+
+    let chat_result = client
+        .chat_completion(
+            "gpt-3.5-turbo",
+            &[
+                open_ai::ChatMessage {
+                    // gpt-3.5-turbo currently places low weight on system messages, use a user message.
+                    role: "user".into(),
+                    content: R6_TRACKER_PROMPT.into(),
+                },
+                open_ai::ChatMessage {
+                    role: "user".into(),
+                    content: message.into(),
+                },
+            ],
+            Some(500),
+        )
+        .await
+        .context("failed to get search results")
+        .and_then(|mut response| {
+            ensure!(!response.choices.is_empty(), "missing response choice");
+            Ok(response.choices.swap_remove(0))
+        });
+
+    let chat_response = match chat_result {
+        Ok(result) => result.message.content,
+        Err(error) => {
+            error!("{error:?}");
+            return ResponseMessage::new().content(format!("{error:?}"));
+        }
+    };
+
+    // This may be expaned in the future.
+    #[allow(clippy::collapsible_match)]
+    match chat_response.split_once(' ') {
+        Some((command, rest)) => match command {
+            "!r6tracker" => {
+                let stats = r6_tracker_client
+                    .get_stats(rest)
+                    .await
+                    .context("failed to get r6tracker stats");
+                match stats.as_ref().map(|stats| stats.data()) {
+                    Ok(Some(stats)) => {
+                        let embed_builder = stats.populate_embed(CreateEmbed::new());
+                        ResponseMessage::new().embed(embed_builder)
+                    }
+                    Ok(None) => {
+                        ResponseMessage::new().content(format!("User \"{rest}\" was not found"))
+                    }
+                    Err(error) => {
+                        error!("{error:?}");
+                        ResponseMessage::new().content(format!("{error:?}"))
+                    }
+                }
+            }
+            _ => ResponseMessage::new().content(chat_response),
+        },
+        None => ResponseMessage::new().content(chat_response),
+    }
+}
+
 /// Create a slash command
 pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
     pikadick_slash_framework::CommandBuilder::new()
         .name("chat")
         .description("Chat with pikadick")
+        .category("Fun")
+        .example("/chat message:Hello!")
+        .example("/chat")
+        .bucket("default")
         .argument(
             pikadick_slash_framework::ArgumentParamBuilder::new()
                 .name("message")
-                .description("The message")
+                .description("The message. Leave empty to write a longer message.")
                 .kind(pikadick_slash_framework::ArgumentKind::String)
-                .required(true)
                 .build()?,
         )
         .on_process(|ctx, interaction, args: Options| async move {
-            let data_lock = ctx.data.read().await;
-            let client_data = data_lock
-                .get::<ClientDataKey>()
-                .expect("missing client data");
-            let client = client_data.open_ai_client.clone();
-            let r6_tracker_client = client_data.r6tracker_client.clone();
-            drop(data_lock);
-
-            info!(
-                "requesting completion for chat message \"{}\"",
-                args.message
-            );
-
-            interaction.defer(&ctx.http).await?;
-
-            let chat_result = client
-                .chat_completion(
-                    "gpt-3.5-turbo",
-                    &[
-                        open_ai::ChatMessage {
-                            // gpt-3.5-turbo currently places low weight on system messages, use a user message.
-                            role: "user".into(),
-                            content: R6_TRACKER_PROMPT.into(),
-                        },
-                        open_ai::ChatMessage {
-                            role: "user".into(),
-                            content: args.message.into(),
-                        },
-                    ],
-                    Some(500),
-                )
-                .await
-                .context("failed to get search results")
-                .and_then(|mut response| {
-                    ensure!(!response.choices.is_empty(), "missing response choice");
-                    Ok(response.choices.swap_remove(0))
-                });
-
-            let chat_response = match chat_result {
-                Ok(result) => result.message.content,
-                Err(error) => {
-                    error!("{error:?}");
-                    let response = EditInteractionResponse::new().content(format!("{error:?}"));
-
-                    interaction.edit_response(&ctx.http, response).await?;
+            let message = match args.message {
+                Some(message) => message,
+                None => {
+                    let modal = pikadick_slash_framework::create_modal::<ChatModal, ()>(
+                        MODAL_PREFIX,
+                        &(),
+                        "Chat with pikadick",
+                    )
+                    .context("failed to create chat modal")?;
+                    interaction
+                        .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+                        .await?;
                     return Ok(());
                 }
             };
 
-            // This may be expaned in the future.
-            #[allow(clippy::collapsible_match)]
-            match chat_response.split_once(' ') {
-                Some((command, rest)) => match command {
-                    "!r6tracker" => {
-                        let stats = r6_tracker_client
-                            .get_stats(rest)
-                            .await
-                            .context("failed to get r6tracker stats");
-                        match stats.as_ref().map(|stats| stats.data()) {
-                            Ok(Some(stats)) => {
-                                let embed_builder = stats.populate_embed(CreateEmbed::new());
-                                let response = EditInteractionResponse::new().embed(embed_builder);
-
-                                interaction.edit_response(&ctx.http, response).await?;
-                            }
-                            Ok(None) => {
-                                let response = EditInteractionResponse::new()
-                                    .content(format!("User \"{rest}\" was not found"));
-
-                                interaction.edit_response(&ctx.http, response).await?;
-                            }
-                            Err(error) => {
-                                let response =
-                                    EditInteractionResponse::new().content(format!("{error:?}"));
-
-                                error!("{error:?}");
-                                interaction.edit_response(&ctx.http, response).await?;
-                            }
-                        }
-                    }
-                    _ => {
-                        let response = EditInteractionResponse::new().content(chat_response);
-                        interaction.edit_response(&ctx.http, response).await?;
-                    }
-                },
-                None => {
-                    let response = EditInteractionResponse::new().content(chat_response);
-                    interaction.edit_response(&ctx.http, response).await?;
-                }
-            }
+            let mut response = Response::new(&ctx, &interaction);
+            let message = response
+                .defer_if_slow(create_chat_response(&ctx, message))
+                .await?;
+            response.send(message).await?;
 
             Ok(())
         })
         .build()
         .context("failed to build chat command")
 }
+
+/// Create the handler for the chat modal
+pub fn create_modal_handler() -> anyhow::Result<pikadick_slash_framework::ModalHandler> {
+    pikadick_slash_framework::ModalHandlerBuilder::new()
+        .prefix(MODAL_PREFIX)
+        .command("chat")
+        .on_process(
+            |ctx, interaction, _state: (), fields: ChatModal| async move {
+                let mut response = Response::new(&ctx, &interaction);
+                let message = response
+                    .defer_if_slow(create_chat_response(&ctx, fields.message))
+                    .await?;
+                response.send(message).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build chat modal handler")
+}
@@ -1,5 +1,6 @@
 use crate::{
     checks::{
+        enabled::is_command_disabled,
         ADMIN_CHECK,
         ENABLED_CHECK,
     },
@@ -25,7 +26,6 @@ use tracing::error;
 #[usage("<enable/disable> <cmd>")]
 #[example("disable ping")]
 #[min_args(2)]
-#[max_args(2)]
 #[sub_commands(list)]
 #[checks(Admin, Enabled)]
 #[bucket("default")]
@@ -59,14 +59,10 @@ pub async fn cmd(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
     args.advance();
 
-    let cmd_name = args.current().expect("missing cmd name");
+    // Context menu commands have spaces in their names, so take the rest of the message.
+    let cmd_name = args.rest().trim();
 
-    let is_valid_command = {
-        let names = data.get_command_names();
-        names.iter().any(|name| name == cmd_name)
-    };
-
-    if !is_valid_command {
+    if !data.contains(cmd_name) {
         msg.channel_id
             .say(
                 &ctx.http,
@@ -76,7 +72,10 @@ pub async fn cmd(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
         return Ok(());
     }
 
-    match db.set_disabled_command(guild_id, cmd_name, disable).await {
+    match data
+        .set_command_disabled(&db, guild_id, cmd_name, disable)
+        .await
+    {
         Ok(_old_value) => {
             let status_str = status_to_str(disable);
 
@@ -121,11 +120,13 @@ pub async fn list(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
     let res = {
         let mut res = "Commands:\n".to_string();
 
-        let names = data.get_command_names().clone();
+        let names = data.get_command_names();
 
         for name in names.iter() {
             let state = if db.is_command_disabled(guild_id, name).await? {
                 "DISABLED"
+            } else if is_command_disabled(&db, guild_id, name).await? {
+                "DISABLED (by parent)"
             } else {
                 "ENABLED"
             };
@@ -0,0 +1,193 @@
+mod board;
+mod concede;
+mod play;
+mod renderer;
+mod stats;
+
+use self::renderer::Renderer;
+pub use self::{
+    board::BOARD_COMMAND,
+    concede::CONCEDE_COMMAND,
+    play::PLAY_COMMAND,
+    stats::STATS_COMMAND,
+};
+use crate::{
+    checks::ENABLED_CHECK,
+    database::{
+        ConnectFourTryMoveError,
+        ConnectFourTryMoveResponse,
+    },
+    ClientDataKey,
+};
+use anyhow::Context as _;
+use connect_four::Team;
+use serenity::{
+    builder::{
+        CreateAttachment,
+        CreateMessage,
+    },
+    client::Context,
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::{
+        channel::Message,
+        prelude::*,
+    },
+};
+use std::sync::Arc;
+use tracing::error;
+
+/// Data pertaining to running connect four games
+#[derive(Clone)]
+pub struct ConnectFourData {
+    renderer: Arc<Renderer>,
+}
+
+impl ConnectFourData {
+    /// Make a new [`ConnectFourData`].
+    pub fn new() -> Self {
+        let renderer = Renderer::new().expect("failed to init renderer");
+
+        Self {
+            renderer: Arc::new(renderer),
+        }
+    }
+}
+
+impl std::fmt::Debug for ConnectFourData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectFourData").finish()
+    }
+}
+
+impl Default for ConnectFourData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Make a message with a rendered board
+async fn create_board_message(
+    connect_four_data: &ConnectFourData,
+    board: connect_four::Board,
+    content: String,
+) -> anyhow::Result<CreateMessage> {
+    let file = connect_four_data
+        .renderer
+        .render_board_async(board)
+        .await
+        .context("failed to render Connect Four board")?;
+    let file = CreateAttachment::bytes(file, format!("c4-{}.png", board.encode_u64()));
+
+    Ok(CreateMessage::new().content(content).add_file(file))
+}
+
+#[command("connect-four")]
+#[aliases("c4")]
+#[sub_commands("play", "concede", "board", "stats")]
+#[description("Play a game of Connect Four")]
+#[usage("<column #>")]
+#[example("4")]
+#[min_args(1)]
+#[max_args(1)]
+#[checks(Enabled)]
+#[bucket("default")]
+pub async fn connect_four(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let connect_four_data = client_data.connect_four_data.clone();
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let guild_id = msg.guild_id;
+    let author_id = msg.author.id;
+
+    let column = match args.trimmed().single::<u8>() {
+        Ok(num) => num,
+        Err(error) => {
+            let response = format!("That column is not a number: {error}\nUse `connect-four play <computer/@user> <red/yellow>` to start a game.");
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    if !(1..=connect_four::WIDTH).contains(&column) {
+        let response = format!(
+            "Your column number must be between 1 and {} {}",
+            connect_four::WIDTH,
+            author_id.mention()
+        );
+        msg.channel_id.say(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let (content, board) = match db
+        .try_connect_four_move(guild_id.into(), author_id.into(), column - 1)
+        .await
+    {
+        Ok(ConnectFourTryMoveResponse::Winner {
+            game,
+            winner,
+            loser,
+        }) => {
+            let content = format!(
+                "{} has triumphed over {} in Connect Four",
+                winner.mention(),
+                loser.mention(),
+            );
+            (content, game.board)
+        }
+        Ok(ConnectFourTryMoveResponse::Tie { game }) => {
+            let content = format!(
+                "{} has tied with {} in Connect Four",
+                game.get_player(Team::Red).mention(),
+                game.get_player(Team::Yellow).mention(),
+            );
+            (content, game.board)
+        }
+        Ok(ConnectFourTryMoveResponse::NextTurn { game }) => {
+            let content = format!("Your turn {}", game.get_player_turn().mention());
+            (content, game.board)
+        }
+        Err(ConnectFourTryMoveError::InvalidTurn) => {
+            let response = "It is not your turn. Please wait for your opponent to finish.";
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(ConnectFourTryMoveError::InvalidMove) => {
+            let response = format!("Invalid move {}. That column is full.", author_id.mention());
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(ConnectFourTryMoveError::NotInAGame) => {
+            let response =
+                "No games in progress. Make one with `connect-four play <computer/@user> <red/yellow>`.";
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(ConnectFourTryMoveError::Database(error)) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, "database error").await?;
+            return Ok(());
+        }
+    };
+
+    match create_board_message(&connect_four_data, board, content).await {
+        Ok(message_builder) => {
+            msg.channel_id
+                .send_message(&ctx.http, message_builder)
+                .await?;
+        }
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, format!("{error:?}")).await?;
+        }
+    }
+
+    Ok(())
+}
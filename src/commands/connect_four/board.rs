@@ -0,0 +1,65 @@
+use super::create_board_message;
+use crate::{
+    checks::ENABLED_CHECK,
+    ClientDataKey,
+};
+use serenity::{
+    client::Context,
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::prelude::*,
+};
+use tracing::error;
+
+#[command]
+#[description("Print the current Connect Four board")]
+#[usage("")]
+#[example("")]
+#[min_args(0)]
+#[max_args(0)]
+#[bucket("c4-board")]
+#[checks(Enabled)]
+pub async fn board(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let connect_four_data = client_data.connect_four_data.clone();
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let game = match db
+        .get_connect_four_game(msg.guild_id.into(), msg.author.id.into())
+        .await
+    {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            let response = "Failed to print board as you have no games in this server";
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, "database error").await?;
+            return Ok(());
+        }
+    };
+
+    let content = format!("Your turn {}", game.get_player_turn().mention());
+    match create_board_message(&connect_four_data, game.board, content).await {
+        Ok(message_builder) => {
+            msg.channel_id
+                .send_message(&ctx.http, message_builder)
+                .await?;
+        }
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, format!("{error:?}")).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use super::create_board_message;
+use crate::{
+    checks::ENABLED_CHECK,
+    database::model::ConnectFourPlayer,
+    ClientDataKey,
+};
+use serenity::{
+    client::Context,
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::prelude::*,
+};
+use tracing::error;
+
+#[command]
+#[description("Concede a game of Connect Four")]
+#[usage("")]
+#[example("")]
+#[min_args(0)]
+#[max_args(0)]
+#[checks(Enabled)]
+#[bucket("default")]
+pub async fn concede(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let connect_four_data = client_data.connect_four_data.clone();
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let author_id = msg.author.id;
+
+    let game = match db
+        .concede_connect_four_game(msg.guild_id.into(), author_id)
+        .await
+    {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            let response = "Failed to concede as you have no games in this server";
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, "database error").await?;
+            return Ok(());
+        }
+    };
+
+    let opponent = game
+        .get_opponent(ConnectFourPlayer::User(author_id))
+        .expect("author is not playing the game");
+    let content = format!(
+        "{} has conceded to {}.",
+        author_id.mention(),
+        opponent.mention()
+    );
+    match create_board_message(&connect_four_data, game.board, content).await {
+        Ok(message_builder) => {
+            msg.channel_id
+                .send_message(&ctx.http, message_builder)
+                .await?;
+        }
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, format!("{error:?}")).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use super::create_board_message;
+use crate::{
+    checks::ENABLED_CHECK,
+    database::{
+        model::ConnectFourPlayer,
+        ConnectFourCreateGameError,
+    },
+    ClientDataKey,
+};
+use serenity::{
+    client::Context,
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::prelude::*,
+};
+use tracing::error;
+
+#[command]
+#[description("Start a game of Connect Four. Red goes first")]
+#[usage("<computer OR @user, red OR yellow>")]
+#[example("computer red")]
+#[min_args(2)]
+#[max_args(2)]
+#[checks(Enabled)]
+#[bucket("default")]
+pub async fn play(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let connect_four_data = client_data.connect_four_data.clone();
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let opponent: ConnectFourPlayer = match args.trimmed().single() {
+        Ok(player) => player,
+        Err(error) => {
+            let response =
+                format!("Invalid opponent. Choose 'computer' or '@user'. Error: {error}");
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let author_team: connect_four::Team = match args.trimmed().single() {
+        Ok(team) => team,
+        Err(error) => {
+            let response = format!("Invalid team. Choose 'red' or 'yellow'. Error: {error}");
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let game = match db
+        .create_connect_four_game(
+            msg.guild_id.into(),
+            msg.author.id.into(),
+            author_team,
+            opponent,
+        )
+        .await
+    {
+        Ok(game) => game,
+        Err(ConnectFourCreateGameError::AuthorInGame) => {
+            let response = "Finish your current game in this server before starting a new one. Use `connect-four concede` to end your current game.";
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(ConnectFourCreateGameError::OpponentInGame) => {
+            let response = "Your opponent is currently in another game in this server. Wait for them to finish.";
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+        Err(ConnectFourCreateGameError::Database(error)) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, "database error").await?;
+            return Ok(());
+        }
+    };
+
+    // The computer already moved if it is red,
+    // so this is always a user.
+    let content = format!(
+        "Game created! Your turn {}",
+        game.get_player_turn().mention()
+    );
+    match create_board_message(&connect_four_data, game.board, content).await {
+        Ok(message_builder) => {
+            msg.channel_id
+                .send_message(&ctx.http, message_builder)
+                .await?;
+        }
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, format!("{error:?}")).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use crate::commands::tic_tac_toe::renderer::{
+    SkiaBuilder,
+    FONT_FACE,
+};
+use anyhow::Context;
+use connect_four::{
+    Team,
+    HEIGHT,
+    WIDTH,
+};
+use std::{
+    sync::Arc,
+    time::Instant,
+};
+use tiny_skia::{
+    Color,
+    Paint,
+    Path,
+    PathBuilder,
+    Pixmap,
+    Rect,
+    Stroke,
+    Transform,
+};
+use tokio::sync::Semaphore;
+use tracing::info;
+
+const TILE_SIZE: u16 = 60;
+const TILE_SIZE_F32: f32 = TILE_SIZE as f32;
+const HALF_TILE_SIZE_F32: f32 = TILE_SIZE_F32 / 2.0;
+const PIECE_RADIUS: f32 = HALF_TILE_SIZE_F32 - 6.0;
+
+// The top row holds the column numbers
+const RENDERED_WIDTH: u16 = TILE_SIZE * WIDTH as u16;
+const RENDERED_HEIGHT: u16 = TILE_SIZE * (HEIGHT as u16 + 1);
+
+const MAX_PARALLEL_RENDER_LIMIT: usize = 4;
+
+const HEADER_COLOR: Color = Color::from_rgba8(48, 48, 48, 255);
+const BOARD_COLOR: Color = Color::from_rgba8(0, 82, 204, 255);
+const EMPTY_COLOR: Color = Color::from_rgba8(230, 230, 230, 255);
+const RED_COLOR: Color = Color::from_rgba8(220, 20, 60, 255);
+const YELLOW_COLOR: Color = Color::from_rgba8(255, 204, 0, 255);
+const WINNING_LINE_COLOR: Color = Color::from_rgba8(48, 48, 48, 255);
+
+/// Get the center of a tile in the image.
+///
+/// Row 0 is the bottom row.
+fn get_tile_center(column: u8, row: u8) -> (f32, f32) {
+    let x = f32::from(u16::from(column) * TILE_SIZE) + HALF_TILE_SIZE_F32;
+    let y = f32::from(u16::from(HEIGHT - row) * TILE_SIZE) + HALF_TILE_SIZE_F32;
+    (x, y)
+}
+
+/// Render a Connect Four board
+#[derive(Debug, Clone)]
+pub(crate) struct Renderer {
+    background_pixmap: Arc<Pixmap>,
+    piece_path: Arc<Path>,
+
+    render_semaphore: Arc<Semaphore>,
+}
+
+impl Renderer {
+    /// Make a new [`Renderer`].
+    pub(crate) fn new() -> anyhow::Result<Self> {
+        let mut background_pixmap = Pixmap::new(RENDERED_WIDTH.into(), RENDERED_HEIGHT.into())
+            .context("failed to create background pixmap")?;
+
+        let mut paint = Paint::default();
+        paint.anti_alias = true;
+
+        let header = Rect::from_xywh(0.0, 0.0, f32::from(RENDERED_WIDTH), TILE_SIZE_F32)
+            .context("failed to make header")?;
+        paint.set_color(HEADER_COLOR);
+        background_pixmap.fill_rect(header, &paint, Transform::identity(), None);
+
+        let board = Rect::from_xywh(
+            0.0,
+            TILE_SIZE_F32,
+            f32::from(RENDERED_WIDTH),
+            f32::from(RENDERED_HEIGHT - TILE_SIZE),
+        )
+        .context("failed to make board")?;
+        paint.set_color(BOARD_COLOR);
+        background_pixmap.fill_rect(board, &paint, Transform::identity(), None);
+
+        let piece_path = PathBuilder::from_circle(0.0, 0.0, PIECE_RADIUS)
+            .context("failed to make piece path")?;
+        paint.set_color(EMPTY_COLOR);
+        for column in 0..WIDTH {
+            for row in 0..HEIGHT {
+                let (x, y) = get_tile_center(column, row);
+                background_pixmap.fill_path(
+                    &piece_path,
+                    &paint,
+                    Default::default(),
+                    Transform::from_translate(x, y),
+                    None,
+                );
+            }
+        }
+
+        // Column numbers start at 1
+        paint.set_color_rgba8(255, 255, 255, 255);
+        for column in 0..WIDTH {
+            let c = char::from(b'1' + column);
+            let glyph_id = FONT_FACE
+                .glyph_index(c)
+                .with_context(|| format!("missing glyph for '{c}'"))?;
+
+            let mut builder = SkiaBuilder::new();
+            let _bb = FONT_FACE
+                .outline_glyph(glyph_id, &mut builder)
+                .with_context(|| format!("missing glyph bounds for '{c}'"))?;
+            let path = builder
+                .into_path()
+                .with_context(|| format!("failed to generate glyph path for '{c}'"))?;
+            let bounds = path.bounds();
+
+            let ratio = (TILE_SIZE_F32 / 2.0) / bounds.height().max(bounds.width());
+            let transform = Transform::from_scale(ratio, ratio).post_translate(
+                f32::from(u16::from(column) * TILE_SIZE) + HALF_TILE_SIZE_F32
+                    - (ratio * bounds.width() / 2.0),
+                HALF_TILE_SIZE_F32 - (ratio * bounds.height() / 2.0),
+            );
+
+            background_pixmap.fill_path(&path, &paint, Default::default(), transform, None);
+        }
+
+        Ok(Self {
+            background_pixmap: Arc::new(background_pixmap),
+            piece_path: Arc::new(piece_path),
+            render_semaphore: Arc::new(Semaphore::new(MAX_PARALLEL_RENDER_LIMIT)),
+        })
+    }
+
+    /// Draw a Connect Four board with `tiny_skia`.
+    fn draw_board(&self, board: connect_four::Board) -> anyhow::Result<Pixmap> {
+        let mut pixmap = self.background_pixmap.as_ref().as_ref().to_owned();
+
+        let mut paint = Paint::default();
+        paint.anti_alias = true;
+
+        for column in 0..WIDTH {
+            for row in 0..board.get_column_height(column) {
+                let color = match board.get(column, row) {
+                    Some(Team::Red) => RED_COLOR,
+                    Some(Team::Yellow) => YELLOW_COLOR,
+                    None => continue,
+                };
+                paint.set_color(color);
+
+                let (x, y) = get_tile_center(column, row);
+                pixmap.fill_path(
+                    &self.piece_path,
+                    &paint,
+                    Default::default(),
+                    Transform::from_translate(x, y),
+                    None,
+                );
+            }
+        }
+
+        if let Some(winner_info) = board.get_winner_info() {
+            let (start_column, start_row) = winner_info.start_tile();
+            let (end_column, end_row) = winner_info.end_tile();
+            let (start_x, start_y) = get_tile_center(start_column, start_row);
+            let (end_x, end_y) = get_tile_center(end_column, end_row);
+
+            let mut path_builder = PathBuilder::new();
+            path_builder.move_to(start_x, start_y);
+            path_builder.line_to(end_x, end_y);
+            let path = path_builder
+                .finish()
+                .context("failed to draw winning line")?;
+
+            let stroke = Stroke {
+                width: 10.0,
+                line_cap: tiny_skia::LineCap::Round,
+                ..Default::default()
+            };
+            paint.set_color(WINNING_LINE_COLOR);
+            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        }
+
+        Ok(pixmap)
+    }
+
+    /// Render a Connect Four board as a png.
+    pub(crate) fn render_board(&self, board: connect_four::Board) -> anyhow::Result<Vec<u8>> {
+        let draw_start = Instant::now();
+        let pixmap = self.draw_board(board)?;
+        let draw_end = Instant::now();
+        info!("board draw time: {:?}", draw_end - draw_start);
+
+        let encode_start = Instant::now();
+        let img = pixmap.encode_png().context("failed to encode board")?;
+        let encode_end = Instant::now();
+
+        info!("board png encode time: {:?}", encode_end - encode_start);
+
+        Ok(img)
+    }
+
+    /// Render a Connect Four board on a threadpool
+    pub(crate) async fn render_board_async(
+        &self,
+        board: connect_four::Board,
+    ) -> anyhow::Result<Vec<u8>> {
+        let _permit = self.render_semaphore.acquire().await?;
+        let self_clone = self.clone();
+        tokio::task::spawn_blocking(move || self_clone.render_board(board)).await?
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_board() {
+        let renderer = Renderer::new().expect("failed to make renderer");
+        let board = connect_four::Board::new()
+            .play(0)
+            .play(1)
+            .play(1)
+            .play(2)
+            .play(2)
+            .play(3)
+            .play(2)
+            .play(3)
+            .play(3)
+            .play(6)
+            .play(3);
+        let img = renderer.render_board(board).expect("failed to render");
+        std::fs::write("c4-render-test.png", img).expect("failed to save");
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    checks::ENABLED_CHECK,
+    util::AsciiTable,
+    ClientDataKey,
+};
+use anyhow::Context as _;
+use serenity::{
+    client::Context,
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::prelude::*,
+};
+use tracing::error;
+
+#[command]
+#[description("Get personal stats for Connect Four")]
+#[checks(Enabled)]
+#[bucket("default")]
+pub async fn stats(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let scores = match db
+        .get_connect_four_score(msg.guild_id.into(), msg.author.id)
+        .await
+        .context("failed to get connect four stats")
+    {
+        Ok(scores) => scores,
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, format!("{error:?}")).await?;
+            return Ok(());
+        }
+    };
+
+    let mut table = AsciiTable::new(4, 2);
+    table.set_padding(2);
+
+    table.set_cell(0, 0, "Wins");
+    table.set_cell(1, 0, "Losses");
+    table.set_cell(2, 0, "Ties");
+    table.set_cell(3, 0, "Concedes");
+
+    table.set_cell(0, 1, scores.wins.to_string());
+    table.set_cell(1, 1, scores.losses.to_string());
+    table.set_cell(2, 1, scores.ties.to_string());
+    table.set_cell(3, 1, scores.concedes.to_string());
+
+    let name = &msg.author.name;
+    msg.channel_id
+        .say(
+            &ctx.http,
+            format!("```\n{name}'s Connect Four Stats\n{table}\n```"),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::{
+    commands::{
+        reddit_embed::RedditEmbedData,
+        tiktok_embed::TikTokData,
+    },
+    util,
+};
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
+use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+        EditInteractionResponse,
+    },
+    model::channel::Message,
+};
+use tracing::error;
+use url::Url;
+
+/// Create a message context menu command to embed the TikTok and Reddit links of a message.
+///
+/// Unlike automatic embeds, this works even if embeds are disabled for the server,
+/// since a user explicitly asked for it.
+pub fn create_context_menu_command() -> anyhow::Result<pikadick_slash_framework::ContextMenuCommand>
+{
+    pikadick_slash_framework::ContextMenuCommandBuilder::new()
+        .name("Embed TikTok/Reddit links")
+        .bucket("default")
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             message: Message,
+             (reddit_embed_data, tiktok_data): (State<RedditEmbedData>, State<TikTokData>)| async move {
+                // We collect into a `Vec` as the regex iterator is not Sync and cannot be held across await points.
+                let urls: Vec<Url> = util::extract_urls(&message.content)
+                    .filter(|url| {
+                        matches!(
+                            url.host(),
+                            Some(url::Host::Domain(
+                                "www.reddit.com"
+                                    | "reddit.com"
+                                    | "vm.tiktok.com"
+                                    | "tiktok.com"
+                                    | "www.tiktok.com"
+                            ))
+                        )
+                    })
+                    .collect();
+
+                if urls.is_empty() {
+                    let message_builder = CreateInteractionResponseMessage::new()
+                        .content("That message has no TikTok or Reddit links")
+                        .ephemeral(true);
+                    let response = CreateInteractionResponse::Message(message_builder);
+                    interaction.create_response(&ctx.http, response).await?;
+                    return Ok(());
+                }
+
+                // Embeds are sent to the channel of the message,
+                // so only tell the user how it went.
+                interaction.defer_ephemeral(&ctx.http).await?;
+
+                let mut num_failed = 0;
+                for url in urls.iter() {
+                    let result = match url.host() {
+                        Some(url::Host::Domain("www.reddit.com" | "reddit.com")) => reddit_embed_data
+                            .try_embed_url(&ctx, &message, url, &mut None)
+                            .await
+                            .context("failed to generate reddit embed"),
+                        _ => tiktok_data
+                            .try_embed_url(&ctx, &message, url, &mut None, false)
+                            .await
+                            .context("failed to generate tiktok embed"),
+                    };
+
+                    if let Err(error) = result {
+                        error!("{error:?}");
+                        num_failed += 1;
+                    }
+                }
+
+                reddit_embed_data.cache.trim();
+                reddit_embed_data.video_data_cache.trim();
+                tiktok_data.post_page_cache.trim();
+
+                let content = if num_failed == 0 {
+                    format!("Embedded {} link(s)", urls.len())
+                } else {
+                    format!("Failed to embed {num_failed} of {} link(s)", urls.len())
+                };
+                interaction
+                    .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+                    .await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build embed links context menu command")
+}
@@ -12,10 +12,17 @@ use crate::{
     ClientDataKey,
 };
 use anyhow::Context as _;
+use pikadick_slash_framework::{
+    FromOptions,
+    State,
+};
 use serenity::{
     builder::{
         CreateEmbed,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
         CreateMessage,
+        EditInteractionResponse,
     },
     framework::standard::{
         macros::command,
@@ -26,7 +33,10 @@ use serenity::{
     prelude::*,
 };
 use std::sync::Arc;
-use tracing::error;
+use tracing::{
+    error,
+    info,
+};
 
 #[derive(Clone, Debug)]
 pub struct IqdbClient {
@@ -78,6 +88,21 @@ impl CacheStatsProvider for IqdbClient {
     }
 }
 
+/// Make an embed for the best match of a search.
+///
+/// Returns `None` if there is no best match.
+pub fn create_best_match_embed(data: &iqdb::SearchResults) -> Option<CreateEmbed> {
+    let data = data.best_match.as_ref()?;
+
+    let embed_builder = CreateEmbed::new()
+        .title("IQDB Best Match")
+        .image(data.image_url.as_str())
+        .url(data.url.as_str())
+        .description(data.url.as_str());
+
+    Some(embed_builder)
+}
+
 #[command]
 #[description("Search IQDB for an image at a url")]
 #[usage("<img_url>")]
@@ -103,29 +128,21 @@ async fn iqdb(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
         .await
         .context("failed to search for image")
     {
-        Ok(data) => {
-            let data = data.data();
-            match data.best_match.as_ref() {
-                Some(data) => {
-                    let embed_builder = CreateEmbed::new()
-                        .title("IQDB Best Match")
-                        .image(data.image_url.as_str())
-                        .url(data.url.as_str())
-                        .description(data.url.as_str());
-                    let message_builder = CreateMessage::new().embed(embed_builder);
-                    msg.channel_id
-                        .send_message(&ctx.http, message_builder)
-                        .await?;
-
-                    loading.send_ok();
-                }
-                None => {
-                    msg.channel_id
-                        .say(&ctx.http, format!("No results on iqdb for \"{query}\"",))
-                        .await?;
-                }
+        Ok(data) => match create_best_match_embed(data.data()) {
+            Some(embed_builder) => {
+                let message_builder = CreateMessage::new().embed(embed_builder);
+                msg.channel_id
+                    .send_message(&ctx.http, message_builder)
+                    .await?;
+
+                loading.send_ok();
             }
-        }
+            None => {
+                msg.channel_id
+                    .say(&ctx.http, format!("No results on iqdb for \"{query}\"",))
+                    .await?;
+            }
+        },
         Err(error) => {
             error!("{error:?}");
             msg.channel_id.say(&ctx.http, format!("{error:?}")).await?;
@@ -134,3 +151,75 @@ async fn iqdb(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 
     Ok(())
 }
+
+/// Options for iqdb
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct IqdbOptions {
+    /// The image to search for
+    #[pikadick_slash_framework(description = "The image to search for")]
+    image: Option<Attachment>,
+
+    /// The url of the image to search for
+    #[pikadick_slash_framework(description = "The url of the image to search for")]
+    url: Option<String>,
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("iqdb")
+        .description("Search IQDB for an image")
+        .category("Images")
+        .example("/iqdb url:https://example.com/image.png")
+        .bucket("default")
+        .arguments(IqdbOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx, interaction, args: IqdbOptions, client: State<IqdbClient>| async move {
+                let query = match args.image.map(|image| image.url).or(args.url) {
+                    Some(query) => query,
+                    None => {
+                        let message_builder = CreateInteractionResponseMessage::new()
+                            .content("Missing an image or image url to search for");
+                        let response = CreateInteractionResponse::Message(message_builder);
+                        interaction.create_response(&ctx.http, response).await?;
+                        return Ok(());
+                    }
+                };
+
+                info!("searching iqdb for \"{query}\"");
+
+                interaction.defer(&ctx.http).await?;
+
+                let mut edit_response_builder = EditInteractionResponse::new();
+                match client
+                    .search(&query)
+                    .await
+                    .context("failed to search for image")
+                {
+                    Ok(data) => match create_best_match_embed(data.data()) {
+                        Some(embed_builder) => {
+                            edit_response_builder = edit_response_builder.embed(embed_builder);
+                        }
+                        None => {
+                            edit_response_builder = edit_response_builder
+                                .content(format!("No results on iqdb for \"{query}\""));
+                        }
+                    },
+                    Err(error) => {
+                        error!("{error:?}");
+                        edit_response_builder = edit_response_builder.content(format!("{error:?}"));
+                    }
+                }
+
+                interaction
+                    .edit_response(&ctx.http, edit_response_builder)
+                    .await?;
+
+                client.search_cache.trim();
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build iqdb command")
+}
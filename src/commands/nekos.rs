@@ -3,17 +3,21 @@ use crate::{
         CacheStatsBuilder,
         CacheStatsProvider,
     },
-    ClientDataKey,
+    locale::TRANSLATIONS,
 };
 use anyhow::Context as _;
 use crossbeam::queue::ArrayQueue;
 use indexmap::set::IndexSet;
 use parking_lot::RwLock;
-use pikadick_slash_framework::FromOptions;
+use pikadick_slash_framework::State;
 use rand::Rng;
-use serenity::builder::{
-    CreateInteractionResponse,
-    CreateInteractionResponseMessage,
+use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    client::Context,
+    model::application::CommandInteraction,
 };
 use std::sync::Arc;
 use tracing::error;
@@ -212,11 +216,31 @@ impl Default for NekosClient {
 // TODO:
 // Consider adding https://nekos.life/api/v2/endpoints
 
-/// Arguments for the nekos command
-#[derive(Debug, Copy, Clone, FromOptions)]
-pub struct NekosArguments {
-    /// Whether the command should look for nsfw pictures
-    pub nsfw: Option<bool>,
+/// Reply to an interaction with a random nekos image
+async fn respond_rand(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    nekos_client: &NekosClient,
+    nsfw: bool,
+) -> Result<(), pikadick_slash_framework::BoxError> {
+    let content = match nekos_client
+        .get_rand(nsfw)
+        .await
+        .context("failed to repopulate nekos caches")
+    {
+        Ok(url) => url.into(),
+        Err(error) => {
+            error!("{error:?}");
+            format!("{error:?}")
+        }
+    };
+
+    let message_builder = CreateInteractionResponseMessage::new().content(content);
+    let response = CreateInteractionResponse::Message(message_builder);
+
+    interaction.create_response(&ctx.http, response).await?;
+
+    Ok(())
 }
 
 /// Make a nekos slash command
@@ -224,40 +248,36 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
     pikadick_slash_framework::CommandBuilder::new()
         .name("nekos")
         .description("Get a random neko")
-        .argument(
-            pikadick_slash_framework::ArgumentParamBuilder::new()
+        .category("Images")
+        .example("/nekos sfw")
+        .example("/nekos nsfw")
+        .localize(&TRANSLATIONS, "commands.nekos")
+        .bucket("default")
+        .subcommand(
+            pikadick_slash_framework::CommandBuilder::new()
+                .name("sfw")
+                .description("Get a random sfw neko")
+                .localize(&TRANSLATIONS, "commands.nekos.sfw")
+                .on_process_with_state(
+                    |ctx, interaction, _args: (), nekos_client: State<NekosClient>| async move {
+                        respond_rand(&ctx, &interaction, &nekos_client, false).await
+                    },
+                )
+                .build()?,
+        )
+        .subcommand(
+            pikadick_slash_framework::CommandBuilder::new()
                 .name("nsfw")
-                .kind(pikadick_slash_framework::ArgumentKind::Boolean)
-                .description("Whether this should use nsfw results")
+                .description("Get a random nsfw neko")
+                .localize(&TRANSLATIONS, "commands.nekos.nsfw")
+                .nsfw(true)
+                .on_process_with_state(
+                    |ctx, interaction, _args: (), nekos_client: State<NekosClient>| async move {
+                        respond_rand(&ctx, &interaction, &nekos_client, true).await
+                    },
+                )
                 .build()?,
         )
-        .on_process(|ctx, interaction, args: NekosArguments| async move {
-            let data_lock = ctx.data.read().await;
-            let client_data = data_lock
-                .get::<ClientDataKey>()
-                .expect("failed to get client data");
-            let nekos_client = client_data.nekos_client.clone();
-            drop(data_lock);
-
-            let content = match nekos_client
-                .get_rand(args.nsfw.unwrap_or(false))
-                .await
-                .context("failed to repopulate nekos caches")
-            {
-                Ok(url) => url.into(),
-                Err(error) => {
-                    error!("{error:?}");
-                    format!("{error:?}")
-                }
-            };
-
-            let message_builder = CreateInteractionResponseMessage::new().content(content);
-            let response = CreateInteractionResponse::Message(message_builder);
-
-            interaction.create_response(&ctx.http, response).await?;
-
-            Ok(())
-        })
         .build()
         .context("failed to build command")
 }
@@ -1,3 +1,4 @@
+use crate::locale::TRANSLATIONS;
 use anyhow::Context as _;
 use serenity::builder::{
     CreateInteractionResponse,
@@ -9,8 +10,12 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
     pikadick_slash_framework::CommandBuilder::new()
         .name("ping")
         .description("Respond with pong")
+        .category("Utility")
+        .example("/ping")
+        .localize(&TRANSLATIONS, "commands.ping")
         .on_process(|ctx, interaction, _args: ()| async move {
-            let message_builder = CreateInteractionResponseMessage::new().content("pong");
+            let content = TRANSLATIONS.get_for(&interaction, "commands.ping.response");
+            let message_builder = CreateInteractionResponseMessage::new().content(content);
             let response = CreateInteractionResponse::Message(message_builder);
             interaction.create_response(&ctx.http, response).await?;
             Ok(())
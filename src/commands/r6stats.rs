@@ -77,6 +77,9 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
     pikadick_slash_framework::CommandBuilder::new()
         .name("r6stats")
         .description("Get r6 stats for a user from r6stats")
+        .category("Games")
+        .example("/r6stats name:Pengu.G2")
+        .bucket("r6stats")
         .argument(
             pikadick_slash_framework::ArgumentParamBuilder::new()
                 .name("name")
@@ -7,12 +7,16 @@ use crate::{
         TimedCache,
         TimedCacheEntry,
     },
-    ClientDataKey,
 };
 use anyhow::Context as _;
+use pikadick_slash_framework::{
+    Response,
+    ResponseMessage,
+    State,
+};
 use serenity::builder::{
+    AutocompleteChoice,
     CreateEmbed,
-    EditInteractionResponse,
 };
 use std::sync::Arc;
 use tracing::{
@@ -234,6 +238,24 @@ impl R6TrackerClient {
             .get_if_fresh(query)
             .context("cache data expired")
     }
+
+    /// Get the names of recently looked-up players that start with the given prefix.
+    ///
+    /// Names of players that could not be found are excluded.
+    pub fn get_recent_names(&self, prefix: &str) -> Vec<String> {
+        let prefix = prefix.to_lowercase();
+        let mut names: Vec<String> = self
+            .search_cache
+            .get_all_fresh()
+            .into_iter()
+            .filter(|(name, entry)| {
+                entry.data().is_some() && name.to_lowercase().starts_with(&prefix)
+            })
+            .map(|(name, _)| name)
+            .collect();
+        names.sort_unstable();
+        names
+    }
 }
 
 impl CacheStatsProvider for R6TrackerClient {
@@ -258,56 +280,57 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
     pikadick_slash_framework::CommandBuilder::new()
         .name("r6tracker")
         .description("Get r6 stats for a user from r6tracker")
+        .category("Games")
+        .example("/r6tracker name:Pengu.G2")
+        .bucket("r6tracker")
         .argument(
             pikadick_slash_framework::ArgumentParamBuilder::new()
                 .name("name")
                 .description("The name of the user")
                 .kind(pikadick_slash_framework::ArgumentKind::String)
                 .required(true)
+                .on_autocomplete_with_state(
+                    |_ctx, _interaction, value, client: State<R6TrackerClient>| async move {
+                        let choices = client
+                            .get_recent_names(&value)
+                            .into_iter()
+                            .map(AutocompleteChoice::from)
+                            .collect();
+
+                        Ok(choices)
+                    },
+                )
                 .build()?,
         )
-        .on_process(|ctx, interaction, args: R6TrackerOptions| async move {
-            let data_lock = ctx.data.read().await;
-            let client_data = data_lock
-                .get::<ClientDataKey>()
-                .expect("missing client data");
-            let client = client_data.r6tracker_client.clone();
-            drop(data_lock);
-
-            let name = args.name;
-
-            info!("Getting r6 stats for \"{name}\" using R6Tracker");
-
-            interaction.defer(&ctx.http).await?;
-
-            let result = client
-                .get_stats(&name)
-                .await
-                .with_context(|| format!("failed to get r6tracker stats for \"{name}\""));
-
-            let mut edit_response_builder = EditInteractionResponse::new();
-            match result.as_ref().map(|entry| entry.data()) {
-                Ok(Some(stats)) => {
-                    let embed_builder = stats.populate_embed(CreateEmbed::new());
-                    edit_response_builder = edit_response_builder.embed(embed_builder);
-                }
-                Ok(None) => {
-                    edit_response_builder = edit_response_builder.content("No Results");
-                }
-                Err(error) => {
-                    error!("{error:?}");
-                    edit_response_builder = edit_response_builder.content(format!("{error:?}"));
-                }
-            }
-
-            interaction
-                .edit_response(&ctx.http, edit_response_builder)
-                .await?;
-
-            client.search_cache.trim();
-
-            Ok(())
-        })
+        .on_process_with_state(
+            |ctx, interaction, args: R6TrackerOptions, client: State<R6TrackerClient>| async move {
+                let name = args.name;
+
+                info!("Getting r6 stats for \"{name}\" using R6Tracker");
+
+                let mut response = Response::new(&ctx, &interaction);
+                let result = response
+                    .defer_if_slow(client.get_stats(&name))
+                    .await?
+                    .with_context(|| format!("failed to get r6tracker stats for \"{name}\""));
+
+                let message = match result.as_ref().map(|entry| entry.data()) {
+                    Ok(Some(stats)) => {
+                        ResponseMessage::new().embed(stats.populate_embed(CreateEmbed::new()))
+                    }
+                    Ok(None) => ResponseMessage::new().content("No Results"),
+                    Err(error) => {
+                        error!("{error:?}");
+                        ResponseMessage::new().content(format!("{error:?}"))
+                    }
+                };
+                response.send(message).await?;
+
+                client.search_cache.trim();
+
+                Ok(())
+            },
+        )
         .build()
         .context("failed to build r6tracker command")
 }
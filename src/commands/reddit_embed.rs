@@ -8,6 +8,7 @@ use crate::{
         CacheStatsProvider,
     },
     util::{
+        parse_yes_no,
         LoadingReaction,
         TimedCache,
         TimedCacheEntry,
@@ -22,6 +23,10 @@ use dashmap::DashMap;
 use rand::seq::SliceRandom;
 use reddit_tube::types::get_video_response::GetVideoResponseOk;
 use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
     framework::standard::{
         macros::command,
         Args,
@@ -44,6 +49,9 @@ use tracing::{
 };
 use url::Url;
 
+/// The custom id prefix of the settings modal
+const SETTINGS_MODAL_PREFIX: &str = "reddit-embed-settings";
+
 type SubReddit = String;
 type PostId = String;
 
@@ -387,26 +395,132 @@ async fn reddit_embed(ctx: &Context, msg: &Message, mut args: Args) -> CommandRe
     };
 
     let old_val = db.set_reddit_embed_enabled(guild_id, enable).await?;
+    msg.channel_id
+        .say(&ctx.http, format_reddit_embed_change(old_val, enable))
+        .await?;
+
+    Ok(())
+}
 
+/// Describe a change of the reddit embed setting
+fn format_reddit_embed_change(old_val: bool, enable: bool) -> String {
     let status_str = if enable { "enabled" } else { "disabled" };
 
     if enable == old_val {
-        msg.channel_id
-            .say(
-                &ctx.http,
-                format!("Reddit embeds are already {} for this server", status_str),
-            )
-            .await?;
+        format!("Reddit embeds are already {} for this server", status_str)
     } else {
-        msg.channel_id
-            .say(
-                &ctx.http,
-                format!("Reddit embeds are now {} for this guild", status_str),
-            )
-            .await?;
+        format!("Reddit embeds are now {} for this guild", status_str)
     }
+}
 
-    Ok(())
+/// Options for reddit-embed
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct RedditEmbedOptions {
+    /// Whether embeds should be enabled for this server
+    #[pikadick_slash_framework(description = "Whether embeds should be enabled for this server")]
+    enable: Option<bool>,
+}
+
+/// Settings form for reddit embeds
+#[derive(Debug, pikadick_slash_framework::FromModal)]
+struct RedditEmbedSettingsModal {
+    #[pikadick_slash_framework(label = "Enabled? (yes/no)", max_length = 8)]
+    enable: String,
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("reddit-embed")
+        .description("Configure reddit embeds for this server")
+        .category("Admin")
+        .example("/reddit-embed enable:True")
+        .example("/reddit-embed")
+        .bucket("default")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .dm_permission(false)
+        .check(crate::checks::admin::create_slash_check)
+        .arguments(RedditEmbedOptions::get_argument_params()?.into_iter())
+        .on_process(|ctx, interaction, args: RedditEmbedOptions| async move {
+            let data_lock = ctx.data.read().await;
+            let client_data = data_lock.get::<ClientDataKey>().unwrap();
+            let db = client_data.db.clone();
+            drop(data_lock);
+
+            let mut response = pikadick_slash_framework::Response::new(&ctx, &interaction);
+            let guild_id = match interaction.guild_id {
+                Some(id) => id,
+                None => {
+                    response
+                        .send_content("Missing server id. Are you in a server right now?")
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            let enable = match args.enable {
+                Some(enable) => enable,
+                None => {
+                    // Open the settings form if no settings were given
+                    let modal = pikadick_slash_framework::create_modal::<
+                        RedditEmbedSettingsModal,
+                        (),
+                    >(SETTINGS_MODAL_PREFIX, &(), "Reddit Embeds")
+                    .context("failed to create reddit embed settings modal")?;
+                    interaction
+                        .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            let old_val = db.set_reddit_embed_enabled(guild_id, enable).await?;
+            response
+                .send_content(format_reddit_embed_change(old_val, enable))
+                .await?;
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build reddit-embed command")
+}
+
+/// Create the handler for the settings modal
+pub fn create_settings_modal_handler() -> anyhow::Result<pikadick_slash_framework::ModalHandler> {
+    pikadick_slash_framework::ModalHandlerBuilder::new()
+        .prefix(SETTINGS_MODAL_PREFIX)
+        .command("reddit-embed")
+        .check(crate::checks::admin::create_modal_check)
+        .on_process(
+            |ctx, interaction, _state: (), fields: RedditEmbedSettingsModal| async move {
+                let data_lock = ctx.data.read().await;
+                let client_data = data_lock.get::<ClientDataKey>().unwrap();
+                let db = client_data.db.clone();
+                drop(data_lock);
+
+                let guild_id = interaction
+                    .guild_id
+                    .context("missing server id. Are you in a server right now?")?;
+                let enable =
+                    parse_yes_no(&fields.enable).context("\"Enabled?\" must be yes or no")?;
+
+                let old_val = db.set_reddit_embed_enabled(guild_id, enable).await?;
+                let message_builder = CreateInteractionResponseMessage::new()
+                    .content(format_reddit_embed_change(old_val, enable));
+                interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(message_builder),
+                    )
+                    .await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build reddit embed settings modal handler")
 }
 
 /// Gets the subreddit and post id from a reddit url.
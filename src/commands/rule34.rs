@@ -3,17 +3,22 @@ use crate::{
         CacheStatsBuilder,
         CacheStatsProvider,
     },
+    locale::TRANSLATIONS,
     util::{
         TimedCache,
         TimedCacheEntry,
     },
-    ClientDataKey,
 };
 use anyhow::Context as _;
+use pikadick_slash_framework::State;
 use rand::seq::SliceRandom;
-use serenity::builder::{
-    CreateInteractionResponse,
-    CreateInteractionResponseMessage,
+use serenity::{
+    builder::{
+        AutocompleteChoice,
+        CreateActionRow,
+        CreateButton,
+    },
+    model::application::ButtonStyle,
 };
 use std::sync::Arc;
 use tracing::{
@@ -21,6 +26,17 @@ use tracing::{
     info,
 };
 
+/// The max number of tags to suggest.
+///
+/// This is the max number of autocomplete choices Discord accepts.
+const MAX_TAG_SUGGESTIONS: u16 = 25;
+
+/// The max length of an autocomplete choice name or value
+const MAX_CHOICE_LEN: usize = 100;
+
+/// The custom id prefix of the reroll button
+const REROLL_PREFIX: &str = "rule34-reroll";
+
 /// A caching rule34 client
 #[derive(Clone, Default, Debug)]
 pub struct Rule34Client {
@@ -34,6 +50,8 @@ pub struct Rule34Client {
     // in the future, or add a setting to it to cap the maximum
     // number of entries.
     list_cache: TimedCache<String, rule34::PostList>,
+    /// Tag lists for autocomplete, keyed by tag prefix
+    tag_cache: TimedCache<String, rule34::TagList>,
 }
 
 impl Rule34Client {
@@ -42,6 +60,7 @@ impl Rule34Client {
         Rule34Client {
             client: rule34::Client::new(),
             list_cache: TimedCache::new(),
+            tag_cache: TimedCache::new(),
         }
     }
 
@@ -62,11 +81,39 @@ impl Rule34Client {
             .context("failed to search rule34")?;
         Ok(self.list_cache.insert_and_get(String::from(tags), results))
     }
+
+    /// List tags that start with the given prefix.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_tags_with_prefix(
+        &self,
+        prefix: &str,
+    ) -> anyhow::Result<Arc<TimedCacheEntry<rule34::TagList>>> {
+        if let Some(entry) = self.tag_cache.get_if_fresh(prefix) {
+            return Ok(entry);
+        }
+
+        // `_` is a single char wildcard in this pattern,
+        // so results may not actually start with the prefix.
+        // Callers are expected to filter.
+        let name_pattern = format!("{prefix}%");
+
+        let mut query = self.client.list_tags();
+        query.limit = Some(MAX_TAG_SUGGESTIONS);
+        query.name_pattern = Some(&name_pattern);
+        query.order = Some("count");
+        let results = query
+            .execute()
+            .await
+            .context("failed to list rule34 tags")?;
+
+        Ok(self.tag_cache.insert_and_get(String::from(prefix), results))
+    }
 }
 
 impl CacheStatsProvider for Rule34Client {
     fn publish_cache_stats(&self, cache_stats_builder: &mut CacheStatsBuilder) {
         cache_stats_builder.publish_stat("rule34", "list_cache", self.list_cache.len() as f32);
+        cache_stats_builder.publish_stat("rule34", "tag_cache", self.tag_cache.len() as f32);
     }
 }
 
@@ -82,63 +129,178 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
     pikadick_slash_framework::CommandBuilder::new()
         .name("rule34")
         .description("Look up rule34 for almost anything")
+        .category("Images")
+        .example("/rule34 query:pikachu")
+        .localize(&TRANSLATIONS, "commands.rule34")
+        .bucket("default")
+        .nsfw(true)
         .argument(
             pikadick_slash_framework::ArgumentParamBuilder::new()
                 .name("query")
                 .description("The search query")
+                .localize(&TRANSLATIONS, "commands.rule34.query")
                 .kind(pikadick_slash_framework::ArgumentKind::String)
                 .required(true)
+                .on_autocomplete_with_state(
+                    |_ctx, _interaction, value, client: State<Rule34Client>| async move {
+                        // Only the last tag is being typed, the rest are complete.
+                        let (completed, partial) = match value.rsplit_once(' ') {
+                            Some((completed, partial)) => (Some(completed), partial),
+                            None => (None, value.as_str()),
+                        };
+
+                        if partial.is_empty() {
+                            return Ok(Vec::new());
+                        }
+
+                        let tag_list = client.list_tags_with_prefix(partial).await?;
+                        let mut tags: Vec<_> = tag_list
+                            .data()
+                            .tags
+                            .iter()
+                            .filter(|tag| tag.name.starts_with(partial))
+                            .collect();
+                        tags.sort_unstable_by(|a, b| b.count.cmp(&a.count));
+
+                        let choices = tags
+                            .into_iter()
+                            .map(|tag| {
+                                let value = match completed {
+                                    Some(completed) => format!("{completed} {}", tag.name),
+                                    None => tag.name.to_string(),
+                                };
+                                let name = format!("{value} ({})", tag.count);
+                                (name, value)
+                            })
+                            // Discord rejects choices longer than this.
+                            .filter(|(name, value)| {
+                                name.len() <= MAX_CHOICE_LEN && value.len() <= MAX_CHOICE_LEN
+                            })
+                            .map(|(name, value)| AutocompleteChoice::new(name, value))
+                            .collect();
+
+                        client.tag_cache.trim();
+
+                        Ok(choices)
+                    },
+                )
                 .build()?,
         )
-        .on_process(|ctx, interaction, args: Rule34Options| async move {
-            let data_lock = ctx.data.read().await;
-            let client_data = data_lock
-                .get::<ClientDataKey>()
-                .expect("missing client data");
-            let client = client_data.rule34_client.clone();
-            drop(data_lock);
-
-            let query_str = rule34::SearchQueryBuilder::new()
-                .add_tag_iter(args.query.split(' '))
-                .take_query_string();
-
-            info!("searching rule34 for \"{query_str}\"");
-
-            let result = client
-                .list(&query_str)
-                .await
-                .context("failed to get search results");
-
-            let mut message_builder = CreateInteractionResponseMessage::new();
-            match result {
-                Ok(list_results) => {
-                    let maybe_list_result: Option<String> = list_results
-                        .data()
-                        .posts
-                        .choose(&mut rand::thread_rng())
-                        .map(|list_result| list_result.file_url.to_string());
-
-                    if let Some(file_url) = maybe_list_result {
-                        info!("sending \"{file_url}\"");
-                        message_builder = message_builder.content(file_url);
-                    } else {
-                        info!("no results");
-                        message_builder =
-                            message_builder.content(format!("No results for \"{query_str}\""));
+        .on_process_with_state(
+            |ctx, interaction, args: Rule34Options, client: State<Rule34Client>| async move {
+                let query_str = rule34::SearchQueryBuilder::new()
+                    .add_tag_iter(args.query.split(' '))
+                    .take_query_string();
+
+                info!("searching rule34 for \"{query_str}\"");
+
+                let mut response = pikadick_slash_framework::Response::new(&ctx, &interaction);
+                let result = response
+                    .defer_if_slow(client.list(&query_str))
+                    .await?
+                    .context("failed to get search results");
+
+                let mut message_builder = pikadick_slash_framework::ResponseMessage::new();
+                match result {
+                    Ok(list_results) => {
+                        let maybe_list_result: Option<String> = list_results
+                            .data()
+                            .posts
+                            .choose(&mut rand::thread_rng())
+                            .map(|list_result| list_result.file_url.to_string());
+
+                        if let Some(file_url) = maybe_list_result {
+                            info!("sending \"{file_url}\"");
+                            message_builder = message_builder.content(file_url);
+
+                            // Very long queries do not fit in a custom id.
+                            // Those users will just have to run the command again.
+                            if let Some(custom_id) = pikadick_slash_framework::create_custom_id(
+                                REROLL_PREFIX,
+                                &query_str,
+                            ) {
+                                let button = CreateButton::new(custom_id)
+                                    .label("Reroll")
+                                    .style(ButtonStyle::Secondary);
+                                message_builder = message_builder
+                                    .components(vec![CreateActionRow::Buttons(vec![button])]);
+                            }
+                        } else {
+                            info!("no results");
+                            let content = TRANSLATIONS
+                                .get_for(&interaction, "commands.rule34.no-results")
+                                .replace("{query}", &query_str);
+                            message_builder = message_builder.content(content);
+                        }
+                    }
+                    Err(error) => {
+                        error!("{error:?}");
+                        message_builder = message_builder.content(format!("{error:?}"));
                     }
                 }
-                Err(error) => {
-                    error!("{error:?}");
-                    message_builder = message_builder.content(format!("{error:?}"));
-                }
-            }
-            let response = CreateInteractionResponse::Message(message_builder);
-            interaction.create_response(&ctx.http, response).await?;
+                response.send(message_builder).await?;
 
-            client.list_cache.trim();
+                client.list_cache.trim();
 
-            Ok(())
-        })
+                Ok(())
+            },
+        )
         .build()
         .context("failed to build rule34 command")
 }
+
+/// Create the handler for the reroll button
+pub fn create_reroll_component_handler(
+) -> anyhow::Result<pikadick_slash_framework::ComponentHandler> {
+    pikadick_slash_framework::ComponentHandlerBuilder::new()
+        .prefix(REROLL_PREFIX)
+        .command("rule34")
+        .nsfw(true)
+        .on_process_with_state(
+            |ctx, interaction, query_str: String, client: State<Rule34Client>| async move {
+                info!("rerolling rule34 for \"{query_str}\"");
+
+                let mut response = pikadick_slash_framework::Response::new(&ctx, &interaction);
+                response.update(true);
+                let result = response
+                    .defer_if_slow(client.list(&query_str))
+                    .await?
+                    .context("failed to get search results");
+
+                let content = match result {
+                    Ok(list_results) => {
+                        let maybe_list_result: Option<String> = list_results
+                            .data()
+                            .posts
+                            .choose(&mut rand::thread_rng())
+                            .map(|list_result| list_result.file_url.to_string());
+
+                        match maybe_list_result {
+                            Some(file_url) => {
+                                info!("sending \"{file_url}\"");
+                                file_url
+                            }
+                            None => {
+                                info!("no results");
+                                TRANSLATIONS
+                                    .get_for(&interaction, "commands.rule34.no-results")
+                                    .replace("{query}", &query_str)
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        error!("{error:?}");
+                        format!("{error:?}")
+                    }
+                };
+
+                response.send_content(content).await?;
+
+                client.list_cache.trim();
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build rule34 reroll component handler")
+}
@@ -4,6 +4,10 @@ use crate::{
         CacheStatsBuilder,
         CacheStatsProvider,
     },
+    commands::iqdb::{
+        self,
+        IqdbClient,
+    },
     util::{
         LoadingReaction,
         TimedCache,
@@ -12,10 +16,14 @@ use crate::{
     ClientDataKey,
 };
 use anyhow::Context as _;
+use pikadick_slash_framework::State;
 use serenity::{
     builder::{
         CreateEmbed,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
         CreateMessage,
+        EditInteractionResponse,
     },
     framework::standard::{
         macros::command,
@@ -26,7 +34,10 @@ use serenity::{
     prelude::*,
 };
 use std::sync::Arc;
-use tracing::error;
+use tracing::{
+    error,
+    info,
+};
 
 #[derive(Clone, Debug)]
 pub struct SauceNaoClient {
@@ -76,6 +87,36 @@ impl CacheStatsProvider for SauceNaoClient {
     }
 }
 
+/// Make an embed for the best match of a search.
+///
+/// Returns `None` if there are no results.
+pub fn create_best_match_embed(data: &sauce_nao::OkResponse) -> Option<CreateEmbed> {
+    let data = data.results.first()?;
+
+    let mut embed_builder = CreateEmbed::new()
+        .title("SauceNao Best Match")
+        .image(data.header.thumbnail.as_str());
+    if let Some(ext_url) = data.data.ext_urls.first() {
+        embed_builder = embed_builder
+            .description(ext_url.as_str())
+            .url(ext_url.as_str());
+    }
+
+    if let Some(source) = data.data.source.as_deref() {
+        embed_builder = embed_builder.field("Source", source, true);
+    }
+
+    if let Some(eng_name) = data.data.eng_name.as_deref() {
+        embed_builder = embed_builder.field("English Name", eng_name, true);
+    }
+
+    if let Some(jp_name) = data.data.jp_name.as_deref() {
+        embed_builder = embed_builder.field("Jap Name", jp_name, true);
+    }
+
+    Some(embed_builder)
+}
+
 #[command("sauce-nao")]
 #[description("Search SauceNao for an image at a url")]
 #[usage("<img_url>")]
@@ -104,29 +145,8 @@ async fn sauce_nao(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         Ok(data) => {
             let data = data.data();
 
-            match data.results.first() {
-                Some(data) => {
-                    let mut embed_builder = CreateEmbed::new()
-                        .title("SauceNao Best Match")
-                        .image(data.header.thumbnail.as_str());
-                    if let Some(ext_url) = data.data.ext_urls.first() {
-                        embed_builder = embed_builder
-                            .description(ext_url.as_str())
-                            .url(ext_url.as_str());
-                    }
-
-                    if let Some(source) = data.data.source.as_deref() {
-                        embed_builder = embed_builder.field("Source", source, true);
-                    }
-
-                    if let Some(eng_name) = data.data.eng_name.as_deref() {
-                        embed_builder = embed_builder.field("English Name", eng_name, true);
-                    }
-
-                    if let Some(jp_name) = data.data.jp_name.as_deref() {
-                        embed_builder = embed_builder.field("Jap Name", jp_name, true);
-                    }
-
+            match create_best_match_embed(data) {
+                Some(embed_builder) => {
                     let message_builder = CreateMessage::new().embed(embed_builder);
 
                     msg.channel_id
@@ -150,3 +170,78 @@ async fn sauce_nao(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 
     Ok(())
 }
+
+/// Create a message context menu command to find the source of the first attachment of a message.
+///
+/// This tries SauceNao first, then IQDB.
+pub fn create_find_source_context_menu_command(
+) -> anyhow::Result<pikadick_slash_framework::ContextMenuCommand> {
+    pikadick_slash_framework::ContextMenuCommandBuilder::new()
+        .name("Find source")
+        .bucket("find-source")
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             message: Message,
+             (sauce_nao_client, iqdb_client): (State<SauceNaoClient>, State<IqdbClient>)| async move {
+                let query = match message.attachments.first() {
+                    Some(attachment) => attachment.url.clone(),
+                    None => {
+                        let message_builder = CreateInteractionResponseMessage::new()
+                            .content("That message has no attachments")
+                            .ephemeral(true);
+                        let response = CreateInteractionResponse::Message(message_builder);
+                        interaction.create_response(&ctx.http, response).await?;
+                        return Ok(());
+                    }
+                };
+
+                info!("finding source for \"{query}\"");
+
+                interaction.defer(&ctx.http).await?;
+
+                let mut maybe_embed_builder = None;
+                match sauce_nao_client
+                    .search(&query)
+                    .await
+                    .context("failed to search SauceNao for image")
+                {
+                    Ok(data) => {
+                        maybe_embed_builder = create_best_match_embed(data.data());
+                    }
+                    Err(error) => {
+                        error!("{error:?}");
+                    }
+                }
+
+                if maybe_embed_builder.is_none() {
+                    match iqdb_client
+                        .search(&query)
+                        .await
+                        .context("failed to search IQDB for image")
+                    {
+                        Ok(data) => {
+                            maybe_embed_builder = iqdb::create_best_match_embed(data.data());
+                        }
+                        Err(error) => {
+                            error!("{error:?}");
+                        }
+                    }
+                }
+
+                let edit_response_builder = match maybe_embed_builder {
+                    Some(embed_builder) => EditInteractionResponse::new().embed(embed_builder),
+                    None => EditInteractionResponse::new().content("No source found"),
+                };
+                interaction
+                    .edit_response(&ctx.http, edit_response_builder)
+                    .await?;
+
+                sauce_nao_client.search_cache.trim();
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build find source context menu command")
+}
@@ -7,8 +7,14 @@ use crate::{
     util::TimedCache,
     ClientDataKey,
 };
+use anyhow::Context as _;
+use pikadick_slash_framework::FromOptions;
 use rand::seq::SliceRandom;
 use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
     framework::standard::{
         macros::command,
         ArgError,
@@ -31,22 +37,47 @@ use std::{
 #[derive(Debug)]
 struct GameParseError(String);
 
-struct GameArg(Game);
+/// A Borderlands game
+#[derive(Debug, Clone, Copy, pikadick_slash_framework::FromOptionValue)]
+enum GameArg {
+    #[pikadick_slash_framework(rename = "bl", name = "Borderlands")]
+    Borderlands,
+
+    #[pikadick_slash_framework(rename = "bl2", name = "Borderlands 2")]
+    Borderlands2,
+
+    #[pikadick_slash_framework(rename = "blps", name = "Borderlands: The Pre-Sequel")]
+    BorderlandsPreSequel,
+
+    #[pikadick_slash_framework(rename = "bl3", name = "Borderlands 3")]
+    Borderlands3,
+}
 
 impl FromStr for GameArg {
     type Err = GameParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "bl" => Ok(Self(Game::Borderlands)),
-            "bl2" => Ok(Self(Game::Borderlands2)),
-            "blps" => Ok(Self(Game::BorderlandsPreSequel)),
-            "bl3" => Ok(Self(Game::Borderlands3)),
+            "bl" => Ok(Self::Borderlands),
+            "bl2" => Ok(Self::Borderlands2),
+            "blps" => Ok(Self::BorderlandsPreSequel),
+            "bl3" => Ok(Self::Borderlands3),
             _ => Err(GameParseError(s.into())),
         }
     }
 }
 
+impl From<GameArg> for Game {
+    fn from(game: GameArg) -> Self {
+        match game {
+            GameArg::Borderlands => Self::Borderlands,
+            GameArg::Borderlands2 => Self::Borderlands2,
+            GameArg::BorderlandsPreSequel => Self::BorderlandsPreSequel,
+            GameArg::Borderlands3 => Self::Borderlands3,
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct ShiftClient {
     orcz_client: OrczClient,
@@ -150,7 +181,7 @@ async fn shift(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let client = client_data.shift_client.clone();
     drop(data_lock);
 
-    let game = match args.single::<GameArg>().map(|el| el.0) {
+    let game = match args.single::<GameArg>().map(Game::from) {
         Ok(g) => g,
         Err(ArgError::Parse(e)) => {
             msg.channel_id
@@ -172,18 +203,7 @@ async fn shift(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     match client.get_rand(game).await {
         Ok(Some(code)) => {
             msg.channel_id
-                .say(
-                    &ctx.http,
-                    format!(
-                        "Source: {}\nIssue Date: {}\nReward: {}\nCode: {}",
-                        code.source,
-                        code.issue_date
-                            .map(|d| d.to_string())
-                            .unwrap_or_else(|| "unknown".to_string()),
-                        code.rewards,
-                        code.pc
-                    ),
-                )
+                .say(&ctx.http, format_shift_code(&code))
                 .await?;
         }
         Ok(None) => {
@@ -202,3 +222,58 @@ async fn shift(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 
     Ok(())
 }
+
+/// Format a shift code for display
+fn format_shift_code(code: &ShiftCode) -> String {
+    format!(
+        "Source: {}\nIssue Date: {}\nReward: {}\nCode: {}",
+        code.source,
+        code.issue_date
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        code.rewards,
+        code.pc
+    )
+}
+
+/// Options for shift
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct ShiftOptions {
+    #[pikadick_slash_framework(description = "The Borderlands game")]
+    game: GameArg,
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("shift")
+        .description("Get a random shift code for a Borderlands game")
+        .category("Games")
+        .example("/shift game:Borderlands 3")
+        .bucket("default")
+        .arguments(ShiftOptions::get_argument_params()?.into_iter())
+        .on_process(|ctx, interaction, args: ShiftOptions| async move {
+            let data_lock = ctx.data.read().await;
+            let client_data = data_lock.get::<ClientDataKey>().unwrap();
+            let client = client_data.shift_client.clone();
+            drop(data_lock);
+
+            let game = Game::from(args.game);
+
+            let content = match client.get_rand(game).await {
+                Ok(Some(code)) => format_shift_code(&code),
+                Ok(None) => format!("No valid codes for {:?}", game),
+                Err(e) => format!("Failed to get shift code: {:#?}", e),
+            };
+
+            let message_builder = CreateInteractionResponseMessage::new().content(content);
+            let response = CreateInteractionResponse::Message(message_builder);
+            interaction.create_response(&ctx.http, response).await?;
+
+            client.cache.trim();
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build shift command")
+}
@@ -1,30 +1,62 @@
 mod board;
 mod concede;
+mod history;
 mod play;
-mod renderer;
+mod profile;
+pub(crate) mod renderer;
+mod replay;
 mod scoreboard;
+mod set_theme;
 mod stats;
+mod sweeper;
+mod theme;
 
-use self::renderer::Renderer;
 pub use self::{
     board::BOARD_COMMAND,
     concede::CONCEDE_COMMAND,
     play::PLAY_COMMAND,
+    profile::PROFILE_COMMAND,
     scoreboard::SCOREBOARD_COMMAND,
-    stats::STATS_COMMAND,
+    set_theme::SET_THEME_COMMAND,
+    stats::{
+        create_stats_context_menu_command,
+        STATS_COMMAND,
+    },
+    sweeper::TicTacToeSweeper,
+};
+use self::{
+    renderer::Renderer,
+    theme::Theme,
 };
 use crate::{
     checks::ENABLED_CHECK,
     database::{
-        model::TicTacToePlayer,
+        model::{
+            TicTacToeGame,
+            TicTacToeGameResult,
+            TicTacToeHistoryGame,
+            TicTacToePlayer,
+            TicTacToeTheme,
+        },
+        Database,
         TicTacToeTryMoveError,
         TicTacToeTryMoveResponse,
     },
     ClientDataKey,
 };
+use anyhow::Context as _;
+use pikadick_slash_framework::{
+    BoxError,
+    ComponentState,
+    State,
+};
 use serenity::{
     builder::{
+        CreateActionRow,
         CreateAttachment,
+        CreateButton,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
         CreateMessage,
     },
     client::Context,
@@ -34,13 +66,25 @@ use serenity::{
         CommandResult,
     },
     model::{
+        application::ButtonStyle,
         channel::Message,
         prelude::*,
     },
 };
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    sync::Arc,
+};
+use tic_tac_toe::Team;
 use tracing::error;
 
+/// The custom id prefix of the board buttons
+const MOVE_PREFIX: &str = "ttt-move";
+
+/// The reply to a user who clicks the board of a game they are not playing
+const NOT_A_PLAYER: &str =
+    "You are not playing this game. Start your own with `/tic-tac-toe play`.";
+
 /// Data pertaining to running tic_tac_toe games
 #[derive(Clone)]
 pub struct TicTacToeData {
@@ -91,9 +135,62 @@ impl std::fmt::Display for GamePlayerMention {
     }
 }
 
+/// A board theme
+#[derive(Debug, Clone, Copy, pikadick_slash_framework::FromOptionValue)]
+enum ThemeArg {
+    #[pikadick_slash_framework(rename = "classic", name = "Classic")]
+    Classic,
+
+    #[pikadick_slash_framework(rename = "dark", name = "Dark")]
+    Dark,
+
+    #[pikadick_slash_framework(rename = "light", name = "Light")]
+    Light,
+
+    #[pikadick_slash_framework(rename = "minimal", name = "Minimal")]
+    Minimal,
+
+    #[pikadick_slash_framework(rename = "large", name = "Large")]
+    Large,
+}
+
+impl From<ThemeArg> for TicTacToeTheme {
+    fn from(theme: ThemeArg) -> Self {
+        match theme {
+            ThemeArg::Classic => Self::Classic,
+            ThemeArg::Dark => Self::Dark,
+            ThemeArg::Light => Self::Light,
+            ThemeArg::Minimal => Self::Minimal,
+            ThemeArg::Large => Self::Large,
+        }
+    }
+}
+
+/// Get the theme to render a board with.
+///
+/// The theme a user chose overrides the theme of the server.
+async fn get_theme(
+    db: &Database,
+    guild_id: Option<GuildId>,
+    theme: Option<ThemeArg>,
+) -> anyhow::Result<TicTacToeTheme> {
+    match theme {
+        Some(theme) => Ok(theme.into()),
+        None => db.get_tic_tac_toe_theme(guild_id).await,
+    }
+}
+
 #[command("tic-tac-toe")]
 #[aliases("ttt")]
-#[sub_commands("play", "concede", "board", "stats", "scoreboard")]
+#[sub_commands(
+    "play",
+    "concede",
+    "board",
+    "stats",
+    "scoreboard",
+    "profile",
+    "set_theme"
+)]
 #[description("Play a game of Tic-Tac-Toe")]
 #[usage("<move #>")]
 #[example("0")]
@@ -133,8 +230,16 @@ pub async fn tic_tac_toe(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 
     move_index -= 1;
 
+    // Text moves have nowhere to keep a theme, so prefix games always use the server theme.
+    let theme = Theme::from(db.get_tic_tac_toe_theme(guild_id).await?);
+
     match db
-        .try_tic_tac_toe_move(guild_id.into(), author_id.into(), move_index)
+        .try_tic_tac_toe_move(
+            guild_id.into(),
+            author_id.into(),
+            move_index,
+            msg.channel_id,
+        )
         .await
     {
         Ok(TicTacToeTryMoveResponse::Winner {
@@ -144,7 +249,7 @@ pub async fn tic_tac_toe(ctx: &Context, msg: &Message, mut args: Args) -> Comman
         }) => {
             let file = match tic_tac_toe_data
                 .renderer
-                .render_board_async(game.board)
+                .render_board_async(game.board, theme)
                 .await
             {
                 Ok(file) => {
@@ -174,7 +279,7 @@ pub async fn tic_tac_toe(ctx: &Context, msg: &Message, mut args: Args) -> Comman
         Ok(TicTacToeTryMoveResponse::Tie { game }) => {
             let file = match tic_tac_toe_data
                 .renderer
-                .render_board_async(game.board)
+                .render_board_async(game.board, theme)
                 .await
             {
                 Ok(file) => {
@@ -204,7 +309,7 @@ pub async fn tic_tac_toe(ctx: &Context, msg: &Message, mut args: Args) -> Comman
         Ok(TicTacToeTryMoveResponse::NextTurn { game }) => {
             let file = match tic_tac_toe_data
                 .renderer
-                .render_board_async(game.board)
+                .render_board_async(game.board, theme)
                 .await
             {
                 Ok(file) => {
@@ -251,3 +356,326 @@ pub async fn tic_tac_toe(ctx: &Context, msg: &Message, mut args: Args) -> Comman
 
     Ok(())
 }
+
+/// The state of a board button
+#[derive(Debug, Copy, Clone)]
+struct MoveState {
+    /// The tile index
+    index: u8,
+
+    /// The encoded board the button was made for.
+    ///
+    /// This keeps buttons of old boards from making moves in newer games.
+    board: u16,
+
+    /// The theme the board was rendered with.
+    ///
+    /// This is `None` for buttons made before themes were added.
+    theme: Option<TicTacToeTheme>,
+
+    /// The x and o players of the game.
+    ///
+    /// This is `None` for buttons made before players were added.
+    players: Option<(TicTacToePlayer, TicTacToePlayer)>,
+}
+
+impl ComponentState for MoveState {
+    fn encode(&self) -> String {
+        let mut state = format!("{}-{}", self.index, self.board);
+        if let Some(theme) = self.theme {
+            state.push_str(&format!("-{theme}"));
+
+            if let Some((x_player, o_player)) = self.players {
+                let x_player: Cow<'static, str> = x_player.into();
+                let o_player: Cow<'static, str> = o_player.into();
+                state.push_str(&format!("-{x_player}-{o_player}"));
+            }
+        }
+        state
+    }
+
+    fn decode(state: &str) -> Result<Self, BoxError> {
+        let mut parts = state.split('-');
+        let index = parts.next().ok_or("missing index")?;
+        let board = parts.next().ok_or("missing board")?;
+        let theme = parts.next().map(str::parse::<TicTacToeTheme>).transpose()?;
+        let players = match (parts.next(), parts.next()) {
+            (Some(x_player), Some(o_player)) => Some((x_player.parse()?, o_player.parse()?)),
+            (None, None) => None,
+            _ => return Err("missing o player".into()),
+        };
+        Ok(Self {
+            index: index.parse()?,
+            board: board.parse()?,
+            theme,
+            players,
+        })
+    }
+}
+
+/// Make the button grid for a game.
+///
+/// Occupied tiles are disabled, and every tile is disabled once the game is finished.
+/// Tiles can't be disabled for the player whose turn it isn't,
+/// as Discord shows the same buttons to everyone.
+/// The move handler rejects their clicks instead.
+fn create_board_components(
+    game: &TicTacToeGame,
+    finished: bool,
+    theme: TicTacToeTheme,
+) -> Vec<CreateActionRow> {
+    let board = game.board;
+    let encoded_board = board.encode_u16();
+    (0..3)
+        .map(|row| {
+            let buttons = (0..3)
+                .map(|column| {
+                    let index = (row * 3) + column;
+                    let state = MoveState {
+                        index,
+                        board: encoded_board,
+                        theme: Some(theme),
+                        players: Some((game.x_player, game.o_player)),
+                    };
+                    let custom_id = pikadick_slash_framework::create_custom_id(MOVE_PREFIX, &state)
+                        .expect("tic-tac-toe custom id should fit");
+
+                    let tile = board.get(index);
+                    let (label, style) = match tile {
+                        Some(Team::X) => ("X".to_string(), ButtonStyle::Danger),
+                        Some(Team::O) => ("O".to_string(), ButtonStyle::Primary),
+                        None => ((index + 1).to_string(), ButtonStyle::Secondary),
+                    };
+
+                    CreateButton::new(custom_id)
+                        .label(label)
+                        .style(style)
+                        .disabled(finished || tile.is_some())
+                })
+                .collect();
+
+            CreateActionRow::Buttons(buttons)
+        })
+        .collect()
+}
+
+/// Make a message with a rendered board and its buttons.
+///
+/// The buttons keep the theme, so later moves are rendered with it too.
+/// Boards with buttons are always png, as Discord only previews raster images inline.
+/// The board command can render svgs.
+async fn create_board_message(
+    tic_tac_toe_data: &TicTacToeData,
+    game: &TicTacToeGame,
+    content: String,
+    finished: bool,
+    theme: TicTacToeTheme,
+) -> anyhow::Result<CreateInteractionResponseMessage> {
+    let board = game.board;
+    let file = tic_tac_toe_data
+        .renderer
+        .render_board_async(board, theme.into())
+        .await
+        .context("failed to render Tic-Tac-Toe board")?;
+    let file = CreateAttachment::bytes(file, format!("ttt-{}.png", board.encode_u16()));
+
+    Ok(CreateInteractionResponseMessage::new()
+        .content(content)
+        .files([file])
+        .components(create_board_components(game, finished, theme)))
+}
+
+/// Describe the players and result of a finished game
+fn describe_history_game(game: &TicTacToeHistoryGame) -> String {
+    let description = match game.result {
+        TicTacToeGameResult::Win(team) => format!(
+            "{} beat {}",
+            game.get_player(team).mention(),
+            game.get_player(team.inverse()).mention()
+        ),
+        TicTacToeGameResult::Draw => format!(
+            "{} tied with {}",
+            game.x_player.mention(),
+            game.o_player.mention()
+        ),
+        TicTacToeGameResult::Concede(team) => format!(
+            "{} conceded to {}",
+            game.get_player(team).mention(),
+            game.get_player(team.inverse()).mention()
+        ),
+    };
+
+    match game.difficulty {
+        Some(difficulty) => format!("{description} on {difficulty}"),
+        None => description,
+    }
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("tic-tac-toe")
+        .description("Play a game of Tic-Tac-Toe")
+        .category("Games")
+        .example("/tic-tac-toe play team:X")
+        .example("/tic-tac-toe play team:O difficulty:easy")
+        .example("/tic-tac-toe play team:O opponent:@user")
+        .example("/tic-tac-toe play team:X theme:dark")
+        .example("/tic-tac-toe concede")
+        .example("/tic-tac-toe board theme:light format:svg")
+        .example("/tic-tac-toe theme theme:dark")
+        .example("/tic-tac-toe history user:@user")
+        .example("/tic-tac-toe replay game:1")
+        .example("/tic-tac-toe profile user:@user")
+        .bucket("default")
+        .subcommand(self::play::create_slash_command()?)
+        .subcommand(self::concede::create_slash_command()?)
+        .subcommand(self::board::create_slash_command()?)
+        .subcommand(self::set_theme::create_slash_command()?)
+        .subcommand(self::history::create_slash_command()?)
+        .subcommand(self::replay::create_slash_command()?)
+        .subcommand(self::profile::create_slash_command()?)
+        .build()
+        .context("failed to build tic-tac-toe command")
+}
+
+/// Create the handler for the board buttons
+pub fn create_move_component_handler() -> anyhow::Result<pikadick_slash_framework::ComponentHandler>
+{
+    pikadick_slash_framework::ComponentHandlerBuilder::new()
+        .prefix(MOVE_PREFIX)
+        .command("tic-tac-toe::play")
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             state: MoveState,
+             (tic_tac_toe_data, db): (State<TicTacToeData>, State<Database>)| async move {
+                let guild_id = interaction.guild_id;
+                let player = TicTacToePlayer::from(interaction.user.id);
+
+                let is_player = state
+                    .players
+                    .is_none_or(|(x_player, o_player)| player == x_player || player == o_player);
+                let is_stale = db
+                    .get_tic_tac_toe_game(guild_id.into(), player)
+                    .await?
+                    .is_some_and(|game| {
+                        let players_changed = state
+                            .players
+                            .is_some_and(|players| players != (game.x_player, game.o_player));
+                        game.board.encode_u16() != state.board || players_changed
+                    });
+                let result = if !is_player {
+                    Err(NOT_A_PLAYER)
+                } else if is_stale {
+                    Err("This board is from an older game. Use the board of your current game.")
+                } else {
+                    match db
+                        .try_tic_tac_toe_move(
+                            guild_id.into(),
+                            player,
+                            state.index,
+                            interaction.channel_id,
+                        )
+                        .await
+                    {
+                        Ok(response) => Ok(response),
+                        Err(TicTacToeTryMoveError::InvalidTurn) => {
+                            Err("It is not your turn. Please wait for your opponent to finish.")
+                        }
+                        Err(TicTacToeTryMoveError::InvalidMove) => {
+                            Err("That tile is taken. Please choose one of the available tiles.")
+                        }
+                        Err(TicTacToeTryMoveError::NotInAGame) => Err(NOT_A_PLAYER),
+                        Err(TicTacToeTryMoveError::Database(error)) => {
+                            error!("{error:?}");
+                            Err("database error")
+                        }
+                    }
+                };
+
+                let (content, game, finished) = match result {
+                    Ok(TicTacToeTryMoveResponse::Winner {
+                        game,
+                        winner,
+                        loser,
+                    }) => {
+                        let content = format!(
+                            "{} has triumphed over {} in Tic-Tac-Toe",
+                            winner.mention(),
+                            loser.mention(),
+                        );
+                        (content, game, true)
+                    }
+                    Ok(TicTacToeTryMoveResponse::Tie { game }) => {
+                        let content = format!(
+                            "{} has tied with {} in Tic-Tac-Toe",
+                            game.get_player(Team::X).mention(),
+                            game.get_player(Team::O).mention(),
+                        );
+                        (content, game, true)
+                    }
+                    Ok(TicTacToeTryMoveResponse::NextTurn { game }) => {
+                        let content = format!("Your turn {}", game.get_player_turn().mention());
+                        (content, game, false)
+                    }
+                    Err(content) => {
+                        let message_builder = CreateInteractionResponseMessage::new()
+                            .content(content)
+                            .ephemeral(true);
+                        let response = CreateInteractionResponse::Message(message_builder);
+                        interaction.create_response(&ctx.http, response).await?;
+                        return Ok(());
+                    }
+                };
+
+                let theme = match state.theme {
+                    Some(theme) => theme,
+                    None => db.get_tic_tac_toe_theme(guild_id).await?,
+                };
+                let message_builder =
+                    create_board_message(&tic_tac_toe_data, &game, content, finished, theme)
+                        .await?;
+                let response = CreateInteractionResponse::UpdateMessage(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe move component handler")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn move_state_round_trip() {
+        let state = MoveState {
+            index: 4,
+            board: 123,
+            theme: Some(TicTacToeTheme::Dark),
+            players: Some((
+                TicTacToePlayer::User(UserId::new(1)),
+                TicTacToePlayer::Computer,
+            )),
+        };
+        let decoded = MoveState::decode(&state.encode()).expect("failed to decode state");
+        assert_eq!(decoded.index, state.index);
+        assert_eq!(decoded.board, state.board);
+        assert_eq!(decoded.theme, state.theme);
+        assert_eq!(decoded.players, state.players);
+    }
+
+    #[test]
+    fn move_state_decodes_old_buttons() {
+        let decoded = MoveState::decode("4-123").expect("failed to decode state");
+        assert_eq!(decoded.theme, None);
+        assert_eq!(decoded.players, None);
+
+        let decoded = MoveState::decode("4-123-dark").expect("failed to decode state");
+        assert_eq!(decoded.theme, Some(TicTacToeTheme::Dark));
+        assert_eq!(decoded.players, None);
+    }
+}
@@ -1,10 +1,24 @@
+use super::{
+    get_theme,
+    Theme,
+    ThemeArg,
+    TicTacToeData,
+};
 use crate::{
     checks::ENABLED_CHECK,
+    database::{
+        model::TicTacToeTheme,
+        Database,
+    },
     ClientDataKey,
 };
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
 use serenity::{
     builder::{
         CreateAttachment,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
         CreateMessage,
     },
     client::Context,
@@ -17,15 +31,44 @@ use serenity::{
 };
 use tracing::error;
 
+/// Render a board to a file.
+///
+/// Boards are rendered as a png, or as an svg for crisp scaling.
+async fn render_board_file(
+    tic_tac_toe_data: &TicTacToeData,
+    board: tic_tac_toe::Board,
+    theme: Theme,
+    svg: bool,
+) -> anyhow::Result<CreateAttachment> {
+    if svg {
+        let file = tic_tac_toe_data.renderer.render_board_svg(board, &theme)?;
+        Ok(CreateAttachment::bytes(
+            file,
+            format!("ttt-{}.svg", board.encode_u16()),
+        ))
+    } else {
+        let file = tic_tac_toe_data
+            .renderer
+            .render_board_async(board, theme)
+            .await?;
+        Ok(CreateAttachment::bytes(
+            file,
+            format!("ttt-{}.png", board.encode_u16()),
+        ))
+    }
+}
+
 #[command]
 #[description("Print the current Tic-Tac-Toe board")]
-#[usage("")]
+#[usage("[theme] [png OR svg]")]
 #[example("")]
+#[example("dark")]
+#[example("light svg")]
 #[min_args(0)]
-#[max_args(0)]
+#[max_args(2)]
 #[bucket("ttt-board")]
 #[checks(Enabled)]
-pub async fn board(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
+pub async fn board(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let data_lock = ctx.data.read().await;
     let client_data = data_lock
         .get::<ClientDataKey>()
@@ -37,19 +80,42 @@ pub async fn board(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
     let guild_id = msg.guild_id;
     let author_id = msg.author.id;
 
+    let mut theme = None;
+    let mut svg = false;
+    for arg in args.iter::<String>().trimmed() {
+        let arg = arg?;
+        if arg.eq_ignore_ascii_case("svg") {
+            svg = true;
+        } else if arg.eq_ignore_ascii_case("png") {
+            svg = false;
+        } else {
+            match arg.parse::<TicTacToeTheme>() {
+                Ok(arg) => theme = Some(arg),
+                Err(e) => {
+                    let valid = TicTacToeTheme::ALL.map(TicTacToeTheme::as_str).join(", ");
+                    let response = format!(
+                        "Invalid theme or format. Choose one of {valid}, or 'png' or 'svg'. Error: {e}"
+                    );
+                    msg.channel_id.say(&ctx.http, response).await?;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    let theme = match theme {
+        Some(theme) => theme,
+        None => db.get_tic_tac_toe_theme(guild_id).await?,
+    };
+    let theme = Theme::from(theme);
+
     match db
         .get_tic_tac_toe_game(guild_id.into(), author_id.into())
         .await
     {
         Ok(Some(game)) => {
-            let file = match tic_tac_toe_data
-                .renderer
-                .render_board_async(game.board)
-                .await
-            {
-                Ok(file) => {
-                    CreateAttachment::bytes(file, format!("ttt-{}.png", game.board.encode_u16()))
-                }
+            let file = match render_board_file(&tic_tac_toe_data, game.board, theme, svg).await {
+                Ok(file) => file,
                 Err(error) => {
                     error!("Failed to render Tic-Tac-Toe board: {error}");
                     msg.channel_id
@@ -79,3 +145,77 @@ pub async fn board(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
 
     Ok(())
 }
+
+/// An image format
+#[derive(Debug, Clone, Copy, pikadick_slash_framework::FromOptionValue)]
+enum FormatArg {
+    #[pikadick_slash_framework(rename = "png", name = "PNG")]
+    Png,
+
+    #[pikadick_slash_framework(rename = "svg", name = "SVG")]
+    Svg,
+}
+
+/// Options for board
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct BoardOptions {
+    /// The board theme
+    #[pikadick_slash_framework(
+        description = "The board theme. Defaults to the theme of this server"
+    )]
+    theme: Option<ThemeArg>,
+
+    /// The image format
+    #[pikadick_slash_framework(description = "The image format. Defaults to png")]
+    format: Option<FormatArg>,
+}
+
+/// Create a slash subcommand
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("board")
+        .description("Print the current Tic-Tac-Toe board")
+        .arguments(BoardOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             args: BoardOptions,
+             (tic_tac_toe_data, db): (State<TicTacToeData>, State<Database>)| async move {
+                let theme = get_theme(&db, interaction.guild_id, args.theme).await?;
+                let svg = matches!(args.format, Some(FormatArg::Svg));
+                let result = db
+                    .get_tic_tac_toe_game(interaction.guild_id.into(), interaction.user.id.into())
+                    .await;
+                let message_builder = match result {
+                    Ok(Some(game)) => {
+                        match render_board_file(&tic_tac_toe_data, game.board, theme.into(), svg)
+                            .await
+                        {
+                            Ok(file) => CreateInteractionResponseMessage::new().add_file(file),
+                            Err(error) => {
+                                error!("Failed to render Tic-Tac-Toe board: {error}");
+                                CreateInteractionResponseMessage::new()
+                                    .content(format!("Failed to render Tic-Tac-Toe board: {error}"))
+                            }
+                        }
+                    }
+                    Ok(None) => CreateInteractionResponseMessage::new()
+                        .content("Failed to print board as you have no games in this server")
+                        .ephemeral(true),
+                    Err(error) => {
+                        error!("{error:?}");
+                        CreateInteractionResponseMessage::new().content("database error")
+                    }
+                };
+
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe board command")
+}
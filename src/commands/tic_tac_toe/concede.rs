@@ -1,11 +1,28 @@
+use super::{
+    create_board_message,
+    get_theme,
+    Theme,
+    ThemeArg,
+    TicTacToeData,
+};
 use crate::{
     checks::ENABLED_CHECK,
-    database::model::TicTacToePlayer,
+    database::{
+        model::{
+            TicTacToePlayer,
+            TicTacToeTheme,
+        },
+        Database,
+    },
     ClientDataKey,
 };
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
 use serenity::{
     builder::{
         CreateAttachment,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
         CreateMessage,
     },
     client::Context,
@@ -20,13 +37,14 @@ use tracing::error;
 
 #[command]
 #[description("Concede a game of Tic-Tac-Toe")]
-#[usage("")]
+#[usage("[theme]")]
 #[example("")]
+#[example("dark")]
 #[min_args(0)]
-#[max_args(0)]
+#[max_args(1)]
 #[checks(Enabled)]
 #[bucket("default")]
-pub async fn concede(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
+pub async fn concede(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let data_lock = ctx.data.read().await;
     let client_data = data_lock
         .get::<ClientDataKey>()
@@ -38,6 +56,20 @@ pub async fn concede(ctx: &Context, msg: &Message, _args: Args) -> CommandResult
     let guild_id = msg.guild_id;
     let author_id = msg.author.id;
 
+    let theme = if args.is_empty() {
+        db.get_tic_tac_toe_theme(guild_id).await?
+    } else {
+        match args.trimmed().single::<TicTacToeTheme>() {
+            Ok(theme) => theme,
+            Err(e) => {
+                let valid = TicTacToeTheme::ALL.map(TicTacToeTheme::as_str).join(", ");
+                let response = format!("Invalid theme. Choose one of {valid}. Error: {e}");
+                msg.channel_id.say(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+    };
+
     let game = match db
         .concede_tic_tac_toe_game(guild_id.into(), author_id)
         .await
@@ -61,7 +93,7 @@ pub async fn concede(ctx: &Context, msg: &Message, _args: Args) -> CommandResult
 
     let file = match tic_tac_toe_data
         .renderer
-        .render_board_async(game.board)
+        .render_board_async(game.board, Theme::from(theme))
         .await
     {
         Ok(file) => CreateAttachment::bytes(file, format!("ttt-{}.png", game.board.encode_u16())),
@@ -90,3 +122,62 @@ pub async fn concede(ctx: &Context, msg: &Message, _args: Args) -> CommandResult
 
     Ok(())
 }
+
+/// Options for concede
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct ConcedeOptions {
+    /// The board theme
+    #[pikadick_slash_framework(
+        description = "The board theme. Defaults to the theme of this server"
+    )]
+    theme: Option<ThemeArg>,
+}
+
+/// Create a slash subcommand
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("concede")
+        .description("Concede a game of Tic-Tac-Toe")
+        .arguments(ConcedeOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             args: ConcedeOptions,
+             (tic_tac_toe_data, db): (State<TicTacToeData>, State<Database>)| async move {
+                let author_id = interaction.user.id;
+                let theme = get_theme(&db, interaction.guild_id, args.theme).await?;
+                let result = db
+                    .concede_tic_tac_toe_game(interaction.guild_id.into(), author_id)
+                    .await;
+                let message_builder = match result {
+                    Ok(Some(game)) => {
+                        let opponent = game
+                            .get_opponent(TicTacToePlayer::User(author_id))
+                            .expect("author is not playing the game");
+                        let content = format!(
+                            "{} has conceded to {}.",
+                            author_id.mention(),
+                            opponent.mention()
+                        );
+                        create_board_message(&tic_tac_toe_data, &game, content, true, theme).await?
+                    }
+                    Ok(None) => CreateInteractionResponseMessage::new()
+                        .content("Failed to concede as you have no games in this server")
+                        .ephemeral(true),
+                    Err(error) => {
+                        error!("{error:?}");
+                        CreateInteractionResponseMessage::new().content("database error")
+                    }
+                };
+
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe concede command")
+}
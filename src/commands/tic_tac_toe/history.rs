@@ -0,0 +1,79 @@
+use super::describe_history_game;
+use crate::database::Database;
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
+use serenity::{
+    builder::{
+        CreateEmbed,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    model::prelude::*,
+};
+use std::fmt::Write;
+use tracing::error;
+
+/// Options for history
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct HistoryOptions {
+    /// The user
+    #[pikadick_slash_framework(
+        description = "The user to show the games of. Leave empty to show your games"
+    )]
+    user: Option<UserId>,
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("history")
+        .description("List recent games of Tic-Tac-Toe")
+        .arguments(HistoryOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx, interaction, args: HistoryOptions, db: State<Database>| async move {
+                let user_id = args.user.unwrap_or(interaction.user.id);
+                let result = db
+                    .get_tic_tac_toe_history(interaction.guild_id.into(), user_id)
+                    .await;
+                let message_builder = match result {
+                    Ok(games) if games.is_empty() => CreateInteractionResponseMessage::new()
+                        .content(format!(
+                            "{} has no finished games in this server",
+                            user_id.mention()
+                        ))
+                        .ephemeral(true),
+                    Ok(games) => {
+                        let mut description = format!("Recent games of {}\n\n", user_id.mention());
+                        for game in games.iter() {
+                            writeln!(
+                                &mut description,
+                                "`#{}` {} <t:{}:R>",
+                                game.id,
+                                describe_history_game(game),
+                                game.ended_at
+                            )?;
+                        }
+                        description.push_str("\nWatch a game with `/tic-tac-toe replay`.");
+
+                        let embed = CreateEmbed::new()
+                            .title("Tic-Tac-Toe History")
+                            .description(description);
+                        CreateInteractionResponseMessage::new().embed(embed)
+                    }
+                    Err(error) => {
+                        error!("{error:?}");
+                        CreateInteractionResponseMessage::new().content("database error")
+                    }
+                };
+
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe history command")
+}
@@ -1,14 +1,26 @@
+use super::{
+    create_board_message,
+    get_theme,
+    Theme,
+    ThemeArg,
+    TicTacToeData,
+};
 use crate::{
     checks::ENABLED_CHECK,
     database::{
         model::TicTacToePlayer,
+        Database,
         TicTacToeCreateGameError,
     },
     ClientDataKey,
 };
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
 use serenity::{
     builder::{
         CreateAttachment,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
         CreateMessage,
     },
     client::Context,
@@ -21,12 +33,83 @@ use serenity::{
 };
 use tracing::error;
 
+/// A Tic-Tac-Toe team
+#[derive(Debug, Clone, Copy, pikadick_slash_framework::FromOptionValue)]
+enum TeamArg {
+    #[pikadick_slash_framework(rename = "x", name = "X")]
+    X,
+
+    #[pikadick_slash_framework(rename = "o", name = "O")]
+    O,
+}
+
+impl From<TeamArg> for tic_tac_toe::Team {
+    fn from(team: TeamArg) -> Self {
+        match team {
+            TeamArg::X => Self::X,
+            TeamArg::O => Self::O,
+        }
+    }
+}
+
+/// A computer difficulty
+#[derive(Debug, Clone, Copy, pikadick_slash_framework::FromOptionValue)]
+enum DifficultyArg {
+    #[pikadick_slash_framework(rename = "easy", name = "Easy")]
+    Easy,
+
+    #[pikadick_slash_framework(rename = "medium", name = "Medium")]
+    Medium,
+
+    #[pikadick_slash_framework(rename = "hard", name = "Hard")]
+    Hard,
+}
+
+impl From<DifficultyArg> for tic_tac_toe::Difficulty {
+    fn from(difficulty: DifficultyArg) -> Self {
+        match difficulty {
+            DifficultyArg::Easy => Self::Easy,
+            DifficultyArg::Medium => Self::Medium,
+            DifficultyArg::Hard => Self::Hard,
+        }
+    }
+}
+
+/// Options for play
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct PlayOptions {
+    /// Your team
+    #[pikadick_slash_framework(description = "Your team. X goes first")]
+    team: TeamArg,
+
+    /// The opponent
+    #[pikadick_slash_framework(
+        description = "The user to play against. Leave empty to play against the computer"
+    )]
+    opponent: Option<UserId>,
+
+    /// The computer difficulty
+    #[pikadick_slash_framework(
+        description = "The computer difficulty. Only used against the computer, defaults to hard"
+    )]
+    difficulty: Option<DifficultyArg>,
+
+    /// The board theme
+    #[pikadick_slash_framework(
+        description = "The board theme. Defaults to the theme of this server"
+    )]
+    theme: Option<ThemeArg>,
+}
+
 #[command]
-#[description("Start a game of Tic-Tac-Toe")]
-#[usage("<computer OR @user, X OR O>")]
+#[description(
+    "Start a game of Tic-Tac-Toe. Boards use the server theme, use `board` to pick another"
+)]
+#[usage("<computer OR @user, X OR O, [easy OR medium OR hard, computer only]>")]
 #[example("computer X")]
+#[example("computer O easy")]
 #[min_args(2)]
-#[max_args(2)]
+#[max_args(3)]
 #[checks(Enabled)]
 #[bucket("default")]
 pub async fn play(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
@@ -59,11 +142,38 @@ pub async fn play(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
         }
     };
 
+    let difficulty: tic_tac_toe::Difficulty = if args.is_empty() {
+        tic_tac_toe::Difficulty::default()
+    } else if opponent.is_user() {
+        let response = "A difficulty can only be chosen when playing the computer.";
+        msg.channel_id.say(&ctx.http, response).await?;
+        return Ok(());
+    } else {
+        match args.trimmed().single() {
+            Ok(difficulty) => difficulty,
+            Err(e) => {
+                let response = format!(
+                    "Invalid difficulty. Choose 'easy', 'medium' or 'hard'. Error: {}",
+                    e
+                );
+                msg.channel_id.say(&ctx.http, response).await?;
+                return Ok(());
+            }
+        }
+    };
+
     let author_id = msg.author.id;
     let guild_id = msg.guild_id;
 
     let game = match db
-        .create_tic_tac_toe_game(guild_id.into(), author_id.into(), author_team, opponent)
+        .create_tic_tac_toe_game(
+            guild_id.into(),
+            author_id.into(),
+            author_team,
+            opponent,
+            difficulty,
+            msg.channel_id,
+        )
         .await
     {
         Ok(game) => game,
@@ -99,9 +209,10 @@ pub async fn play(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
         author_id
     };
 
+    let theme = Theme::from(db.get_tic_tac_toe_theme(guild_id).await?);
     let file = match tic_tac_toe_data
         .renderer
-        .render_board_async(game_board)
+        .render_board_async(game_board, theme)
         .await
     {
         Ok(file) => CreateAttachment::bytes(file, format!("{}.png", game_board.encode_u16())),
@@ -126,3 +237,74 @@ pub async fn play(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
     Ok(())
 }
+
+/// Create a slash subcommand
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("play")
+        .description("Start a game of Tic-Tac-Toe")
+        .arguments(PlayOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             args: PlayOptions,
+             (tic_tac_toe_data, db): (State<TicTacToeData>, State<Database>)| async move {
+                let author = TicTacToePlayer::from(interaction.user.id);
+                let author_team = tic_tac_toe::Team::from(args.team);
+                let opponent = args
+                    .opponent
+                    .map_or(TicTacToePlayer::Computer, TicTacToePlayer::from);
+                let difficulty = args
+                    .difficulty
+                    .map(tic_tac_toe::Difficulty::from)
+                    .unwrap_or_default();
+
+                let theme = get_theme(&db, interaction.guild_id, args.theme).await?;
+                let result = db
+                    .create_tic_tac_toe_game(
+                        interaction.guild_id.into(),
+                        author,
+                        author_team,
+                        opponent,
+                        difficulty,
+                        interaction.channel_id,
+                    )
+                    .await;
+                let message_builder = match result {
+                    Ok(game) => {
+                        // The computer already moved if it is X,
+                        // so this is always a user.
+                        let player = game.get_player_turn();
+                        let content = format!("Game created! Your turn {}", player.mention());
+                        create_board_message(&tic_tac_toe_data, &game, content, false, theme)
+                            .await?
+                    }
+                    Err(TicTacToeCreateGameError::AuthorInGame) => {
+                        let content = "Finish your current game in this server before starting a new one. Use `/tic-tac-toe concede` to end your current game.";
+                        CreateInteractionResponseMessage::new()
+                            .content(content)
+                            .ephemeral(true)
+                    }
+                    Err(TicTacToeCreateGameError::OpponentInGame) => {
+                        let content = "Your opponent is currently in another game in this server. Wait for them to finish.";
+                        CreateInteractionResponseMessage::new()
+                            .content(content)
+                            .ephemeral(true)
+                    }
+                    Err(TicTacToeCreateGameError::Database(error)) => {
+                        error!("{error:?}");
+                        CreateInteractionResponseMessage::new().content("database error")
+                    }
+                };
+
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe play command")
+}
@@ -0,0 +1,313 @@
+use crate::{
+    checks::ENABLED_CHECK,
+    database::{
+        model::{
+            MaybeGuildString,
+            TicTacToeProfile,
+        },
+        Database,
+    },
+    util::AsciiTable,
+    ClientDataKey,
+};
+use anyhow::Context as _;
+use futures::future::try_join_all;
+use pikadick_slash_framework::State;
+use serenity::{
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::prelude::*,
+    prelude::*,
+    utils::parse_user_mention,
+};
+use tracing::error;
+
+/// The max # of characters in a message
+const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Options for profile
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct ProfileOptions {
+    /// The user
+    #[pikadick_slash_framework(
+        description = "The user to show the profile of. Leave empty to show your profile"
+    )]
+    user: Option<UserId>,
+}
+
+#[command]
+#[description("Get the Tic-Tac-Toe stats of a user across every server")]
+#[usage("[@user]")]
+#[example("")]
+#[example("@user")]
+#[min_args(0)]
+#[max_args(1)]
+#[checks(Enabled)]
+#[bucket("default")]
+pub async fn profile(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let user_id = match args.trimmed().current() {
+        None => msg.author.id,
+        Some(arg) => match parse_user_mention(arg) {
+            Some(user_id) => user_id,
+            None => {
+                let response = format!("Invalid user '{arg}'. Mention a user with '@user'.");
+                msg.channel_id.say(&ctx.http, response).await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let content = match get_profile_content(ctx, &db, user_id, msg.guild_id).await {
+        Ok(content) => content,
+        Err(error) => {
+            error!("{error:?}");
+            format!("{error:?}")
+        }
+    };
+    msg.channel_id.say(&ctx.http, content).await?;
+
+    Ok(())
+}
+
+/// Get the profile of a user, formatted as tables.
+///
+/// `guild_id` is the server the profile is shown in.
+async fn get_profile_content(
+    ctx: &Context,
+    db: &Database,
+    user_id: UserId,
+    guild_id: Option<GuildId>,
+) -> anyhow::Result<String> {
+    let profile = db
+        .get_tic_tac_toe_profile(user_id)
+        .await
+        .context("failed to get tic-tac-toe profile")?;
+    let name = user_id
+        .to_user(&ctx)
+        .await
+        .context("failed to get user name")?
+        .name;
+    let opponent_names = try_join_all(
+        profile
+            .opponents
+            .iter()
+            .map(|score| score.opponent.to_user(&ctx)),
+    )
+    .await
+    .context("failed to get opponent names")?
+    .into_iter()
+    .map(|user| user.name)
+    .collect::<Vec<_>>();
+
+    format_profile(&name, &profile, &opponent_names, guild_id)
+}
+
+/// Format the profile of a user as tables.
+///
+/// Only the total of every server and the scores of the current server are shown,
+/// so other servers the user is in are not revealed.
+/// Head to head rows are dropped until the profile fits in a message.
+fn format_profile(
+    name: &str,
+    profile: &TicTacToeProfile,
+    opponent_names: &[String],
+    guild_id: Option<GuildId>,
+) -> anyhow::Result<String> {
+    let global_rating = match profile.get_global_rating() {
+        Some(rating) => rating,
+        None => return Ok(format!("{name} has not finished any rated games")),
+    };
+
+    let guild_score = profile
+        .guilds
+        .iter()
+        .find(|score| score.guild_id == MaybeGuildString::from(guild_id));
+
+    let mut guild_table = AsciiTable::new(6, if guild_score.is_some() { 3 } else { 2 });
+    guild_table.set_padding(2);
+
+    guild_table.set_cell(0, 0, "Server");
+    guild_table.set_cell(1, 0, "Rating");
+    guild_table.set_cell(2, 0, "Wins");
+    guild_table.set_cell(3, 0, "Losses");
+    guild_table.set_cell(4, 0, "Ties");
+    guild_table.set_cell(5, 0, "Concedes");
+
+    let mut total_wins = 0;
+    let mut total_losses = 0;
+    let mut total_ties = 0;
+    let mut total_concedes = 0;
+    for score in profile.guilds.iter() {
+        total_wins += score.wins;
+        total_losses += score.losses;
+        total_ties += score.ties;
+        total_concedes += score.concedes;
+    }
+
+    guild_table.set_cell(0, 1, "All Servers");
+    guild_table.set_cell(1, 1, format!("{global_rating:.0}"));
+    guild_table.set_cell(2, 1, total_wins.to_string());
+    guild_table.set_cell(3, 1, total_losses.to_string());
+    guild_table.set_cell(4, 1, total_ties.to_string());
+    guild_table.set_cell(5, 1, total_concedes.to_string());
+
+    if let Some(score) = guild_score {
+        let guild_name = if guild_id.is_some() {
+            "This Server"
+        } else {
+            "Direct Messages"
+        };
+
+        guild_table.set_cell(0, 2, guild_name);
+        guild_table.set_cell(1, 2, format!("{:.0}", score.rating));
+        guild_table.set_cell(2, 2, score.wins.to_string());
+        guild_table.set_cell(3, 2, score.losses.to_string());
+        guild_table.set_cell(4, 2, score.ties.to_string());
+        guild_table.set_cell(5, 2, score.concedes.to_string());
+    }
+
+    let mut content = format!(
+        "```\n{name}'s Tic-Tac-Toe Profile\nLongest win streak: {}\n\n{guild_table}\n",
+        profile.longest_win_streak
+    );
+
+    let opponents = profile.opponents.iter().zip(opponent_names);
+    for num_opponents in (1..=profile.opponents.len()).rev() {
+        let mut opponent_table = AsciiTable::new(5, num_opponents + 1);
+        opponent_table.set_padding(2);
+
+        opponent_table.set_cell(0, 0, "Opponent");
+        opponent_table.set_cell(1, 0, "Wins");
+        opponent_table.set_cell(2, 0, "Losses");
+        opponent_table.set_cell(3, 0, "Ties");
+        opponent_table.set_cell(4, 0, "Concedes");
+
+        for (i, (score, opponent_name)) in opponents.clone().take(num_opponents).enumerate() {
+            opponent_table.set_cell(0, i + 1, opponent_name);
+            opponent_table.set_cell(1, i + 1, score.wins.to_string());
+            opponent_table.set_cell(2, i + 1, score.losses.to_string());
+            opponent_table.set_cell(3, i + 1, score.ties.to_string());
+            opponent_table.set_cell(4, i + 1, score.concedes.to_string());
+        }
+
+        let section = format!("\nHead to Head\n{opponent_table}\n");
+        if content.len() + section.len() + "```".len() <= MAX_MESSAGE_LEN {
+            content.push_str(&section);
+            break;
+        }
+    }
+
+    content.push_str("```");
+
+    Ok(content)
+}
+
+/// Create a slash subcommand
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("profile")
+        .description("Show the Tic-Tac-Toe stats of a user across every server")
+        .arguments(ProfileOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx, interaction, args: ProfileOptions, db: State<Database>| async move {
+                let user_id = args.user.unwrap_or(interaction.user.id);
+                let mut response = pikadick_slash_framework::Response::new(&ctx, &interaction);
+                let result = response
+                    .defer_if_slow(get_profile_content(
+                        &ctx,
+                        &db,
+                        user_id,
+                        interaction.guild_id,
+                    ))
+                    .await?;
+                let content = match result {
+                    Ok(content) => content,
+                    Err(error) => {
+                        error!("{error:?}");
+                        format!("{error:?}")
+                    }
+                };
+
+                response.send_content(content).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe profile command")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::database::model::{
+        TicTacToeGuildScore,
+        TicTacToeHeadToHeadScore,
+    };
+
+    fn make_profile(num_opponents: u64) -> TicTacToeProfile {
+        let guild_score = |guild_id| TicTacToeGuildScore {
+            guild_id: MaybeGuildString::from(Some(GuildId::new(guild_id))),
+            wins: 1,
+            losses: 2,
+            ties: 3,
+            concedes: 0,
+            rating: 1500.0,
+            rated_games: 6,
+        };
+
+        TicTacToeProfile {
+            guilds: vec![guild_score(1), guild_score(2)],
+            longest_win_streak: 1,
+            opponents: (1..=num_opponents)
+                .map(|opponent| TicTacToeHeadToHeadScore {
+                    opponent: UserId::new(opponent),
+                    wins: 1,
+                    losses: 0,
+                    ties: 0,
+                    concedes: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn format_profile_only_shows_current_server() {
+        let profile = make_profile(1);
+        let names = ["opponent".to_string()];
+        let content = format_profile("user", &profile, &names, Some(GuildId::new(2)))
+            .expect("failed to format profile");
+        assert!(content.contains("All Servers"));
+        assert!(content.contains("This Server"));
+        assert!(content.contains("opponent"));
+        assert!(!content.contains("Unknown Server"));
+
+        let content = format_profile("user", &profile, &names, Some(GuildId::new(3)))
+            .expect("failed to format profile");
+        assert!(!content.contains("This Server"));
+    }
+
+    #[test]
+    fn format_profile_fits_in_a_message() {
+        let profile = make_profile(10);
+        let names: Vec<_> = (0..10).map(|i| format!("{i}").repeat(100)).collect();
+        let content =
+            format_profile("user", &profile, &names, None).expect("failed to format profile");
+        assert!(content.len() <= MAX_MESSAGE_LEN);
+        assert!(content.contains(&names[0]));
+        assert!(!content.contains(&names[9]));
+        assert!(content.ends_with("```"));
+    }
+}
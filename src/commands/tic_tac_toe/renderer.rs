@@ -1,13 +1,17 @@
+use super::theme::Theme;
 use anyhow::Context;
 use once_cell::sync::Lazy;
 use std::{
+    fmt::Write as _,
     sync::Arc,
     time::Instant,
 };
 use tiny_skia::{
+    Color,
     Paint,
     Path,
     PathBuilder,
+    PathSegment,
     Pixmap,
     Rect,
     Stroke,
@@ -19,21 +23,19 @@ use ttf_parser::OutlineBuilder;
 
 const FONT_BYTES: &[u8] =
     include_bytes!("../../../assets/Averia_Serif_Libre/AveriaSerifLibre-Light.ttf");
-static FONT_FACE: Lazy<ttf_parser::Face<'static>> =
+pub(crate) static FONT_FACE: Lazy<ttf_parser::Face<'static>> =
     Lazy::new(|| ttf_parser::Face::parse(FONT_BYTES, 0).expect("failed to load `FONT_BYTES`"));
 
-const RENDERED_SIZE: u16 = 300;
-const SQUARE_SIZE: u16 = RENDERED_SIZE / 3;
-const SQUARE_SIZE_USIZE: usize = SQUARE_SIZE as usize;
-const SQUARE_SIZE_F32: f32 = SQUARE_SIZE as f32;
-const HALF_SQUARE_SIZE_F32: f32 = SQUARE_SIZE_F32 / 2.0;
-
 const MAX_PARALLEL_RENDER_LIMIT: usize = 4;
 
+/// The time each move of a replay is shown for, in milliseconds
+const REPLAY_FRAME_DELAY_MS: u16 = 1000;
+/// The time the final board of a replay is shown for, in milliseconds
+const REPLAY_FINAL_FRAME_DELAY_MS: u16 = 3000;
+
 /// Render a Tic-Tac-Toe board
 #[derive(Debug, Clone)]
 pub(crate) struct Renderer {
-    background_pixmap: Arc<Pixmap>,
     number_paths: Arc<[Path]>,
 
     render_semaphore: Arc<Semaphore>,
@@ -43,31 +45,7 @@ pub(crate) struct Renderer {
 impl Renderer {
     /// Make a new [`Renderer`].
     pub(crate) fn new() -> anyhow::Result<Self> {
-        let mut background_pixmap = Pixmap::new(RENDERED_SIZE.into(), RENDERED_SIZE.into())
-            .context("failed to create background pixmap")?;
-
-        let mut paint = Paint::default();
-        for i in 0..3 {
-            for j in 0..3 {
-                let x = i * SQUARE_SIZE;
-                let y = j * SQUARE_SIZE;
-                let square =
-                    Rect::from_xywh(f32::from(x), f32::from(y), SQUARE_SIZE_F32, SQUARE_SIZE_F32)
-                        .context("failed to make square")?;
-
-                if (j * 3 + i) % 2 == 0 {
-                    paint.set_color_rgba8(255, 0, 0, 255);
-                } else {
-                    paint.set_color_rgba8(119, 119, 119, 255);
-                }
-
-                background_pixmap.fill_rect(square, &paint, Transform::identity(), None);
-            }
-        }
-
         let mut number_paths = Vec::with_capacity(10);
-        let mut paint = Paint::default();
-        paint.set_color_rgba8(255, 255, 255, 255);
         for i in b'0'..=b'9' {
             let glyph_id = FONT_FACE
                 .glyph_index(char::from(i))
@@ -85,83 +63,103 @@ impl Renderer {
         }
 
         Ok(Self {
-            background_pixmap: Arc::new(background_pixmap),
             number_paths: Arc::from(number_paths),
             render_semaphore: Arc::new(Semaphore::new(MAX_PARALLEL_RENDER_LIMIT)),
         })
     }
 
-    /// Render a Tic-Tac-Toe board with `tiny_skia`.
-    // Author might add more fields
-    #[allow(clippy::field_reassign_with_default)]
-    pub(crate) fn render_board(&self, board: tic_tac_toe::Board) -> anyhow::Result<Vec<u8>> {
-        const PIECE_WIDTH: u16 = 4;
+    /// Get the number path of an empty tile, and the transform that centers it in the tile.
+    fn get_number_path(&self, index: u8, square_size: f32) -> (&Path, Transform) {
+        let path = &self.number_paths[usize::from(index) + 1];
+        let bounds = path.bounds();
 
-        let draw_start = Instant::now();
-        let mut pixmap = self.background_pixmap.as_ref().as_ref().to_owned();
+        let ratio = (square_size / 2.0) / bounds.height().max(bounds.width());
+        let transform = get_tile_transform(index, square_size)
+            .pre_scale(ratio, ratio)
+            .post_translate(
+                (square_size / 2.0) - (ratio * bounds.width() / 2.0),
+                (square_size / 2.0) - (ratio * bounds.height() / 2.0),
+            );
+
+        (path, transform)
+    }
+
+    /// Draw a Tic-Tac-Toe board with `tiny_skia`.
+    fn draw_board(&self, board: tic_tac_toe::Board, theme: &Theme) -> anyhow::Result<Pixmap> {
+        let square_size = theme.square_size();
+        let mut pixmap = Pixmap::new(theme.size.into(), theme.size.into())
+            .context("failed to create board pixmap")?;
 
         let mut paint = Paint::default();
-        let mut stroke = Stroke::default();
+        for i in 0..tic_tac_toe::NUM_TILES {
+            let square = Rect::from_xywh(0.0, 0.0, square_size, square_size)
+                .context("failed to make square")?;
+            paint.set_color(theme.squares[usize::from(i % 2)]);
+            pixmap.fill_rect(square, &paint, get_tile_transform(i, square_size), None);
+        }
+
         paint.anti_alias = true;
-        stroke.width = f32::from(PIECE_WIDTH);
 
-        for (i, team) in board.iter() {
-            let transform = Transform::from_translate(
-                f32::from((u16::from(i) % 3) * SQUARE_SIZE),
-                f32::from((u16::from(i) / 3) * SQUARE_SIZE),
-            );
+        if let Some(color) = theme.grid {
+            let path = get_grid_path(square_size).context("failed to build grid path")?;
+            let stroke = Stroke {
+                width: get_grid_width(square_size),
+                ..Default::default()
+            };
+            paint.set_color(color);
+            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        }
 
-            if let Some(team) = team {
-                paint.set_color_rgba8(0, 0, 0, 255);
-                let path = match team {
-                    tic_tac_toe::Team::X => {
-                        let mut path_builder = PathBuilder::new();
-                        path_builder.move_to((PIECE_WIDTH / 2).into(), (PIECE_WIDTH / 2).into());
-                        path_builder.line_to(
-                            SQUARE_SIZE_F32 - f32::from(PIECE_WIDTH / 2),
-                            SQUARE_SIZE_F32 - f32::from(PIECE_WIDTH / 2),
-                        );
-                        path_builder.move_to(
-                            (PIECE_WIDTH / 2).into(),
-                            SQUARE_SIZE_F32 - f32::from(PIECE_WIDTH / 2),
-                        );
-                        path_builder.line_to(
-                            SQUARE_SIZE_F32 - f32::from(PIECE_WIDTH / 2),
-                            f32::from(PIECE_WIDTH / 2),
-                        );
-                        path_builder.finish()
+        let stroke = Stroke {
+            width: get_piece_width(square_size),
+            ..Default::default()
+        };
+        for (i, team) in board.iter() {
+            match team {
+                Some(team) => {
+                    let path = get_piece_path(team, square_size)
+                        .with_context(|| format!("failed to build path for team '{:?}'", team))?;
+                    paint.set_color(get_team_color(theme, team));
+                    pixmap.stroke_path(
+                        &path,
+                        &paint,
+                        &stroke,
+                        get_tile_transform(i, square_size),
+                        None,
+                    );
+                }
+                None => {
+                    if let Some(color) = theme.numbers {
+                        let (path, transform) = self.get_number_path(i, square_size);
+                        paint.set_color(color);
+                        pixmap.fill_path(path, &paint, Default::default(), transform, None);
                     }
-                    tic_tac_toe::Team::O => PathBuilder::from_circle(
-                        HALF_SQUARE_SIZE_F32,
-                        HALF_SQUARE_SIZE_F32,
-                        HALF_SQUARE_SIZE_F32 - f32::from(PIECE_WIDTH / 2),
-                    ),
-                };
-                let path =
-                    path.with_context(|| format!("failed to build path for team '{:?}'", team))?;
-
-                pixmap.stroke_path(&path, &paint, &stroke, transform, None);
-            } else {
-                paint.set_color_rgba8(255, 255, 255, 255);
-                let path = &self.number_paths[usize::from(i) + 1];
-                let bounds = path.bounds();
-
-                let ratio = f32::from(SQUARE_SIZE / 2) / bounds.height().max(bounds.width());
-                let transform = transform.pre_scale(ratio, ratio).post_translate(
-                    (SQUARE_SIZE_F32 / 2.0) - (ratio * bounds.width() / 2.0),
-                    (SQUARE_SIZE_F32 / 2.0) - (ratio * bounds.height() / 2.0),
-                );
-
-                pixmap.fill_path(path, &paint, Default::default(), transform, None);
+                }
             }
         }
 
-        // Draw winning line
         if let Some(winner_info) = board.get_winner_info() {
-            draw_winning_line(&mut pixmap, stroke, paint, winner_info)
+            let path = get_winning_line_path(winner_info, square_size)
                 .context("failed to draw winning line")?;
+            let stroke = Stroke {
+                width: get_winning_line_width(square_size),
+                ..Default::default()
+            };
+            paint.set_color(theme.winning_line);
+            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
         }
 
+        Ok(pixmap)
+    }
+
+    /// Render a Tic-Tac-Toe board as a png.
+    pub(crate) fn render_board(
+        &self,
+        board: tic_tac_toe::Board,
+        theme: &Theme,
+    ) -> anyhow::Result<Vec<u8>> {
+        let draw_start = Instant::now();
+        let pixmap = self.draw_board(board, theme)?;
         let draw_end = Instant::now();
         info!("board draw time: {:?}", draw_end - draw_start);
 
@@ -178,67 +176,307 @@ impl Renderer {
     pub(crate) async fn render_board_async(
         &self,
         board: tic_tac_toe::Board,
+        theme: Theme,
     ) -> anyhow::Result<Vec<u8>> {
         // TODO: LRU cache
         let _permit = self.render_semaphore.acquire().await?;
         let self_clone = self.clone();
-        tokio::task::spawn_blocking(move || self_clone.render_board(board)).await?
+        tokio::task::spawn_blocking(move || self_clone.render_board(board, &theme)).await?
+    }
+
+    /// Render a Tic-Tac-Toe board as an svg.
+    ///
+    /// This draws the same shapes as [`Self::render_board`], so both look the same.
+    pub(crate) fn render_board_svg(
+        &self,
+        board: tic_tac_toe::Board,
+        theme: &Theme,
+    ) -> anyhow::Result<String> {
+        let square_size = theme.square_size();
+        let mut svg = String::new();
+
+        write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#,
+            size = theme.size
+        )?;
+
+        for i in 0..tic_tac_toe::NUM_TILES {
+            let transform = get_tile_transform(i, square_size);
+            write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{square_size}" height="{square_size}" fill="{}"/>"#,
+                transform.tx,
+                transform.ty,
+                get_svg_color(theme.squares[usize::from(i % 2)]),
+            )?;
+        }
+
+        if let Some(color) = theme.grid {
+            let path = get_grid_path(square_size).context("failed to build grid path")?;
+            write!(
+                svg,
+                r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                get_svg_path_data(&path),
+                get_svg_color(color),
+                get_grid_width(square_size),
+            )?;
+        }
+
+        for (i, team) in board.iter() {
+            match team {
+                Some(team) => {
+                    let path = get_piece_path(team, square_size)
+                        .with_context(|| format!("failed to build path for team '{:?}'", team))?;
+                    write!(
+                        svg,
+                        r#"<path d="{}" transform="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                        get_svg_path_data(&path),
+                        get_svg_transform(get_tile_transform(i, square_size)),
+                        get_svg_color(get_team_color(theme, team)),
+                        get_piece_width(square_size),
+                    )?;
+                }
+                None => {
+                    if let Some(color) = theme.numbers {
+                        let (path, transform) = self.get_number_path(i, square_size);
+                        write!(
+                            svg,
+                            r#"<path d="{}" transform="{}" fill="{}"/>"#,
+                            get_svg_path_data(path),
+                            get_svg_transform(transform),
+                            get_svg_color(color),
+                        )?;
+                    }
+                }
+            }
+        }
+
+        if let Some(winner_info) = board.get_winner_info() {
+            let path = get_winning_line_path(winner_info, square_size)
+                .context("failed to draw winning line")?;
+            write!(
+                svg,
+                r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                get_svg_path_data(&path),
+                get_svg_color(theme.winning_line),
+                get_winning_line_width(square_size),
+            )?;
+        }
+
+        svg.push_str("</svg>");
+
+        Ok(svg)
+    }
+
+    /// Render a replay of Tic-Tac-Toe boards as an animated png.
+    ///
+    /// Each board is a frame, and the last frame is shown for longer.
+    pub(crate) fn render_replay(
+        &self,
+        boards: &[tic_tac_toe::Board],
+        theme: &Theme,
+    ) -> anyhow::Result<Vec<u8>> {
+        let num_frames = u32::try_from(boards.len()).context("too many frames")?;
+
+        let draw_start = Instant::now();
+        let frames = boards
+            .iter()
+            .map(|board| self.draw_board(*board, theme))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let draw_end = Instant::now();
+        info!("replay draw time: {:?}", draw_end - draw_start);
+
+        let encode_start = Instant::now();
+        let mut img = Vec::new();
+        let mut encoder = png::Encoder::new(&mut img, theme.size.into(), theme.size.into());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .set_animated(num_frames, 0)
+            .context("failed to make replay animated")?;
+        let mut writer = encoder
+            .write_header()
+            .context("failed to write replay header")?;
+        for (i, frame) in frames.iter().enumerate() {
+            let delay = if i + 1 == frames.len() {
+                REPLAY_FINAL_FRAME_DELAY_MS
+            } else {
+                REPLAY_FRAME_DELAY_MS
+            };
+            writer
+                .set_frame_delay(delay, 1000)
+                .context("failed to set frame delay")?;
+
+            // Boards are fully opaque, so premultiplied and straight alpha are the same
+            writer
+                .write_image_data(frame.data())
+                .context("failed to write frame")?;
+        }
+        writer.finish().context("failed to finish replay")?;
+        let encode_end = Instant::now();
+        info!("replay png encode time: {:?}", encode_end - encode_start);
+
+        Ok(img)
+    }
+
+    /// Render a replay of Tic-Tac-Toe boards on a threadpool
+    pub(crate) async fn render_replay_async(
+        &self,
+        boards: Vec<tic_tac_toe::Board>,
+        theme: Theme,
+    ) -> anyhow::Result<Vec<u8>> {
+        let _permit = self.render_semaphore.acquire().await?;
+        let self_clone = self.clone();
+        tokio::task::spawn_blocking(move || self_clone.render_replay(&boards, &theme)).await?
+    }
+}
+
+/// Get the transform that moves the top left of a square to the top left of a tile
+fn get_tile_transform(index: u8, square_size: f32) -> Transform {
+    Transform::from_translate(
+        f32::from(index % 3) * square_size,
+        f32::from(index / 3) * square_size,
+    )
+}
+
+/// Get the width of piece strokes
+fn get_piece_width(square_size: f32) -> f32 {
+    square_size / 25.0
+}
+
+/// Get the width of grid lines
+fn get_grid_width(square_size: f32) -> f32 {
+    square_size / 50.0
+}
+
+/// Get the width of the winning line
+fn get_winning_line_width(square_size: f32) -> f32 {
+    square_size / 10.0
+}
+
+/// Get the color of a team's pieces
+fn get_team_color(theme: &Theme, team: tic_tac_toe::Team) -> Color {
+    match team {
+        tic_tac_toe::Team::X => theme.x,
+        tic_tac_toe::Team::O => theme.o,
+    }
+}
+
+/// Get the lines between squares
+fn get_grid_path(square_size: f32) -> Option<Path> {
+    let size = square_size * 3.0;
+
+    let mut path_builder = PathBuilder::new();
+    for i in 1..3_u8 {
+        let offset = f32::from(i) * square_size;
+        path_builder.move_to(offset, 0.0);
+        path_builder.line_to(offset, size);
+        path_builder.move_to(0.0, offset);
+        path_builder.line_to(size, offset);
     }
+    path_builder.finish()
 }
 
-/// Draw the winning line
-fn draw_winning_line(
-    pixmap: &mut Pixmap,
-    mut stroke: Stroke,
-    mut paint: Paint<'_>,
-    winner_info: tic_tac_toe::WinnerInfo,
-) -> anyhow::Result<()> {
-    stroke.width = 10.0;
-    paint.set_color_rgba8(48, 48, 48, 255);
-
-    let start_index = winner_info.start_tile_index();
-    let start = usize::from(start_index);
-    let mut start_x = ((start % 3) * SQUARE_SIZE_USIZE + (SQUARE_SIZE_USIZE / 2)) as f32;
-    let mut start_y = ((start / 3) * SQUARE_SIZE_USIZE + (SQUARE_SIZE_USIZE / 2)) as f32;
-
-    let end_index = winner_info.end_tile_index();
-    let end = usize::from(end_index);
-    let mut end_x = ((end % 3) * SQUARE_SIZE_USIZE + (SQUARE_SIZE_USIZE / 2)) as f32;
-    let mut end_y = ((end / 3) * SQUARE_SIZE_USIZE + (SQUARE_SIZE_USIZE / 2)) as f32;
+/// Get the path of a piece, in the coordinates of its square
+fn get_piece_path(team: tic_tac_toe::Team, square_size: f32) -> Option<Path> {
+    let half_width = get_piece_width(square_size) / 2.0;
+    match team {
+        tic_tac_toe::Team::X => {
+            let mut path_builder = PathBuilder::new();
+            path_builder.move_to(half_width, half_width);
+            path_builder.line_to(square_size - half_width, square_size - half_width);
+            path_builder.move_to(half_width, square_size - half_width);
+            path_builder.line_to(square_size - half_width, half_width);
+            path_builder.finish()
+        }
+        tic_tac_toe::Team::O => PathBuilder::from_circle(
+            square_size / 2.0,
+            square_size / 2.0,
+            (square_size / 2.0) - half_width,
+        ),
+    }
+}
 
+/// Get the winning line
+fn get_winning_line_path(winner_info: tic_tac_toe::WinnerInfo, square_size: f32) -> Option<Path> {
+    let get_tile_center = |index: u8| {
+        let transform = get_tile_transform(index, square_size);
+        (
+            transform.tx + (square_size / 2.0),
+            transform.ty + (square_size / 2.0),
+        )
+    };
+    let (mut start_x, mut start_y) = get_tile_center(winner_info.start_tile_index());
+    let (mut end_x, mut end_y) = get_tile_center(winner_info.end_tile_index());
+
+    let extension = square_size / 4.0;
     match winner_info.win_type {
         tic_tac_toe::WinType::Horizontal => {
-            start_x -= SQUARE_SIZE_F32 / 4.0;
-            end_x += SQUARE_SIZE_F32 / 4.0;
+            start_x -= extension;
+            end_x += extension;
         }
         tic_tac_toe::WinType::Vertical => {
-            start_y -= SQUARE_SIZE_F32 / 4.0;
-            end_y += SQUARE_SIZE_F32 / 4.0;
+            start_y -= extension;
+            end_y += extension;
         }
         tic_tac_toe::WinType::Diagonal => {
-            start_x -= SQUARE_SIZE_F32 / 4.0;
-            start_y -= SQUARE_SIZE_F32 / 4.0;
-            end_x += SQUARE_SIZE_F32 / 4.0;
-            end_y += SQUARE_SIZE_F32 / 4.0;
+            start_x -= extension;
+            start_y -= extension;
+            end_x += extension;
+            end_y += extension;
         }
         tic_tac_toe::WinType::AntiDiagonal => {
-            start_x += SQUARE_SIZE_F32 / 4.0;
-            start_y -= SQUARE_SIZE_F32 / 4.0;
-            end_x -= SQUARE_SIZE_F32 / 4.0;
-            end_y += SQUARE_SIZE_F32 / 4.0;
+            start_x += extension;
+            start_y -= extension;
+            end_x -= extension;
+            end_y += extension;
         }
     }
 
     let mut path_builder = PathBuilder::new();
     path_builder.move_to(start_x, start_y);
     path_builder.line_to(end_x, end_y);
-    let path = path_builder
-        .finish()
-        .context("failed to draw winning line")?;
+    path_builder.finish()
+}
 
-    pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+/// Format a color as an svg color.
+///
+/// Alpha is ignored, as boards are fully opaque.
+fn get_svg_color(color: Color) -> String {
+    let color = color.to_color_u8();
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        color.red(),
+        color.green(),
+        color.blue()
+    )
+}
 
-    Ok(())
+/// Format a transform as an svg transform
+fn get_svg_transform(transform: Transform) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        transform.sx, transform.ky, transform.kx, transform.sy, transform.tx, transform.ty
+    )
+}
+
+/// Format a path as svg path data
+fn get_svg_path_data(path: &Path) -> String {
+    let mut data = String::new();
+    for segment in path.segments() {
+        // Writing to a String cannot fail
+        let _ = match segment {
+            PathSegment::MoveTo(p) => write!(data, "M{} {}", p.x, p.y),
+            PathSegment::LineTo(p) => write!(data, "L{} {}", p.x, p.y),
+            PathSegment::QuadTo(p1, p) => write!(data, "Q{} {} {} {}", p1.x, p1.y, p.x, p.y),
+            PathSegment::CubicTo(p1, p2, p) => {
+                write!(data, "C{} {} {} {} {} {}", p1.x, p1.y, p2.x, p2.y, p.x, p.y)
+            }
+            PathSegment::Close => write!(data, "Z"),
+        };
+    }
+    data
 }
 
 /// Utility to draw a font glyph to a path.
@@ -297,6 +535,7 @@ impl OutlineBuilder for SkiaBuilder {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::database::model::TicTacToeTheme;
     use tic_tac_toe::Team;
 
     #[test]
@@ -306,7 +545,41 @@ mod test {
             .set(0, Some(Team::X))
             .set(4, Some(Team::X))
             .set(8, Some(Team::X));
-        let img = renderer.render_board(board).expect("failed to render");
+        let img = renderer
+            .render_board(board, &Theme::default())
+            .expect("failed to render");
         std::fs::write("ttt-render-test.png", img).expect("failed to save");
     }
+
+    #[test]
+    fn render_board_svg() {
+        let renderer = Renderer::new().expect("failed to make renderer");
+        let board = tic_tac_toe::Board::new()
+            .set(2, Some(Team::O))
+            .set(4, Some(Team::O))
+            .set(6, Some(Team::O))
+            .set(0, Some(Team::X));
+        for theme in TicTacToeTheme::ALL {
+            let img = renderer
+                .render_board_svg(board, &theme.into())
+                .expect("failed to render");
+            std::fs::write(format!("ttt-render-test-{theme}.svg"), img).expect("failed to save");
+        }
+    }
+
+    #[test]
+    fn render_replay() {
+        let renderer = Renderer::new().expect("failed to make renderer");
+        let boards = [
+            tic_tac_toe::Board::new(),
+            tic_tac_toe::Board::new().set(4, Some(Team::X)),
+            tic_tac_toe::Board::new()
+                .set(4, Some(Team::X))
+                .set(0, Some(Team::O)),
+        ];
+        let img = renderer
+            .render_replay(&boards, &TicTacToeTheme::Dark.into())
+            .expect("failed to render");
+        std::fs::write("ttt-replay-test.png", img).expect("failed to save");
+    }
 }
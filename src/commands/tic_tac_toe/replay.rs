@@ -0,0 +1,88 @@
+use super::{
+    describe_history_game,
+    get_theme,
+    ThemeArg,
+    TicTacToeData,
+};
+use crate::database::Database;
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
+use serenity::builder::{
+    CreateAllowedMentions,
+    CreateAttachment,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use tracing::error;
+
+/// Options for replay
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct ReplayOptions {
+    /// The game id
+    #[pikadick_slash_framework(
+        description = "The id of the game, from `/tic-tac-toe history`",
+        min = 1
+    )]
+    game: i64,
+
+    /// The board theme
+    #[pikadick_slash_framework(
+        description = "The board theme. Defaults to the theme of this server"
+    )]
+    theme: Option<ThemeArg>,
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("replay")
+        .description("Replay a finished game of Tic-Tac-Toe")
+        .arguments(ReplayOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx,
+             interaction,
+             args: ReplayOptions,
+             (tic_tac_toe_data, db): (State<TicTacToeData>, State<Database>)| async move {
+                let theme = get_theme(&db, interaction.guild_id, args.theme).await?;
+                let result = db
+                    .get_tic_tac_toe_history_game(interaction.guild_id.into(), args.game)
+                    .await;
+                let message_builder = match result {
+                    Ok(Some(game)) => {
+                        let file = tic_tac_toe_data
+                            .renderer
+                            .render_replay_async(game.get_replay_boards(), theme.into())
+                            .await
+                            .context("failed to render Tic-Tac-Toe replay")?;
+                        let file =
+                            CreateAttachment::bytes(file, format!("ttt-replay-{}.png", game.id));
+
+                        CreateInteractionResponseMessage::new()
+                            .content(format!(
+                                "Replay of game #{}: {}",
+                                game.id,
+                                describe_history_game(&game)
+                            ))
+                            .allowed_mentions(CreateAllowedMentions::new())
+                            .files([file])
+                    }
+                    Ok(None) => CreateInteractionResponseMessage::new()
+                        .content("There is no finished game with that id in this server")
+                        .ephemeral(true),
+                    Err(error) => {
+                        error!("{error:?}");
+                        CreateInteractionResponseMessage::new().content("database error")
+                    }
+                };
+
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe replay command")
+}
@@ -16,10 +16,15 @@ use serenity::{
 use tracing::error;
 
 #[command]
-#[description("Get the top stats for Tic-Tac-Toe in this server")]
+#[description("Get the top stats for Tic-Tac-Toe in this server, or across every server")]
+#[usage("[global]")]
+#[example("")]
+#[example("global")]
+#[min_args(0)]
+#[max_args(1)]
 #[checks(Enabled)]
 #[bucket("default")]
-pub async fn scoreboard(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
+pub async fn scoreboard(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let data_lock = ctx.data.read().await;
     let client_data = data_lock
         .get::<ClientDataKey>()
@@ -27,11 +32,22 @@ pub async fn scoreboard(ctx: &Context, msg: &Message, _args: Args) -> CommandRes
     let db = client_data.db.clone();
     drop(data_lock);
 
-    let scores = match db
-        .get_top_tic_tac_toe_scores(msg.guild_id.into())
-        .await
-        .context("failed to get top tic-tac-toe stats")
-    {
+    let global = match args.trimmed().current() {
+        None => false,
+        Some(arg) if arg.eq_ignore_ascii_case("global") => true,
+        Some(arg) => {
+            let response = format!("The argument '{arg}' is not recognized. Valid: global");
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let scores = if global {
+        db.get_global_top_tic_tac_toe_scores().await
+    } else {
+        db.get_top_tic_tac_toe_scores(msg.guild_id.into()).await
+    };
+    let scores = match scores.context("failed to get top tic-tac-toe stats") {
         Ok(scores) => scores,
         Err(e) => {
             error!("{:?}", e);
@@ -45,7 +61,7 @@ pub async fn scoreboard(ctx: &Context, msg: &Message, _args: Args) -> CommandRes
 
     table.set_cell(0, 0, "Position");
     table.set_cell(1, 0, "Name");
-    table.set_cell(2, 0, "Score");
+    table.set_cell(2, 0, "Rating");
     table.set_cell(3, 0, "Wins");
     table.set_cell(4, 0, "Losses");
     table.set_cell(5, 0, "Ties");
@@ -61,18 +77,20 @@ pub async fn scoreboard(ctx: &Context, msg: &Message, _args: Args) -> CommandRes
 
         table.set_cell(0, i + 1, format!("{}", i + 1));
         table.set_cell(1, i + 1, username);
-        table.set_cell(2, i + 1, score.score.to_string());
+        table.set_cell(2, i + 1, format!("{:.0}", score.rating));
         table.set_cell(3, i + 1, score.wins.to_string());
         table.set_cell(4, i + 1, score.losses.to_string());
         table.set_cell(5, i + 1, score.ties.to_string());
         table.set_cell(6, i + 1, score.concedes.to_string());
     }
 
+    let title = if global {
+        "Top Global Tic-Tac-Toe Stats"
+    } else {
+        "Top Tic-Tac-Toe Stats"
+    };
     msg.channel_id
-        .say(
-            &ctx.http,
-            format!("```\nTop Tic-Tac-Toe Stats\n{}\n```", table),
-        )
+        .say(&ctx.http, format!("```\n{}\n{}\n```", title, table))
         .await?;
     Ok(())
 }
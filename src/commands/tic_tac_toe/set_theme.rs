@@ -0,0 +1,138 @@
+use super::ThemeArg;
+use crate::{
+    checks::{
+        ADMIN_CHECK,
+        ENABLED_CHECK,
+    },
+    database::{
+        model::TicTacToeTheme,
+        Database,
+    },
+    ClientDataKey,
+};
+use anyhow::Context as _;
+use pikadick_slash_framework::State;
+use serenity::{
+    framework::standard::{
+        macros::command,
+        Args,
+        CommandResult,
+    },
+    model::prelude::*,
+    prelude::*,
+};
+use tracing::error;
+
+#[command("theme")]
+#[description("Set the Tic-Tac-Toe board theme for this server")]
+#[usage("<classic OR dark OR light OR minimal OR large>")]
+#[example("dark")]
+#[min_args(1)]
+#[max_args(1)]
+#[checks(Admin, Enabled)]
+#[bucket("default")]
+pub async fn set_theme(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock
+        .get::<ClientDataKey>()
+        .expect("missing client data");
+    let db = client_data.db.clone();
+    drop(data_lock);
+
+    let theme: TicTacToeTheme = match args.trimmed().single() {
+        Ok(theme) => theme,
+        Err(e) => {
+            let valid = TicTacToeTheme::ALL.map(TicTacToeTheme::as_str).join(", ");
+            let response = format!("Invalid theme. Choose one of {valid}. Error: {e}");
+            msg.channel_id.say(&ctx.http, response).await?;
+            return Ok(());
+        }
+    };
+
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => {
+            msg.channel_id
+                .say(
+                    &ctx.http,
+                    "Missing server id. Are you in a server right now?",
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let old_theme = match db.set_tic_tac_toe_theme(guild_id, theme).await {
+        Ok(old_theme) => old_theme,
+        Err(error) => {
+            error!("{error:?}");
+            msg.channel_id.say(&ctx.http, "database error").await?;
+            return Ok(());
+        }
+    };
+
+    msg.channel_id
+        .say(&ctx.http, format_theme_change(old_theme, theme))
+        .await?;
+
+    Ok(())
+}
+
+/// Describe a change of the server theme
+fn format_theme_change(old_theme: TicTacToeTheme, theme: TicTacToeTheme) -> String {
+    if old_theme == theme {
+        format!("The Tic-Tac-Toe theme is already {theme} for this server")
+    } else {
+        format!("The Tic-Tac-Toe theme is now {theme} for this server")
+    }
+}
+
+/// Options for theme
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct ThemeOptions {
+    /// The server theme
+    #[pikadick_slash_framework(description = "The board theme of this server")]
+    theme: ThemeArg,
+}
+
+/// Create a slash subcommand
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    use pikadick_slash_framework::FromOptions;
+
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("theme")
+        .description("Set the Tic-Tac-Toe board theme for this server")
+        .check(crate::checks::admin::create_slash_check)
+        .arguments(ThemeOptions::get_argument_params()?.into_iter())
+        .on_process_with_state(
+            |ctx, interaction, args: ThemeOptions, db: State<Database>| async move {
+                let mut response = pikadick_slash_framework::Response::new(&ctx, &interaction);
+                let guild_id = match interaction.guild_id {
+                    Some(id) => id,
+                    None => {
+                        response
+                            .send_content("Missing server id. Are you in a server right now?")
+                            .await?;
+                        return Ok(());
+                    }
+                };
+
+                let theme = TicTacToeTheme::from(args.theme);
+                match db.set_tic_tac_toe_theme(guild_id, theme).await {
+                    Ok(old_theme) => {
+                        response
+                            .send_content(format_theme_change(old_theme, theme))
+                            .await?;
+                    }
+                    Err(error) => {
+                        error!("{error:?}");
+                        response.send_content("database error").await?;
+                    }
+                }
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tic-tac-toe theme command")
+}
@@ -1,10 +1,19 @@
 use crate::{
     checks::ENABLED_CHECK,
+    database::{
+        model::TicTacToeScore,
+        Database,
+    },
     util::AsciiTable,
     ClientDataKey,
 };
 use anyhow::Context as _;
+use pikadick_slash_framework::State;
 use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
     framework::standard::{
         macros::command,
         Args,
@@ -40,7 +49,15 @@ pub async fn stats(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
         }
     };
 
-    let mut table = AsciiTable::new(4, 2);
+    msg.channel_id
+        .say(&ctx.http, format_stats(&msg.author.name, &scores))
+        .await?;
+    Ok(())
+}
+
+/// Format the stats of a user as a table
+fn format_stats(name: &str, scores: &TicTacToeScore) -> String {
+    let mut table = AsciiTable::new(5, 2);
     table.set_padding(2);
 
     let mut wins_buffer = itoa::Buffer::new();
@@ -52,20 +69,65 @@ pub async fn stats(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
     table.set_cell(1, 0, "Losses");
     table.set_cell(2, 0, "Ties");
     table.set_cell(3, 0, "Concedes");
+    table.set_cell(4, 0, "Rating");
 
     table.set_cell(0, 1, wins_buffer.format(scores.wins));
     table.set_cell(1, 1, losses_buffer.format(scores.losses));
     table.set_cell(2, 1, ties_buffer.format(scores.ties));
     table.set_cell(3, 1, concedes_buffer.format(scores.concedes));
+    table.set_cell(4, 1, format!("{:.0}", scores.rating));
 
-    msg.channel_id
-        .say(
-            &ctx.http,
-            format!(
-                "```\n{}'s Tic-Tac-Toe Stats\n{}\n```",
-                msg.author.name, table
-            ),
+    if scores.computer.is_empty() {
+        return format!("```\n{name}'s Tic-Tac-Toe Stats\n{table}\n```");
+    }
+
+    let mut computer_table = AsciiTable::new(5, scores.computer.len() + 1);
+    computer_table.set_padding(2);
+
+    computer_table.set_cell(0, 0, "Difficulty");
+    computer_table.set_cell(1, 0, "Wins");
+    computer_table.set_cell(2, 0, "Losses");
+    computer_table.set_cell(3, 0, "Ties");
+    computer_table.set_cell(4, 0, "Concedes");
+
+    for (i, score) in scores.computer.iter().enumerate() {
+        computer_table.set_cell(0, i + 1, score.difficulty.as_str());
+        computer_table.set_cell(1, i + 1, score.wins.to_string());
+        computer_table.set_cell(2, i + 1, score.losses.to_string());
+        computer_table.set_cell(3, i + 1, score.ties.to_string());
+        computer_table.set_cell(4, i + 1, score.concedes.to_string());
+    }
+
+    format!("```\n{name}'s Tic-Tac-Toe Stats\n{table}\nAgainst the Computer\n{computer_table}\n```")
+}
+
+/// Create a user context menu command to show the stats of a user
+pub fn create_stats_context_menu_command(
+) -> anyhow::Result<pikadick_slash_framework::ContextMenuCommand> {
+    pikadick_slash_framework::ContextMenuCommandBuilder::new()
+        .name("Tic-tac-toe stats")
+        .bucket("default")
+        .on_process_with_state(
+            |ctx, interaction, user: User, db: State<Database>| async move {
+                let content = match db
+                    .get_tic_tac_toe_score(interaction.guild_id.into(), user.id)
+                    .await
+                    .context("failed to get tic-tac-toe stats")
+                {
+                    Ok(scores) => format_stats(&user.name, &scores),
+                    Err(error) => {
+                        error!("{error:?}");
+                        format!("{error:?}")
+                    }
+                };
+
+                let message_builder = CreateInteractionResponseMessage::new().content(content);
+                let response = CreateInteractionResponse::Message(message_builder);
+                interaction.create_response(&ctx.http, response).await?;
+
+                Ok(())
+            },
         )
-        .await?;
-    Ok(())
+        .build()
+        .context("failed to build tic-tac-toe stats context menu command")
 }
@@ -0,0 +1,170 @@
+use crate::{
+    config::TicTacToeConfig,
+    database::{
+        model::TicTacToeInactiveGame,
+        Database,
+    },
+};
+use anyhow::Context;
+use serenity::{
+    http::Http,
+    model::prelude::*,
+};
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::Notify;
+use tracing::{
+    error,
+    info,
+};
+
+/// The time between sweeps
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A task that warns players of inactive Tic-Tac-Toe games,
+/// and makes them concede if they still do not move.
+#[derive(Debug, Clone)]
+pub struct TicTacToeSweeper {
+    handle: Arc<parking_lot::Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    shutdown: Arc<Notify>,
+}
+
+impl TicTacToeSweeper {
+    /// Start a new sweeper task
+    pub fn new(http: Arc<Http>, db: Database, config: TicTacToeConfig) -> Self {
+        let shutdown = Arc::new(Notify::new());
+        let handle = tokio::spawn(sweeper_task_impl(http, db, config, shutdown.clone()));
+
+        Self {
+            handle: Arc::new(parking_lot::Mutex::new(Some(handle))),
+            shutdown,
+        }
+    }
+
+    /// Shutdown the task, waiting for it to exit.
+    ///
+    /// This can only be called once. Future calls will return an error.
+    pub async fn shutdown(&self) -> anyhow::Result<()> {
+        let handle = self.handle.lock().take().context("missing handle")?;
+        self.shutdown.notify_one();
+        handle.await.context("task panicked")
+    }
+}
+
+/// Impl for the sweeper task
+async fn sweeper_task_impl(
+    http: Arc<Http>,
+    db: Database,
+    config: TicTacToeConfig,
+    shutdown: Arc<Notify>,
+) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    while tokio::select! {
+        _ = interval.tick() => true,
+        _ = shutdown.notified() => false,
+    } {
+        if let Err(error) = sweep(&http, &db, config)
+            .await
+            .context("failed to sweep tic-tac-toe games")
+        {
+            error!("{error:?}");
+        }
+    }
+}
+
+/// Warn players of inactive games, then expire games that are still inactive
+async fn sweep(http: &Http, db: &Database, config: TicTacToeConfig) -> anyhow::Result<()> {
+    // Warnings are pointless if the game would expire at the same time
+    if config.warn_after < config.expire_after {
+        let inactive_games = db
+            .claim_tic_tac_toe_inactivity_warnings(config.warn_after())
+            .await
+            .context("failed to get games to warn about")?;
+
+        for inactive_game in inactive_games {
+            let player = inactive_game.game.get_player_turn();
+            let opponent = inactive_game
+                .game
+                .get_player(inactive_game.game.get_team_turn().inverse());
+            let expires_at = inactive_game
+                .last_move_at
+                .saturating_add_unsigned(config.expire_after);
+            let content = format!(
+                "{}, it is your turn in Tic-Tac-Toe against {}. Move <t:{expires_at}:R> or you will concede.",
+                player.mention(),
+                opponent.mention(),
+            );
+
+            if let Err(error) = notify(http, &inactive_game, content)
+                .await
+                .with_context(|| format!("failed to warn about game {}", inactive_game.id))
+            {
+                error!("{error:?}");
+            }
+        }
+    }
+
+    let expired_games = db
+        .expire_tic_tac_toe_games(config.expire_after())
+        .await
+        .context("failed to expire games")?;
+
+    for inactive_game in expired_games {
+        info!("expired tic-tac-toe game {}", inactive_game.id);
+
+        let player = inactive_game.game.get_player_turn();
+        let opponent = inactive_game
+            .game
+            .get_player(inactive_game.game.get_team_turn().inverse());
+        let content = format!(
+            "{} did not move in time and conceded to {} in Tic-Tac-Toe.",
+            player.mention(),
+            opponent.mention(),
+        );
+
+        if let Err(error) = notify(http, &inactive_game, content)
+            .await
+            .with_context(|| format!("failed to announce expiry of game {}", inactive_game.id))
+        {
+            error!("{error:?}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Send a message about an inactive game.
+///
+/// This is sent to the channel of the last move,
+/// or to the player whose turn it is if the channel is not known.
+async fn notify(
+    http: &Http,
+    inactive_game: &TicTacToeInactiveGame,
+    content: String,
+) -> anyhow::Result<()> {
+    let channel_id = match (
+        inactive_game.channel_id,
+        inactive_game.game.get_player_turn().get_user(),
+    ) {
+        (Some(channel_id), _) => channel_id,
+        (None, Some(user_id)) => {
+            user_id
+                .create_dm_channel(http)
+                .await
+                .context("failed to open dm channel")?
+                .id
+        }
+        (None, None) => return Ok(()),
+    };
+
+    channel_id
+        .say(http, content)
+        .await
+        .context("failed to send message")?;
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::database::model::TicTacToeTheme;
+use tiny_skia::Color;
+
+/// The colors and size of a rendered Tic-Tac-Toe board
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Theme {
+    /// The colors of the squares, alternating from the top left
+    pub(crate) squares: [Color; 2],
+
+    /// The color of the lines between squares, if they are drawn
+    pub(crate) grid: Option<Color>,
+
+    /// The color of X pieces
+    pub(crate) x: Color,
+
+    /// The color of O pieces
+    pub(crate) o: Color,
+
+    /// The color of the numbers of empty tiles.
+    ///
+    /// Tiles are not numbered if this is `None`.
+    pub(crate) numbers: Option<Color>,
+
+    /// The color of the winning line
+    pub(crate) winning_line: Color,
+
+    /// The width and height of the board, in pixels
+    pub(crate) size: u16,
+}
+
+impl Theme {
+    const CLASSIC: Self = Self {
+        squares: [
+            Color::from_rgba8(255, 0, 0, 255),
+            Color::from_rgba8(119, 119, 119, 255),
+        ],
+        grid: None,
+        x: Color::from_rgba8(0, 0, 0, 255),
+        o: Color::from_rgba8(0, 0, 0, 255),
+        numbers: Some(Color::from_rgba8(255, 255, 255, 255)),
+        winning_line: Color::from_rgba8(48, 48, 48, 255),
+        size: 300,
+    };
+
+    const DARK: Self = Self {
+        squares: [
+            Color::from_rgba8(40, 44, 52, 255),
+            Color::from_rgba8(33, 37, 43, 255),
+        ],
+        grid: Some(Color::from_rgba8(80, 86, 96, 255)),
+        x: Color::from_rgba8(224, 108, 117, 255),
+        o: Color::from_rgba8(97, 175, 239, 255),
+        numbers: Some(Color::from_rgba8(92, 99, 112, 255)),
+        winning_line: Color::from_rgba8(229, 192, 123, 255),
+        size: 300,
+    };
+
+    const LIGHT: Self = Self {
+        squares: [
+            Color::from_rgba8(250, 250, 250, 255),
+            Color::from_rgba8(250, 250, 250, 255),
+        ],
+        grid: Some(Color::from_rgba8(60, 60, 60, 255)),
+        x: Color::from_rgba8(200, 30, 30, 255),
+        o: Color::from_rgba8(30, 60, 200, 255),
+        numbers: Some(Color::from_rgba8(180, 180, 180, 255)),
+        winning_line: Color::from_rgba8(48, 48, 48, 255),
+        size: 300,
+    };
+
+    const MINIMAL: Self = Self {
+        numbers: None,
+        ..Self::LIGHT
+    };
+
+    const LARGE: Self = Self {
+        size: 600,
+        ..Self::CLASSIC
+    };
+
+    /// Get the width and height of a square, in pixels
+    pub(crate) fn square_size(&self) -> f32 {
+        f32::from(self.size) / 3.0
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        TicTacToeTheme::default().into()
+    }
+}
+
+impl From<TicTacToeTheme> for Theme {
+    fn from(theme: TicTacToeTheme) -> Self {
+        match theme {
+            TicTacToeTheme::Classic => Self::CLASSIC,
+            TicTacToeTheme::Dark => Self::DARK,
+            TicTacToeTheme::Light => Self::LIGHT,
+            TicTacToeTheme::Minimal => Self::MINIMAL,
+            TicTacToeTheme::Large => Self::LARGE,
+        }
+    }
+}
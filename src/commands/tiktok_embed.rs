@@ -3,7 +3,9 @@ use crate::{
         CacheStatsBuilder,
         CacheStatsProvider,
     },
+    database::Database,
     util::{
+        parse_yes_no,
         EncoderTask,
         TimedCache,
         TimedCacheEntry,
@@ -44,6 +46,9 @@ use tracing::{
 };
 use url::Url;
 
+/// The custom id prefix of the settings modal
+const SETTINGS_MODAL_PREFIX: &str = "tiktok-embed-settings";
+
 const FILE_SIZE_LIMIT_BYTES: u64 = 8 * 1024 * 1024;
 const TARGET_FILE_SIZE_BYTES: u64 = 7 * 1024 * 1024;
 const ENCODER_PREFERENCE_LIST: &[&str] = &[
@@ -477,6 +482,74 @@ struct TikTokEmbedOptions {
     delete_link: Option<bool>,
 }
 
+/// Settings form, for changing every setting at once
+#[derive(Debug, pikadick_slash_framework::FromModal)]
+struct TikTokEmbedSettingsModal {
+    #[pikadick_slash_framework(
+        label = "Enabled? (yes/no)",
+        placeholder = "Leave empty to keep the current setting",
+        max_length = 8
+    )]
+    enable: Option<String>,
+
+    #[pikadick_slash_framework(
+        rename = "delete-link",
+        label = "Delete link? (yes/no)",
+        placeholder = "Leave empty to keep the current setting",
+        max_length = 8
+    )]
+    delete_link: Option<String>,
+}
+
+/// Update the tiktok embed flags of a guild.
+///
+/// Settings that are `None` are left unchanged.
+///
+/// # Returns
+/// Returns an embed of the new settings.
+async fn set_tiktok_embed_settings(
+    db: &Database,
+    guild_id: GuildId,
+    enable: Option<bool>,
+    delete_link: Option<bool>,
+) -> anyhow::Result<CreateEmbed> {
+    let mut set_flags = TikTokEmbedFlags::empty();
+    let mut unset_flags = TikTokEmbedFlags::empty();
+
+    if let Some(enable) = enable {
+        if enable {
+            set_flags.insert(TikTokEmbedFlags::ENABLED);
+        } else {
+            unset_flags.insert(TikTokEmbedFlags::ENABLED);
+        }
+    }
+
+    if let Some(enable) = delete_link {
+        if enable {
+            set_flags.insert(TikTokEmbedFlags::DELETE_LINK);
+        } else {
+            unset_flags.insert(TikTokEmbedFlags::DELETE_LINK);
+        }
+    }
+
+    let (_old_flags, new_flags) = db
+        .set_tiktok_embed_flags(guild_id, set_flags, unset_flags)
+        .await?;
+
+    Ok(CreateEmbed::new()
+        .title("TikTok Embeds")
+        .field(
+            "Enabled?",
+            bool_to_str(new_flags.contains(TikTokEmbedFlags::ENABLED)),
+            false,
+        )
+        .field(
+            "Delete link?",
+            bool_to_str(new_flags.contains(TikTokEmbedFlags::DELETE_LINK)),
+            false,
+        ))
+}
+
 /// Create a slash command
 pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
     use pikadick_slash_framework::FromOptions;
@@ -484,6 +557,12 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
     pikadick_slash_framework::CommandBuilder::new()
         .name("tiktok-embed")
         .description("Configure tiktok embeds for this server")
+        .category("Admin")
+        .example("/tiktok-embed enable:True delete-link:False")
+        .example("/tiktok-embed")
+        .bucket("default")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .dm_permission(false)
         .check(crate::checks::admin::create_slash_check)
         .arguments(TikTokEmbedOptions::get_argument_params()?.into_iter())
         .on_process(|ctx, interaction, args: TikTokEmbedOptions| async move {
@@ -492,62 +571,87 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
             let db = client_data.db.clone();
             drop(data_lock);
 
+            let mut response = pikadick_slash_framework::Response::new(&ctx, &interaction);
             let guild_id = match interaction.guild_id {
                 Some(id) => id,
                 None => {
-                    let message_builder = CreateInteractionResponseMessage::new()
-                        .content("Missing server id. Are you in a server right now?");
-                    let response = CreateInteractionResponse::Message(message_builder);
-                    interaction.create_response(&ctx.http, response).await?;
+                    response
+                        .send_content("Missing server id. Are you in a server right now?")
+                        .await?;
                     return Ok(());
                 }
             };
 
-            let mut set_flags = TikTokEmbedFlags::empty();
-            let mut unset_flags = TikTokEmbedFlags::empty();
-
-            if let Some(enable) = args.enable {
-                if enable {
-                    set_flags.insert(TikTokEmbedFlags::ENABLED);
-                } else {
-                    unset_flags.insert(TikTokEmbedFlags::ENABLED);
-                }
-            }
-
-            if let Some(enable) = args.delete_link {
-                if enable {
-                    set_flags.insert(TikTokEmbedFlags::DELETE_LINK);
-                } else {
-                    unset_flags.insert(TikTokEmbedFlags::DELETE_LINK);
-                }
+            // Open the settings form if no settings were given
+            if args.enable.is_none() && args.delete_link.is_none() {
+                let modal = pikadick_slash_framework::create_modal::<TikTokEmbedSettingsModal, ()>(
+                    SETTINGS_MODAL_PREFIX,
+                    &(),
+                    "TikTok Embeds",
+                )
+                .context("failed to create tiktok embed settings modal")?;
+                interaction
+                    .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+                    .await?;
+                return Ok(());
             }
 
-            let (_old_flags, new_flags) = db
-                .set_tiktok_embed_flags(guild_id, set_flags, unset_flags)
+            let embed_builder =
+                set_tiktok_embed_settings(&db, guild_id, args.enable, args.delete_link).await?;
+            response
+                .send(pikadick_slash_framework::ResponseMessage::new().embed(embed_builder))
                 .await?;
 
-            let embed_builder = CreateEmbed::new()
-                .title("TikTok Embeds")
-                .field(
-                    "Enabled?",
-                    bool_to_str(new_flags.contains(TikTokEmbedFlags::ENABLED)),
-                    false,
-                )
-                .field(
-                    "Delete link?",
-                    bool_to_str(new_flags.contains(TikTokEmbedFlags::DELETE_LINK)),
-                    false,
-                );
-            let message_builder = CreateInteractionResponseMessage::new().embed(embed_builder);
-            let response = CreateInteractionResponse::Message(message_builder);
-            interaction.create_response(&ctx.http, response).await?;
-
             Ok(())
         })
         .build()
         .context("failed to build command")
 }
 
+/// Create the handler for the settings modal
+pub fn create_settings_modal_handler() -> anyhow::Result<pikadick_slash_framework::ModalHandler> {
+    pikadick_slash_framework::ModalHandlerBuilder::new()
+        .prefix(SETTINGS_MODAL_PREFIX)
+        .command("tiktok-embed")
+        .check(crate::checks::admin::create_modal_check)
+        .on_process(
+            |ctx, interaction, _state: (), fields: TikTokEmbedSettingsModal| async move {
+                let data_lock = ctx.data.read().await;
+                let client_data = data_lock.get::<ClientDataKey>().unwrap();
+                let db = client_data.db.clone();
+                drop(data_lock);
+
+                let guild_id = interaction
+                    .guild_id
+                    .context("missing server id. Are you in a server right now?")?;
+                let enable = fields
+                    .enable
+                    .as_deref()
+                    .map(|value| parse_yes_no(value).context("\"Enabled?\" must be yes or no"))
+                    .transpose()?;
+                let delete_link = fields
+                    .delete_link
+                    .as_deref()
+                    .map(|value| parse_yes_no(value).context("\"Delete link?\" must be yes or no"))
+                    .transpose()?;
+
+                let embed_builder =
+                    set_tiktok_embed_settings(&db, guild_id, enable, delete_link).await?;
+                let message_builder = CreateInteractionResponseMessage::new().embed(embed_builder);
+                interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(message_builder),
+                    )
+                    .await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build tiktok embed settings modal handler")
+}
+
 /// Convert a bool to a str
 fn bool_to_str(value: bool) -> &'static str {
     if value {
@@ -1,6 +1,12 @@
 use crate::checks::ENABLED_CHECK;
+use anyhow::Context as _;
+use pikadick_slash_framework::FromOptions;
 use rand::prelude::SliceRandom;
 use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
     client::Context,
     framework::standard::{
         macros::command,
@@ -12,6 +18,9 @@ use serenity::{
 
 const FACES: &[&str] = &["(・`ω´・)", ";;w;;", "owo", "UwU", ">w<", "^w^"];
 
+/// The custom id prefix of the uwuify modal
+const MODAL_PREFIX: &str = "uwuify";
+
 #[command]
 #[description("UwUify as phrase")]
 #[usage("\"<phrase>\"")]
@@ -26,6 +35,100 @@ pub async fn uwuify(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
     Ok(())
 }
 
+/// Options for uwuify
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct UwuifyOptions {
+    #[pikadick_slash_framework(description = "The phrase. Leave empty to write a longer phrase.")]
+    phrase: Option<String>,
+}
+
+/// Modal fields, for phrases that do not fit in an option
+#[derive(Debug, pikadick_slash_framework::FromModal)]
+struct UwuifyModal {
+    #[pikadick_slash_framework(label = "Phrase", paragraph, max_length = 1500)]
+    phrase: String,
+}
+
+/// Respond to an interaction with an uwuified phrase
+fn create_uwuify_response(phrase: &str) -> CreateInteractionResponse {
+    let message_builder = CreateInteractionResponseMessage::new().content(uwuify_str(phrase));
+    CreateInteractionResponse::Message(message_builder)
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("uwuify")
+        .description("UwUify a phrase")
+        .category("Fun")
+        .example("/uwuify phrase:Hello there")
+        .example("/uwuify")
+        .bucket("default")
+        .arguments(UwuifyOptions::get_argument_params()?.into_iter())
+        .on_process(|ctx, interaction, args: UwuifyOptions| async move {
+            let response = match args.phrase {
+                Some(phrase) => create_uwuify_response(&phrase),
+                None => {
+                    let modal = pikadick_slash_framework::create_modal::<UwuifyModal, ()>(
+                        MODAL_PREFIX,
+                        &(),
+                        "UwUify",
+                    )
+                    .context("failed to create uwuify modal")?;
+                    CreateInteractionResponse::Modal(modal)
+                }
+            };
+
+            interaction.create_response(&ctx.http, response).await?;
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build uwuify command")
+}
+
+/// Create the handler for the uwuify modal
+pub fn create_modal_handler() -> anyhow::Result<pikadick_slash_framework::ModalHandler> {
+    pikadick_slash_framework::ModalHandlerBuilder::new()
+        .prefix(MODAL_PREFIX)
+        .command("uwuify")
+        .on_process(
+            |ctx, interaction, _state: (), fields: UwuifyModal| async move {
+                interaction
+                    .create_response(&ctx.http, create_uwuify_response(&fields.phrase))
+                    .await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build uwuify modal handler")
+}
+
+/// Create a message context menu command
+pub fn create_context_menu_command() -> anyhow::Result<pikadick_slash_framework::ContextMenuCommand>
+{
+    pikadick_slash_framework::ContextMenuCommandBuilder::new()
+        .name("UwUify")
+        .bucket("default")
+        .on_process(|ctx, interaction, message: Message| async move {
+            let response = if message.content.is_empty() {
+                let message_builder = CreateInteractionResponseMessage::new()
+                    .content("That message has no text to uwuify")
+                    .ephemeral(true);
+                CreateInteractionResponse::Message(message_builder)
+            } else {
+                create_uwuify_response(&message.content)
+            };
+
+            interaction.create_response(&ctx.http, response).await?;
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build uwuify context menu command")
+}
+
 /// A rust-optimized version of:
 /// ```javascript
 /// /// Taken from: https://honk.moe/tools/owo.html
@@ -135,3 +238,62 @@ pub fn uwuify_str(input: &str) -> String {
 
     output
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pikadick_slash_framework::{
+        testing::{
+            InteractionBuilder,
+            TestHarness,
+        },
+        BucketBuilder,
+        FrameworkBuilder,
+    };
+    use std::time::Duration;
+
+    async fn create_harness() -> TestHarness {
+        let bucket = BucketBuilder::new()
+            .delay(Duration::from_secs(1))
+            .build()
+            .expect("failed to build bucket");
+        let framework = FrameworkBuilder::new()
+            .bucket("default", bucket)
+            .command(create_slash_command().expect("failed to build uwuify command"))
+            .modal_handler(create_modal_handler().expect("failed to build uwuify modal handler"))
+            .build()
+            .expect("failed to build framework");
+
+        TestHarness::new(framework)
+            .await
+            .expect("failed to start harness")
+    }
+
+    #[tokio::test]
+    async fn slash_command_uwuifies() {
+        let harness = create_harness().await;
+
+        let requests = harness
+            .run(InteractionBuilder::command("uwuify").string_option("phrase", "hello world"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].is_interaction_response());
+        assert_eq!(requests[0].content().as_deref(), Some("hewwo wowwd"));
+    }
+
+    #[tokio::test]
+    async fn slash_command_opens_modal() {
+        let harness = create_harness().await;
+
+        let requests = harness
+            .run(&InteractionBuilder::command("uwuify"))
+            .await
+            .expect("failed to run command");
+        assert_eq!(requests.len(), 1);
+        let json = requests[0].json().expect("missing body");
+        // A modal response
+        assert_eq!(json["type"], 9);
+        assert_eq!(json["data"]["custom_id"], "uwuify:");
+    }
+}
@@ -1,9 +1,13 @@
 use crate::ClientDataKey;
 use anyhow::Context as _;
 use pikadick_slash_framework::FromOptions;
-use serenity::builder::{
-    CreateInteractionResponse,
-    CreateInteractionResponseMessage,
+use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    client::Context,
+    model::channel::Message,
 };
 use tracing::error;
 
@@ -14,35 +18,43 @@ struct Options {
     message: String,
 }
 
+/// Translate a message, and respond with the translation
+async fn create_yodaspeak_response(ctx: &Context, message: &str) -> CreateInteractionResponse {
+    let data_lock = ctx.data.read().await;
+    let client_data = data_lock.get::<ClientDataKey>().unwrap();
+    let client = client_data.yodaspeak.clone();
+    drop(data_lock);
+
+    let result = client
+        .translate(message)
+        .await
+        .context("failed to translate");
+
+    let mut message_builder = CreateInteractionResponseMessage::new();
+    match result {
+        Ok(translated) => {
+            message_builder = message_builder.content(translated);
+        }
+        Err(error) => {
+            error!("{error:?}");
+            message_builder = message_builder.content(format!("{error:?}"));
+        }
+    }
+
+    CreateInteractionResponse::Message(message_builder)
+}
+
 /// Create a slash command
 pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
     pikadick_slash_framework::CommandBuilder::new()
         .name("yodaspeak")
         .description("Translate into what yoda would say.")
+        .category("Fun")
+        .example("/yodaspeak message:You will learn patience")
+        .bucket("default")
         .arguments(Options::get_argument_params()?.into_iter())
         .on_process(|ctx, interaction, args: Options| async move {
-            let data_lock = ctx.data.read().await;
-            let client_data = data_lock.get::<ClientDataKey>().unwrap();
-            let client = client_data.yodaspeak.clone();
-            drop(data_lock);
-
-            let result = client
-                .translate(args.message.as_str())
-                .await
-                .context("failed to translate");
-
-            let mut message_builder = CreateInteractionResponseMessage::new();
-            match result {
-                Ok(translated) => {
-                    message_builder = message_builder.content(translated);
-                }
-                Err(error) => {
-                    error!("{error:?}");
-                    message_builder = message_builder.content(format!("{error:?}"));
-                }
-            }
-
-            let response = CreateInteractionResponse::Message(message_builder);
+            let response = create_yodaspeak_response(&ctx, &args.message).await;
             interaction.create_response(&ctx.http, response).await?;
 
             Ok(())
@@ -50,3 +62,27 @@ pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Comman
         .build()
         .context("failed to build command")
 }
+
+/// Create a message context menu command
+pub fn create_context_menu_command() -> anyhow::Result<pikadick_slash_framework::ContextMenuCommand>
+{
+    pikadick_slash_framework::ContextMenuCommandBuilder::new()
+        .name("Yodaspeak")
+        .bucket("default")
+        .on_process(|ctx, interaction, message: Message| async move {
+            let response = if message.content.is_empty() {
+                let message_builder = CreateInteractionResponseMessage::new()
+                    .content("That message has no text to translate")
+                    .ephemeral(true);
+                CreateInteractionResponse::Message(message_builder)
+            } else {
+                create_yodaspeak_response(&ctx, &message.content).await
+            };
+
+            interaction.create_response(&ctx.http, response).await?;
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build yodaspeak context menu command")
+}
@@ -1,5 +1,11 @@
 use crate::checks::ENABLED_CHECK;
+use anyhow::Context as _;
+use pikadick_slash_framework::FromOptions;
 use serenity::{
+    builder::{
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
     client::Context,
     framework::standard::{
         macros::command,
@@ -10,6 +16,12 @@ use serenity::{
 };
 use zalgo::ZalgoBuilder;
 
+/// The custom id prefix of the zalgo modal
+const MODAL_PREFIX: &str = "zalgo";
+
+/// The default max length of zalgoified text
+const DEFAULT_MAX_LEN: usize = 2_000;
+
 #[command]
 #[description("Zalgoify a phrase")]
 #[usage("\"<phrase>\"<Max Length>")]
@@ -20,29 +32,120 @@ use zalgo::ZalgoBuilder;
 #[bucket("default")]
 pub async fn zalgo(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let input: String = args.single_quoted()?;
-    let input_max = args.single().unwrap_or(2_000);
+    let input_max = args.single().unwrap_or(DEFAULT_MAX_LEN);
 
+    msg.channel_id
+        .say(&ctx.http, zalgoify_phrase(&input, input_max))
+        .await?;
+
+    Ok(())
+}
+
+/// Zalgoify a phrase so the output is at most `max_len` chars.
+///
+/// # Returns
+/// Returns an error message if the phrase cannot be zalgoified within the limit.
+fn zalgoify_phrase(input: &str, max_len: usize) -> String {
     let input_len = input.chars().count();
-    let total = (input_max as f32 - input_len as f32) / input_len as f32;
+    let total = (max_len as f32 - input_len as f32) / input_len as f32;
     let max = (total / 3.0) as usize;
 
     if max == 0 {
-        msg.channel_id
-            .say(
-                &ctx.http,
-                "The phrase cannot be zalgoified within the given limits",
-            )
-            .await?;
-        return Ok(());
+        return "The phrase cannot be zalgoified within the given limits".to_string();
     }
 
-    let output = ZalgoBuilder::new()
+    ZalgoBuilder::new()
         .set_up(max)
         .set_down(max)
         .set_mid(max)
-        .zalgoify(&input);
+        .zalgoify(input)
+}
 
-    msg.channel_id.say(&ctx.http, &output).await?;
+/// Options for zalgo
+#[derive(Debug, pikadick_slash_framework::FromOptions)]
+struct ZalgoOptions {
+    #[pikadick_slash_framework(description = "The phrase. Leave empty to write a longer phrase.")]
+    phrase: Option<String>,
 
-    Ok(())
+    #[pikadick_slash_framework(
+        rename = "max-length",
+        description = "The max length of the output"
+    )]
+    max_length: Option<i64>,
+}
+
+/// Modal fields, for phrases that do not fit in an option
+#[derive(Debug, pikadick_slash_framework::FromModal)]
+struct ZalgoModal {
+    #[pikadick_slash_framework(label = "Phrase", paragraph, max_length = 1000)]
+    phrase: String,
+}
+
+/// Respond to an interaction with a zalgoified phrase
+fn create_zalgo_response(phrase: &str, max_len: usize) -> CreateInteractionResponse {
+    let message_builder =
+        CreateInteractionResponseMessage::new().content(zalgoify_phrase(phrase, max_len));
+    CreateInteractionResponse::Message(message_builder)
+}
+
+/// Create a slash command
+pub fn create_slash_command() -> anyhow::Result<pikadick_slash_framework::Command> {
+    pikadick_slash_framework::CommandBuilder::new()
+        .name("zalgo")
+        .description("Zalgoify a phrase")
+        .category("Fun")
+        .example("/zalgo phrase:Hello World! max-length:50")
+        .example("/zalgo")
+        .bucket("default")
+        .arguments(ZalgoOptions::get_argument_params()?.into_iter())
+        .on_process(|ctx, interaction, args: ZalgoOptions| async move {
+            let max_len = match args.max_length {
+                Some(max_length) => usize::try_from(max_length)
+                    .context("the max length cannot be negative")?
+                    .min(DEFAULT_MAX_LEN),
+                None => DEFAULT_MAX_LEN,
+            };
+
+            let response = match args.phrase {
+                Some(phrase) => create_zalgo_response(&phrase, max_len),
+                None => {
+                    // The max length is kept in the custom id until the modal is submitted
+                    let modal = pikadick_slash_framework::create_modal::<ZalgoModal, String>(
+                        MODAL_PREFIX,
+                        &max_len.to_string(),
+                        "Zalgo",
+                    )
+                    .context("failed to create zalgo modal")?;
+                    CreateInteractionResponse::Modal(modal)
+                }
+            };
+
+            interaction.create_response(&ctx.http, response).await?;
+
+            Ok(())
+        })
+        .build()
+        .context("failed to build zalgo command")
+}
+
+/// Create the handler for the zalgo modal
+pub fn create_modal_handler() -> anyhow::Result<pikadick_slash_framework::ModalHandler> {
+    pikadick_slash_framework::ModalHandlerBuilder::new()
+        .prefix(MODAL_PREFIX)
+        .command("zalgo")
+        .on_process(
+            |ctx, interaction, max_len: String, fields: ZalgoModal| async move {
+                let max_len: usize = max_len.parse()?;
+                interaction
+                    .create_response(
+                        &ctx.http,
+                        create_zalgo_response(&fields.phrase, max_len.min(DEFAULT_MAX_LEN)),
+                    )
+                    .await?;
+
+                Ok(())
+            },
+        )
+        .build()
+        .context("failed to build zalgo modal handler")
 }
@@ -14,12 +14,28 @@ use serenity::{
 use std::{
     borrow::Cow,
     collections::HashMap,
+    time::Duration,
 };
 
 fn default_prefix() -> String {
     "p!".to_string()
 }
 
+/// The default command buckets and their delays in seconds.
+///
+/// These are used by both the standard framework and the slash framework.
+pub const DEFAULT_BUCKETS: &[(&str, u64)] = &[
+    ("r6stats", 7),
+    ("r6tracker", 7),
+    ("system", 30),
+    ("quizizz", 10),
+    ("insta-dl", 10),
+    ("find-source", 10),
+    ("ttt-board", 1),
+    ("c4-board", 1),
+    ("default", 1),
+];
+
 /// The bot config
 #[derive(Deserialize, Debug)]
 pub struct Config {
@@ -59,6 +75,14 @@ pub struct Config {
     #[serde(default)]
     pub log: LogConfig,
 
+    /// The slash framework config
+    #[serde(default, rename = "slash-framework")]
+    pub slash_framework: SlashFrameworkConfig,
+
+    /// The tic-tac-toe config
+    #[serde(default, rename = "tic-tac-toe")]
+    pub tic_tac_toe: TicTacToeConfig,
+
     /// Unknown extra data
     #[serde(flatten)]
     pub extra: HashMap<String, toml::Value>,
@@ -104,6 +128,147 @@ pub struct OpenAiConfig {
     pub extra: HashMap<String, toml::Value>,
 }
 
+/// Slash Framework Config
+#[derive(Deserialize, Debug, Default)]
+pub struct SlashFrameworkConfig {
+    /// Whether to only log the changes that registering slash commands would make
+    #[serde(default, rename = "dry-run")]
+    pub dry_run: bool,
+
+    /// Rate limit buckets for slash commands.
+    ///
+    /// These override the default buckets with the same name.
+    #[serde(default)]
+    pub buckets: HashMap<String, BucketConfig>,
+}
+
+impl SlashFrameworkConfig {
+    /// Get all buckets, including defaults that were not overridden
+    pub fn all_buckets(&self) -> HashMap<&str, BucketConfig> {
+        let mut buckets: HashMap<&str, BucketConfig> = DEFAULT_BUCKETS
+            .iter()
+            .map(|(name, delay)| {
+                let bucket = BucketConfig {
+                    scope: BucketScopeConfig::Channel,
+                    delay: *delay,
+                    burst: 1,
+                };
+                (*name, bucket)
+            })
+            .collect();
+        buckets.extend(
+            self.buckets
+                .iter()
+                .map(|(name, bucket)| (name.as_str(), *bucket)),
+        );
+
+        buckets
+    }
+}
+
+/// Bucket Config
+#[derive(Deserialize, Debug, Copy, Clone)]
+pub struct BucketConfig {
+    /// What the bucket limits
+    #[serde(default)]
+    pub scope: BucketScopeConfig,
+
+    /// The time it takes to regain a use, in seconds
+    pub delay: u64,
+
+    /// The number of uses in a row that are allowed
+    #[serde(default = "BucketConfig::default_burst")]
+    pub burst: u32,
+}
+
+impl BucketConfig {
+    fn default_burst() -> u32 {
+        1
+    }
+
+    /// Make a slash framework bucket from this config
+    pub fn build(
+        &self,
+    ) -> Result<pikadick_slash_framework::Bucket, pikadick_slash_framework::BuilderError> {
+        pikadick_slash_framework::BucketBuilder::new()
+            .scope(self.scope.into())
+            .delay(Duration::from_secs(self.delay))
+            .burst(self.burst)
+            .build()
+    }
+}
+
+/// What a bucket limits
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BucketScopeConfig {
+    /// Each user has their own limit
+    User,
+
+    /// Each channel has its own limit
+    #[default]
+    Channel,
+
+    /// Each guild has its own limit
+    Guild,
+}
+
+impl From<BucketScopeConfig> for pikadick_slash_framework::BucketScope {
+    fn from(scope: BucketScopeConfig) -> Self {
+        match scope {
+            BucketScopeConfig::User => Self::User,
+            BucketScopeConfig::Channel => Self::Channel,
+            BucketScopeConfig::Guild => Self::Guild,
+        }
+    }
+}
+
+/// Tic-Tac-Toe Config
+#[derive(Deserialize, Debug, Copy, Clone)]
+pub struct TicTacToeConfig {
+    /// The time without a move before the player whose turn it is is warned, in seconds
+    #[serde(default = "TicTacToeConfig::default_warn_after", rename = "warn-after")]
+    pub warn_after: u64,
+
+    /// The time without a move before the player whose turn it is concedes, in seconds
+    #[serde(
+        default = "TicTacToeConfig::default_expire_after",
+        rename = "expire-after"
+    )]
+    pub expire_after: u64,
+}
+
+impl TicTacToeConfig {
+    fn default_warn_after() -> u64 {
+        // 12 hours
+        12 * 60 * 60
+    }
+
+    fn default_expire_after() -> u64 {
+        // 1 day
+        24 * 60 * 60
+    }
+
+    /// Get the warn time as a [`Duration`]
+    pub fn warn_after(&self) -> Duration {
+        Duration::from_secs(self.warn_after)
+    }
+
+    /// Get the expire time as a [`Duration`]
+    pub fn expire_after(&self) -> Duration {
+        Duration::from_secs(self.expire_after)
+    }
+}
+
+impl Default for TicTacToeConfig {
+    fn default() -> Self {
+        Self {
+            warn_after: Self::default_warn_after(),
+            expire_after: Self::default_expire_after(),
+        }
+    }
+}
+
 /// Log Config
 #[derive(Deserialize, Debug)]
 pub struct LogConfig {
@@ -216,6 +381,15 @@ impl Config {
             }
         }
 
+        if self.tic_tac_toe.warn_after >= self.tic_tac_toe.expire_after {
+            errors.push(ValidationMessage {
+                severity: Severity::Warn,
+                error: ValidationError::Generic(
+                    "tic-tac-toe warn-after should be less than expire-after".into(),
+                ),
+            });
+        }
+
         errors
     }
 }
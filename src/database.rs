@@ -1,3 +1,4 @@
+mod connect_four;
 mod disabled_commands;
 mod kv_store;
 pub mod model;
@@ -5,10 +6,17 @@ mod reddit_embed;
 mod tic_tac_toe;
 mod tiktok_embed;
 
-pub use self::tic_tac_toe::{
-    TicTacToeCreateGameError,
-    TicTacToeTryMoveError,
-    TicTacToeTryMoveResponse,
+pub use self::{
+    connect_four::{
+        ConnectFourCreateGameError,
+        ConnectFourTryMoveError,
+        ConnectFourTryMoveResponse,
+    },
+    tic_tac_toe::{
+        TicTacToeCreateGameError,
+        TicTacToeTryMoveError,
+        TicTacToeTryMoveResponse,
+    },
 };
 use anyhow::Context;
 use camino::{
@@ -22,12 +30,27 @@ use std::{
 };
 use tracing::{
     error,
+    info,
     warn,
 };
 
 // Setup
 const SETUP_TABLES_SQL: &str = include_str!("../sql/setup_tables.sql");
 
+/// Migrations, applied in order after the tables are set up.
+///
+/// The number of applied migrations is stored in `PRAGMA user_version`.
+/// Only ever add migrations to the end of this list.
+const MIGRATIONS: &[&str] = &[
+    include_str!("../sql/migrations/0001_tic_tac_toe_rating.sql"),
+    include_str!("../sql/migrations/0002_tic_tac_toe_history.sql"),
+    include_str!("../sql/migrations/0003_tic_tac_toe_expiry.sql"),
+    include_str!("../sql/migrations/0004_tic_tac_toe_difficulty.sql"),
+    include_str!("../sql/migrations/0005_connect_four.sql"),
+    include_str!("../sql/migrations/0006_tic_tac_toe_theme.sql"),
+    include_str!("../sql/migrations/0007_tic_tac_toe_player_indexes.sql"),
+];
+
 static LOGGER_INIT: Lazy<Result<(), Arc<rusqlite::Error>>> = Lazy::new(|| {
     // Safety:
     // 1. `sqlite_logger_func` is threadsafe.
@@ -41,6 +64,34 @@ fn sqlite_logger_func(error_code: c_int, msg: &str) {
     warn!("sqlite error code ({}): {}", error_code, msg);
 }
 
+/// Apply the migrations that were not applied yet
+fn apply_migrations(db: &mut rusqlite::Connection) -> anyhow::Result<()> {
+    let version: u32 = db
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .context("failed to get database version")?;
+    let version = usize::try_from(version)?;
+
+    if version > MIGRATIONS.len() {
+        warn!(
+            "database version {version} is newer than the latest known version {}",
+            MIGRATIONS.len()
+        );
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let new_version = u32::try_from(i + 1)?;
+        info!("migrating database to version {new_version}");
+
+        let txn = db.transaction()?;
+        txn.execute_batch(migration)
+            .with_context(|| format!("failed to apply migration {new_version}"))?;
+        txn.pragma_update(None, "user_version", new_version)?;
+        txn.commit().context("failed to commit migration")?;
+    }
+
+    Ok(())
+}
+
 /// The database
 #[derive(Clone, Debug)]
 pub struct Database {
@@ -77,6 +128,7 @@ impl Database {
         let db = async_rusqlite::Database::blocking_open(path.as_ref(), create_if_missing, |db| {
             db.execute_batch(SETUP_TABLES_SQL)
                 .context("failed to setup database")?;
+            apply_migrations(db).context("failed to migrate database")?;
             Ok(())
         })
         .context("failed to open database")?;
@@ -0,0 +1,438 @@
+use crate::database::{
+    model::{
+        ConnectFourGame,
+        ConnectFourPlayer,
+        ConnectFourScore,
+        DatabaseConnectFourBoard,
+        MaybeGuildString,
+    },
+    Database,
+};
+use anyhow::Context;
+use connect_four::Team;
+use rusqlite::{
+    named_params,
+    params,
+    OptionalExtension,
+    TransactionBehavior,
+};
+use serenity::model::prelude::*;
+
+// Connect Four SQL
+const DELETE_CONNECT_FOUR_GAME_SQL: &str = include_str!("../../sql/delete_connect_four_game.sql");
+const UPDATE_CONNECT_FOUR_GAME_SQL: &str = include_str!("../../sql/update_connect_four_game.sql");
+const CREATE_CONNECT_FOUR_GAME_SQL: &str = include_str!("../../sql/create_connect_four_game.sql");
+const GET_CONNECT_FOUR_GAME_SQL: &str = include_str!("../../sql/get_connect_four_game.sql");
+const CHECK_IN_CONNECT_FOUR_GAME_SQL: &str =
+    include_str!("../../sql/check_in_connect_four_game.sql");
+const CREATE_DEFAULT_SCORE_CONNECT_FOUR_SQL: &str =
+    include_str!("../../sql/create_default_score_connect_four.sql");
+const INCREMENT_TIES_SCORE_CONNECT_FOUR_SQL: &str =
+    include_str!("../../sql/increment_ties_score_connect_four.sql");
+const INCREMENT_WINS_SCORE_CONNECT_FOUR_SQL: &str =
+    include_str!("../../sql/increment_wins_score_connect_four.sql");
+const INCREMENT_LOSSES_SCORE_CONNECT_FOUR_SQL: &str =
+    include_str!("../../sql/increment_losses_score_connect_four.sql");
+const INCREMENT_CONCEDES_SCORE_CONNECT_FOUR_SQL: &str =
+    include_str!("../../sql/increment_concedes_score_connect_four.sql");
+const GET_CONNECT_FOUR_SCORE_SQL: &str = include_str!("../../sql/get_connect_four_score.sql");
+
+/// The depth of the computer's search
+const COMPUTER_DEPTH: u8 = 10;
+
+/// Error that may occur while creating a connect four game
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectFourCreateGameError {
+    /// The author is in a game
+    #[error("the author is in a game")]
+    AuthorInGame,
+
+    /// The opponent is in a game
+    #[error("the opponent is in a game")]
+    OpponentInGame,
+
+    /// Error accessing the database
+    #[error("database error")]
+    Database(#[source] anyhow::Error),
+}
+
+/// Error that may occur while performing a connect four move
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectFourTryMoveError {
+    /// The user is not in a game
+    #[error("not in a game")]
+    NotInAGame,
+
+    /// It is not the user's turn
+    #[error("not the user's turn to move")]
+    InvalidTurn,
+
+    /// The move is invalid
+    #[error("the move is not valid")]
+    InvalidMove,
+
+    /// Error accessing the database
+    #[error("database error")]
+    Database(#[source] anyhow::Error),
+}
+
+/// The response for making a connect four move
+#[derive(Debug, Copy, Clone)]
+pub enum ConnectFourTryMoveResponse {
+    /// There was a winner
+    Winner {
+        game: ConnectFourGame,
+        winner: ConnectFourPlayer,
+        loser: ConnectFourPlayer,
+    },
+    /// There was a tie
+    Tie { game: ConnectFourGame },
+    /// The next turn executed
+    NextTurn { game: ConnectFourGame },
+}
+
+fn get_connect_four_game(
+    txn: &rusqlite::Transaction<'_>,
+    guild_id: MaybeGuildString,
+    player: ConnectFourPlayer,
+) -> rusqlite::Result<Option<(i64, ConnectFourGame)>> {
+    txn.prepare_cached(GET_CONNECT_FOUR_GAME_SQL)?
+        .query_row(
+            named_params! {
+                ":guild_id": guild_id,
+                ":user_id": player,
+            },
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    ConnectFourGame {
+                        board: row.get::<_, DatabaseConnectFourBoard>(1)?.0,
+                        red_player: row.get(2)?,
+                        yellow_player: row.get(3)?,
+                    },
+                ))
+            },
+        )
+        .optional()
+}
+
+fn update_connect_four_game(
+    txn: &rusqlite::Transaction<'_>,
+    id: i64,
+    board: connect_four::Board,
+) -> rusqlite::Result<()> {
+    txn.prepare_cached(UPDATE_CONNECT_FOUR_GAME_SQL)?
+        .execute(params![DatabaseConnectFourBoard(board), id])?;
+    Ok(())
+}
+
+fn delete_connect_four_game(txn: &rusqlite::Transaction<'_>, id: i64) -> rusqlite::Result<()> {
+    txn.prepare_cached(DELETE_CONNECT_FOUR_GAME_SQL)?
+        .execute([id])?;
+    Ok(())
+}
+
+/// Try to make a user's score data
+fn create_user_score_data(
+    txn: &rusqlite::Transaction<'_>,
+    guild_id: MaybeGuildString,
+    user_id: UserId,
+) -> rusqlite::Result<()> {
+    txn.prepare_cached(CREATE_DEFAULT_SCORE_CONNECT_FOUR_SQL)?
+        .execute(params![guild_id, i64::from(user_id)])?;
+
+    Ok(())
+}
+
+/// Set a connect four game as a draw as part of a larger transaction, consuming it.
+fn set_draw_connect_four_game(
+    txn: rusqlite::Transaction<'_>,
+    id: i64,
+    guild_id: MaybeGuildString,
+    game: ConnectFourGame,
+) -> anyhow::Result<()> {
+    delete_connect_four_game(&txn, id).context("failed to delete game")?;
+
+    if let (ConnectFourPlayer::User(red_player), ConnectFourPlayer::User(yellow_player)) =
+        (game.red_player, game.yellow_player)
+    {
+        create_user_score_data(&txn, guild_id, red_player)?;
+        create_user_score_data(&txn, guild_id, yellow_player)?;
+
+        txn.prepare_cached(INCREMENT_TIES_SCORE_CONNECT_FOUR_SQL)?
+            .execute(params![
+                guild_id,
+                i64::from(red_player),
+                i64::from(yellow_player)
+            ])?;
+    }
+
+    txn.commit().context("failed to commit")?;
+
+    Ok(())
+}
+
+/// Set a connect four game as a win as part of a larger transaction, consuming it.
+fn set_win_connect_four_game(
+    txn: rusqlite::Transaction<'_>,
+    id: i64,
+    guild_id: MaybeGuildString,
+    game: ConnectFourGame,
+    winner_team: Team,
+) -> anyhow::Result<()> {
+    let winner = game.get_player(winner_team);
+    let loser = game.get_player(winner_team.inverse());
+
+    delete_connect_four_game(&txn, id).context("failed to delete game")?;
+
+    if let (ConnectFourPlayer::User(winner), ConnectFourPlayer::User(loser)) = (winner, loser) {
+        create_user_score_data(&txn, guild_id, winner)?;
+        create_user_score_data(&txn, guild_id, loser)?;
+
+        txn.prepare_cached(INCREMENT_WINS_SCORE_CONNECT_FOUR_SQL)?
+            .execute(params![guild_id, i64::from(winner)])?;
+        txn.prepare_cached(INCREMENT_LOSSES_SCORE_CONNECT_FOUR_SQL)?
+            .execute(params![guild_id, i64::from(loser)])?;
+    }
+
+    txn.commit().context("failed to commit")?;
+
+    Ok(())
+}
+
+/// Make the computer's move on a board
+fn get_computer_move(board: connect_four::Board) -> u8 {
+    let (_score, column) = connect_four::minimax(board, COMPUTER_DEPTH);
+    column
+}
+
+impl Database {
+    /// Create a new connect four game
+    pub async fn create_connect_four_game(
+        &self,
+        guild_id: MaybeGuildString,
+        author: ConnectFourPlayer,
+        author_team: Team,
+        opponent: ConnectFourPlayer,
+    ) -> Result<ConnectFourGame, ConnectFourCreateGameError> {
+        let (red_player, yellow_player) = if author_team == Team::Red {
+            (author, opponent)
+        } else {
+            (opponent, author)
+        };
+
+        self.access_db(move |db| {
+            let txn = db
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .context("failed to create transaction")
+                .map_err(ConnectFourCreateGameError::Database)?;
+
+            let check_in_game_result: Option<(ConnectFourPlayer, ConnectFourPlayer)> = txn
+                .prepare_cached(CHECK_IN_CONNECT_FOUR_GAME_SQL)
+                .context("failed to prepare query")
+                .map_err(ConnectFourCreateGameError::Database)?
+                .query_row(
+                    named_params! {
+                        ":guild_id": guild_id,
+                        ":author": author,
+                        ":opponent": opponent,
+                    },
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .context("failed to query if in game")
+                .map_err(ConnectFourCreateGameError::Database)?;
+
+            if let Some((maybe_red_player_in_game, maybe_yellow_player_in_game)) =
+                check_in_game_result
+            {
+                if maybe_red_player_in_game == author || maybe_yellow_player_in_game == author {
+                    return Err(ConnectFourCreateGameError::AuthorInGame);
+                }
+
+                if maybe_red_player_in_game == opponent || maybe_yellow_player_in_game == opponent {
+                    return Err(ConnectFourCreateGameError::OpponentInGame);
+                }
+            }
+
+            let mut game = ConnectFourGame::new(red_player, yellow_player);
+
+            if red_player.is_computer() {
+                game.board = game.board.play(get_computer_move(game.board));
+            }
+
+            txn.prepare_cached(CREATE_CONNECT_FOUR_GAME_SQL)
+                .context("failed to prepare query")
+                .map_err(ConnectFourCreateGameError::Database)?
+                .execute(params![
+                    DatabaseConnectFourBoard(game.board),
+                    red_player,
+                    yellow_player,
+                    guild_id,
+                ])
+                .context("failed to create game in database")
+                .map_err(ConnectFourCreateGameError::Database)?;
+
+            txn.commit()
+                .context("failed to commit")
+                .map_err(ConnectFourCreateGameError::Database)?;
+
+            Ok(game)
+        })
+        .await
+        .context("database access failed to join")
+        .map_err(ConnectFourCreateGameError::Database)?
+    }
+
+    /// Try to make a connect four move
+    pub async fn try_connect_four_move(
+        &self,
+        guild_id: MaybeGuildString,
+        player: ConnectFourPlayer,
+        column: u8,
+    ) -> Result<ConnectFourTryMoveResponse, ConnectFourTryMoveError> {
+        self.access_db(move |db| {
+            let txn = db
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .context("failed to create transaction")
+                .map_err(ConnectFourTryMoveError::Database)?;
+
+            let (id, mut game) = get_connect_four_game(&txn, guild_id, player)
+                .context("failed to get game")
+                .map_err(ConnectFourTryMoveError::Database)?
+                .ok_or(ConnectFourTryMoveError::NotInAGame)?;
+
+            if player != game.get_player_turn() {
+                return Err(ConnectFourTryMoveError::InvalidTurn);
+            }
+
+            if !game.try_move(column) {
+                return Err(ConnectFourTryMoveError::InvalidMove);
+            }
+
+            // The computer moves right after the player,
+            // so the board only has to be stored once the game continues.
+            if game.get_player_turn() == ConnectFourPlayer::Computer
+                && game.board.get_winner().is_none()
+                && !game.board.is_draw()
+            {
+                game.board = game.board.play(get_computer_move(game.board));
+            }
+
+            if let Some(winner_team) = game.board.get_winner() {
+                let winner = game.get_player(winner_team);
+                let loser = game.get_player(winner_team.inverse());
+
+                set_win_connect_four_game(txn, id, guild_id, game, winner_team)
+                    .map_err(ConnectFourTryMoveError::Database)?;
+
+                return Ok(ConnectFourTryMoveResponse::Winner {
+                    game,
+                    winner,
+                    loser,
+                });
+            }
+
+            if game.board.is_draw() {
+                set_draw_connect_four_game(txn, id, guild_id, game)
+                    .map_err(ConnectFourTryMoveError::Database)?;
+                return Ok(ConnectFourTryMoveResponse::Tie { game });
+            }
+
+            update_connect_four_game(&txn, id, game.board)
+                .context("failed to update game")
+                .map_err(ConnectFourTryMoveError::Database)?;
+            txn.commit()
+                .context("failed to commit")
+                .map_err(ConnectFourTryMoveError::Database)?;
+
+            Ok(ConnectFourTryMoveResponse::NextTurn { game })
+        })
+        .await
+        .context("database access failed to join")
+        .map_err(ConnectFourTryMoveError::Database)?
+    }
+
+    /// Try to get a connect four game by guild and player
+    pub async fn get_connect_four_game(
+        &self,
+        guild_id: MaybeGuildString,
+        player: ConnectFourPlayer,
+    ) -> anyhow::Result<Option<ConnectFourGame>> {
+        self.access_db(move |db| {
+            let txn = db.transaction()?;
+            let ret = get_connect_four_game(&txn, guild_id, player).context("failed to query")?;
+            txn.commit()
+                .context("failed to commit")
+                .map(|_| ret.map(|ret| ret.1))
+        })
+        .await?
+    }
+
+    /// Try to concede a Connect Four game.
+    ///
+    /// # Returns
+    /// Returns the game if it existed
+    pub async fn concede_connect_four_game(
+        &self,
+        guild_id: MaybeGuildString,
+        player: UserId,
+    ) -> anyhow::Result<Option<ConnectFourGame>> {
+        self.access_db(move |db| {
+            let txn = db.transaction()?;
+            let ret =
+                get_connect_four_game(&txn, guild_id, player.into()).context("failed to query")?;
+
+            if let Some((id, game)) = ret {
+                delete_connect_four_game(&txn, id).context("failed to delete game")?;
+
+                let conceding_team = if game.red_player == ConnectFourPlayer::from(player) {
+                    Team::Red
+                } else {
+                    Team::Yellow
+                };
+                let opponent = game.get_player(conceding_team.inverse());
+
+                if let ConnectFourPlayer::User(opponent) = opponent {
+                    create_user_score_data(&txn, guild_id, player)?;
+                    create_user_score_data(&txn, guild_id, opponent)?;
+
+                    txn.prepare_cached(INCREMENT_CONCEDES_SCORE_CONNECT_FOUR_SQL)?
+                        .execute(params![guild_id, i64::from(player)])?;
+                    txn.prepare_cached(INCREMENT_WINS_SCORE_CONNECT_FOUR_SQL)?
+                        .execute(params![guild_id, i64::from(opponent)])?;
+                }
+            }
+
+            txn.commit()
+                .context("failed to commit")
+                .map(|_| ret.map(|ret| ret.1))
+        })
+        .await?
+    }
+
+    /// Get the user's Connect Four scores
+    pub async fn get_connect_four_score(
+        &self,
+        guild_id: MaybeGuildString,
+        player: UserId,
+    ) -> anyhow::Result<ConnectFourScore> {
+        self.access_db(move |db| {
+            let txn = db.transaction()?;
+            create_user_score_data(&txn, guild_id, player)?;
+            let ret = txn.prepare_cached(GET_CONNECT_FOUR_SCORE_SQL)?.query_row(
+                params![guild_id, i64::from(player)],
+                |row| {
+                    Ok(ConnectFourScore {
+                        wins: row.get(0)?,
+                        losses: row.get(1)?,
+                        ties: row.get(2)?,
+                        concedes: row.get(3)?,
+                    })
+                },
+            )?;
+            txn.commit().context("failed to commit").map(|_| ret)
+        })
+        .await?
+    }
+}
@@ -37,6 +37,37 @@ impl FromSql for DatabaseUserId {
     }
 }
 
+/// A wrapper for a Tic-Tac-Toe difficulty
+struct DatabaseDifficulty(tic_tac_toe::Difficulty);
+
+impl FromSql for DatabaseDifficulty {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .as_str()?
+            .parse()
+            .map(Self)
+            .map_err(|error| FromSqlError::Other(Box::new(error)))
+    }
+}
+
+/// A wrapper for a serenity channel id
+struct DatabaseChannelId(ChannelId);
+
+impl FromSql for DatabaseChannelId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        // This is not heavy
+        #[allow(clippy::or_fun_call)]
+        let value = value
+            .as_i64()
+            .map(i64::to_ne_bytes)
+            .map(u64::from_ne_bytes)
+            .map(NonZeroU64::new)?
+            .ok_or(FromSqlError::OutOfRange(0))?;
+
+        Ok(Self(ChannelId::from(value)))
+    }
+}
+
 /// A Tic-Tac-Toe game
 #[derive(Debug, Copy, Clone)]
 pub struct TicTacToeGame {
@@ -46,18 +77,41 @@ pub struct TicTacToeGame {
     pub x_player: TicTacToePlayer,
     /// The o player
     pub o_player: TicTacToePlayer,
+    /// The difficulty of the computer.
+    ///
+    /// This is `None` for games between users.
+    pub difficulty: Option<tic_tac_toe::Difficulty>,
 }
 
 impl TicTacToeGame {
     /// Make a new [`TicTacToeGame`].
-    pub(super) fn new(x_player: TicTacToePlayer, o_player: TicTacToePlayer) -> Self {
+    ///
+    /// The difficulty is only kept if one of the players is the computer.
+    pub(super) fn new(
+        x_player: TicTacToePlayer,
+        o_player: TicTacToePlayer,
+        difficulty: tic_tac_toe::Difficulty,
+    ) -> Self {
+        let has_computer = x_player.is_computer() || o_player.is_computer();
+
         Self {
             board: Default::default(),
             x_player,
             o_player,
+            difficulty: has_computer.then_some(difficulty),
         }
     }
 
+    /// Get the difficulty from a database row
+    pub(super) fn get_difficulty_from_row(
+        row: &rusqlite::Row<'_>,
+        index: usize,
+    ) -> rusqlite::Result<Option<tic_tac_toe::Difficulty>> {
+        Ok(row
+            .get::<_, Option<DatabaseDifficulty>>(index)?
+            .map(|difficulty| difficulty.0))
+    }
+
     /// Get whos turn it is
     pub fn get_team_turn(&self) -> tic_tac_toe::Team {
         self.board.get_turn()
@@ -119,23 +173,23 @@ impl TicTacToeGame {
 }
 
 #[derive(Debug, Clone)]
-pub struct TicTacToePlayerParseError(std::num::ParseIntError);
+pub struct GamePlayerParseError(std::num::ParseIntError);
 
-impl std::fmt::Display for TicTacToePlayerParseError {
+impl std::fmt::Display for GamePlayerParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         "invalid player".fmt(f)
     }
 }
 
-impl std::error::Error for TicTacToePlayerParseError {
+impl std::error::Error for GamePlayerParseError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         Some(&self.0)
     }
 }
 
-/// A player of Tic-Tac-Toe
+/// A player of a game, like Tic-Tac-Toe or Connect Four
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub enum TicTacToePlayer {
+pub enum GamePlayer {
     /// AI player
     Computer,
 
@@ -143,7 +197,7 @@ pub enum TicTacToePlayer {
     User(UserId),
 }
 
-impl TicTacToePlayer {
+impl GamePlayer {
     /// Check if this player is a computer
     pub fn is_computer(self) -> bool {
         matches!(self, Self::Computer)
@@ -163,17 +217,17 @@ impl TicTacToePlayer {
     }
 }
 
-impl From<TicTacToePlayer> for Cow<'static, str> {
-    fn from(player: TicTacToePlayer) -> Self {
+impl From<GamePlayer> for Cow<'static, str> {
+    fn from(player: GamePlayer) -> Self {
         match player {
-            TicTacToePlayer::Computer => "computer".into(),
-            TicTacToePlayer::User(id) => id.to_string().into(),
+            GamePlayer::Computer => "computer".into(),
+            GamePlayer::User(id) => id.to_string().into(),
         }
     }
 }
 
-impl FromStr for TicTacToePlayer {
-    type Err = TicTacToePlayerParseError;
+impl FromStr for GamePlayer {
+    type Err = GamePlayerParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         if input.eq_ignore_ascii_case("computer") {
@@ -181,19 +235,19 @@ impl FromStr for TicTacToePlayer {
         } else if let Some(user_id) = parse_user_mention(input) {
             Ok(Self::User(user_id))
         } else {
-            let user_id: NonZeroU64 = input.parse().map_err(TicTacToePlayerParseError)?;
+            let user_id: NonZeroU64 = input.parse().map_err(GamePlayerParseError)?;
             Ok(Self::User(UserId::from(user_id)))
         }
     }
 }
 
-impl From<UserId> for TicTacToePlayer {
+impl From<UserId> for GamePlayer {
     fn from(user_id: UserId) -> Self {
         Self::User(user_id)
     }
 }
 
-impl ToSql for TicTacToePlayer {
+impl ToSql for GamePlayer {
     fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
         match self {
             Self::Computer => Ok(ToSqlOutput::Borrowed(ValueRef::Null)),
@@ -202,7 +256,7 @@ impl ToSql for TicTacToePlayer {
     }
 }
 
-impl FromSql for TicTacToePlayer {
+impl FromSql for GamePlayer {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value {
             ValueRef::Integer(int) => {
@@ -219,10 +273,16 @@ impl FromSql for TicTacToePlayer {
     }
 }
 
+/// A player of Tic-Tac-Toe
+pub type TicTacToePlayer = GamePlayer;
+
+/// A player of Connect Four
+pub type ConnectFourPlayer = GamePlayer;
+
 /// A String wrapper for a [`GuildId`]
 ///
 /// This is "[u64].to_string()" if a guild, or "empty" if not.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct MaybeGuildString {
     pub guild_id: Option<GuildId>,
 }
@@ -261,7 +321,7 @@ impl FromSql for MaybeGuildString {
 }
 
 /// Tic-Tac-Toe scores
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TicTacToeScore {
     /// Wins
     pub wins: u64,
@@ -271,13 +331,54 @@ pub struct TicTacToeScore {
     pub ties: u64,
     /// The number of times the player has conceded
     pub concedes: u64,
+    /// The rating
+    pub rating: f64,
+    /// Scores against the computer, by difficulty.
+    ///
+    /// These are not included in the other scores.
+    pub computer: Vec<TicTacToeComputerScore>,
+}
+
+/// Tic-Tac-Toe scores against the computer
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TicTacToeComputerScore {
+    /// The difficulty
+    pub difficulty: tic_tac_toe::Difficulty,
+    /// Wins
+    pub wins: u64,
+    /// Losses
+    pub losses: u64,
+    /// Ties
+    pub ties: u64,
+    /// The number of times the player has conceded
+    pub concedes: u64,
+}
+
+impl TicTacToeComputerScore {
+    /// Parse this from a rusqlite row.
+    ///
+    /// Data must be in the following order:
+    /// 1. difficulty
+    /// 2. wins
+    /// 3. losses
+    /// 4. ties
+    /// 5. concedes
+    pub(crate) fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            difficulty: row.get::<_, DatabaseDifficulty>(0)?.0,
+            wins: row.get(1)?,
+            losses: row.get(2)?,
+            ties: row.get(3)?,
+            concedes: row.get(4)?,
+        })
+    }
 }
 
 /// Top Player Tic-Tac-Toe scores
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TicTacToeTopPlayerScore {
-    /// The score
-    pub score: i64,
+    /// The rating
+    pub rating: f64,
     /// The player
     pub player: UserId,
     /// Wins
@@ -294,14 +395,14 @@ impl TicTacToeTopPlayerScore {
     /// Parse this from a rusqlite row.
     ///
     /// Data must be in the following order:
-    /// 1. score
+    /// 1. rating
     /// 2. player
     /// 3. wins
     /// 4. losses
     /// 5. ties
     /// 6. concedes
     pub(crate) fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
-        let score = row.get(0)?;
+        let rating = row.get(0)?;
 
         let player = row.get::<_, DatabaseUserId>(1)?.0;
 
@@ -314,7 +415,7 @@ impl TicTacToeTopPlayerScore {
         let concedes = row.get(5)?;
 
         Ok(TicTacToeTopPlayerScore {
-            score,
+            rating,
             player,
             wins,
             losses,
@@ -324,6 +425,481 @@ impl TicTacToeTopPlayerScore {
     }
 }
 
+/// The Tic-Tac-Toe scores of a player in one server
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TicTacToeGuildScore {
+    /// The server
+    pub guild_id: MaybeGuildString,
+    /// Wins
+    pub wins: u64,
+    /// Losses
+    pub losses: u64,
+    /// Ties
+    pub ties: u64,
+    /// The number of times the player has conceded
+    pub concedes: u64,
+    /// The rating
+    pub rating: f64,
+    /// The number of games that changed the rating, including games against the computer
+    pub rated_games: u64,
+}
+
+impl TicTacToeGuildScore {
+    /// Parse this from a rusqlite row.
+    ///
+    /// Data must be in the following order:
+    /// 1. guild_id
+    /// 2. wins
+    /// 3. losses
+    /// 4. ties
+    /// 5. concedes
+    /// 6. rating
+    /// 7. rated_games
+    pub(crate) fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            guild_id: row.get(0)?,
+            wins: row.get(1)?,
+            losses: row.get(2)?,
+            ties: row.get(3)?,
+            concedes: row.get(4)?,
+            rating: row.get(5)?,
+            rated_games: row.get(6)?,
+        })
+    }
+}
+
+/// The Tic-Tac-Toe record of a player against one opponent
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TicTacToeHeadToHeadScore {
+    /// The opponent
+    pub opponent: UserId,
+    /// Wins, including games the opponent conceded
+    pub wins: u64,
+    /// Losses
+    pub losses: u64,
+    /// Ties
+    pub ties: u64,
+    /// The number of times the player has conceded
+    pub concedes: u64,
+}
+
+impl TicTacToeHeadToHeadScore {
+    /// Parse this from a rusqlite row.
+    ///
+    /// Data must be in the following order:
+    /// 1. opponent
+    /// 2. wins
+    /// 3. losses
+    /// 4. ties
+    /// 5. concedes
+    pub(crate) fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            opponent: row.get::<_, DatabaseUserId>(0)?.0,
+            wins: row.get(1)?,
+            losses: row.get(2)?,
+            ties: row.get(3)?,
+            concedes: row.get(4)?,
+        })
+    }
+}
+
+/// The Tic-Tac-Toe scores of a player across every server.
+///
+/// Like [`TicTacToeScore`], this does not include games against the computer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicTacToeProfile {
+    /// The scores in each server, most played first
+    pub guilds: Vec<TicTacToeGuildScore>,
+    /// The longest run of wins in a row.
+    ///
+    /// This only counts games played after finished games started being recorded.
+    pub longest_win_streak: u64,
+    /// The records against the most played opponents, most played first
+    pub opponents: Vec<TicTacToeHeadToHeadScore>,
+}
+
+impl TicTacToeProfile {
+    /// Get the global rating.
+    ///
+    /// This is the average of the ratings in each server, weighted by the # of rated games played there.
+    /// This is `None` if no rated games were played.
+    pub fn get_global_rating(&self) -> Option<f64> {
+        let rated_games: u64 = self.guilds.iter().map(|score| score.rated_games).sum();
+        if rated_games == 0 {
+            return None;
+        }
+
+        let total: f64 = self
+            .guilds
+            .iter()
+            .map(|score| score.rating * score.rated_games as f64)
+            .sum();
+        Some(total / rated_games as f64)
+    }
+}
+
+/// A Tic-Tac-Toe game without a recent move
+#[derive(Debug, Copy, Clone)]
+pub struct TicTacToeInactiveGame {
+    /// The id
+    pub id: i64,
+    /// The guild
+    pub guild_id: MaybeGuildString,
+    /// The game
+    pub game: TicTacToeGame,
+    /// The channel of the last move, if known
+    pub channel_id: Option<ChannelId>,
+    /// The unix timestamp of the last move
+    pub last_move_at: i64,
+}
+
+impl TicTacToeInactiveGame {
+    /// Parse this from a rusqlite row.
+    ///
+    /// Data must be in the following order:
+    /// 1. id
+    /// 2. guild_id
+    /// 3. board
+    /// 4. x_player
+    /// 5. o_player
+    /// 6. channel_id
+    /// 7. last_move_at
+    /// 8. difficulty
+    pub(crate) fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            guild_id: row.get(1)?,
+            game: TicTacToeGame {
+                board: tic_tac_toe::Board::decode_u16(row.get(2)?),
+                x_player: row.get(3)?,
+                o_player: row.get(4)?,
+                difficulty: TicTacToeGame::get_difficulty_from_row(row, 7)?,
+            },
+            channel_id: row
+                .get::<_, Option<DatabaseChannelId>>(5)?
+                .map(|channel_id| channel_id.0),
+            last_move_at: row.get(6)?,
+        })
+    }
+}
+
+/// The result of a finished Tic-Tac-Toe game
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TicTacToeGameResult {
+    /// A team won
+    Win(tic_tac_toe::Team),
+    /// The game was a draw
+    Draw,
+    /// A team conceded
+    Concede(tic_tac_toe::Team),
+}
+
+impl ToSql for TicTacToeGameResult {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value: &'static str = match self {
+            Self::Win(tic_tac_toe::Team::X) => "x-win",
+            Self::Win(tic_tac_toe::Team::O) => "o-win",
+            Self::Draw => "draw",
+            Self::Concede(tic_tac_toe::Team::X) => "x-concede",
+            Self::Concede(tic_tac_toe::Team::O) => "o-concede",
+        };
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(value.as_bytes())))
+    }
+}
+
+impl FromSql for TicTacToeGameResult {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "x-win" => Ok(Self::Win(tic_tac_toe::Team::X)),
+            "o-win" => Ok(Self::Win(tic_tac_toe::Team::O)),
+            "draw" => Ok(Self::Draw),
+            "x-concede" => Ok(Self::Concede(tic_tac_toe::Team::X)),
+            "o-concede" => Ok(Self::Concede(tic_tac_toe::Team::O)),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// The moves of a Tic-Tac-Toe game, stored as a string of tile indices
+struct DatabaseMoves(Vec<u8>);
+
+impl FromSql for DatabaseMoves {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .as_str()?
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .and_then(|index| u8::try_from(index).ok())
+                    .filter(|index| *index < tic_tac_toe::NUM_TILES)
+                    .ok_or(FromSqlError::InvalidType)
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// A finished Tic-Tac-Toe game
+#[derive(Debug, Clone)]
+pub struct TicTacToeHistoryGame {
+    /// The id
+    pub id: i64,
+    /// The x player
+    pub x_player: TicTacToePlayer,
+    /// The o player
+    pub o_player: TicTacToePlayer,
+    /// The final board
+    pub board: tic_tac_toe::Board,
+    /// The tile indices of the moves, in order.
+    ///
+    /// This is empty for games that started before moves were recorded.
+    pub moves: Vec<u8>,
+    /// The result
+    pub result: TicTacToeGameResult,
+    /// The unix timestamp of when the game started, if known
+    pub started_at: Option<i64>,
+    /// The unix timestamp of when the game ended
+    pub ended_at: i64,
+    /// The difficulty of the computer, if it played
+    pub difficulty: Option<tic_tac_toe::Difficulty>,
+}
+
+impl TicTacToeHistoryGame {
+    /// Parse this from a rusqlite row.
+    ///
+    /// Data must be in the following order:
+    /// 1. id
+    /// 2. x_player
+    /// 3. o_player
+    /// 4. board
+    /// 5. moves
+    /// 6. result
+    /// 7. started_at
+    /// 8. ended_at
+    /// 9. difficulty
+    pub(crate) fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            x_player: row.get(1)?,
+            o_player: row.get(2)?,
+            board: tic_tac_toe::Board::decode_u16(row.get(3)?),
+            moves: row.get::<_, DatabaseMoves>(4)?.0,
+            result: row.get(5)?,
+            started_at: row.get(6)?,
+            ended_at: row.get(7)?,
+            difficulty: TicTacToeGame::get_difficulty_from_row(row, 8)?,
+        })
+    }
+
+    /// Get the player for the given team.
+    pub fn get_player(&self, team: tic_tac_toe::Team) -> TicTacToePlayer {
+        match team {
+            tic_tac_toe::Team::X => self.x_player,
+            tic_tac_toe::Team::O => self.o_player,
+        }
+    }
+
+    /// Get the board after each move, starting with an empty board.
+    ///
+    /// Games without recorded moves skip straight to the final board.
+    pub fn get_replay_boards(&self) -> Vec<tic_tac_toe::Board> {
+        let mut board = tic_tac_toe::Board::new();
+        let mut boards = vec![board];
+        for &index in self.moves.iter() {
+            board = board.set(index, Some(board.get_turn()));
+            boards.push(board);
+        }
+
+        if board != self.board {
+            boards.push(self.board);
+        }
+
+        boards
+    }
+}
+
+/// A theme for rendered Tic-Tac-Toe boards
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TicTacToeTheme {
+    /// Red and gray squares
+    #[default]
+    Classic,
+
+    /// Dark squares with colored pieces
+    Dark,
+
+    /// White squares with a grid
+    Light,
+
+    /// Like light, without tile numbers
+    Minimal,
+
+    /// Like classic, at twice the resolution
+    Large,
+}
+
+impl TicTacToeTheme {
+    /// Every theme
+    pub const ALL: [Self; 5] = [
+        Self::Classic,
+        Self::Dark,
+        Self::Light,
+        Self::Minimal,
+        Self::Large,
+    ];
+
+    /// Get this as a str
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Minimal => "minimal",
+            Self::Large => "large",
+        }
+    }
+}
+
+impl std::fmt::Display for TicTacToeTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TicTacToeThemeParseError(String);
+
+impl std::fmt::Display for TicTacToeThemeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid theme '{}'", self.0)
+    }
+}
+
+impl std::error::Error for TicTacToeThemeParseError {}
+
+impl FromStr for TicTacToeTheme {
+    type Err = TicTacToeThemeParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|theme| input.eq_ignore_ascii_case(theme.as_str()))
+            .ok_or_else(|| TicTacToeThemeParseError(input.to_string()))
+    }
+}
+
+impl ToSql for TicTacToeTheme {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(
+            self.as_str().as_bytes(),
+        )))
+    }
+}
+
+impl FromSql for TicTacToeTheme {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .as_str()?
+            .parse()
+            .map_err(|error| FromSqlError::Other(Box::new(error)))
+    }
+}
+
+/// A wrapper for a Connect Four board, stored as its [`u64`] encoding
+pub(crate) struct DatabaseConnectFourBoard(pub(crate) connect_four::Board);
+
+impl ToSql for DatabaseConnectFourBoard {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = i64::try_from(self.0.encode_u64())
+            .map_err(|error| rusqlite::Error::ToSqlConversionFailure(Box::new(error)))?;
+        Ok(ToSqlOutput::from(value))
+    }
+}
+
+impl FromSql for DatabaseConnectFourBoard {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let value = value.as_i64()?;
+        let value = u64::try_from(value).map_err(|_e| FromSqlError::OutOfRange(value))?;
+
+        Ok(Self(connect_four::Board::decode_u64(value)))
+    }
+}
+
+/// A Connect Four game
+#[derive(Debug, Copy, Clone)]
+pub struct ConnectFourGame {
+    /// The board
+    pub board: connect_four::Board,
+    /// The red player
+    pub red_player: ConnectFourPlayer,
+    /// The yellow player
+    pub yellow_player: ConnectFourPlayer,
+}
+
+impl ConnectFourGame {
+    /// Make a new [`ConnectFourGame`].
+    pub(super) fn new(red_player: ConnectFourPlayer, yellow_player: ConnectFourPlayer) -> Self {
+        Self {
+            board: Default::default(),
+            red_player,
+            yellow_player,
+        }
+    }
+
+    /// Get whos turn it is
+    pub fn get_team_turn(&self) -> connect_four::Team {
+        self.board.get_turn()
+    }
+
+    /// Get the player for the given team.
+    pub fn get_player(&self, team: connect_four::Team) -> ConnectFourPlayer {
+        match team {
+            connect_four::Team::Red => self.red_player,
+            connect_four::Team::Yellow => self.yellow_player,
+        }
+    }
+
+    /// Get the player whos turn it is
+    pub fn get_player_turn(&self) -> ConnectFourPlayer {
+        self.get_player(self.get_team_turn())
+    }
+
+    /// Try to drop a piece of the current team into a column.
+    ///
+    /// # Returns
+    /// Returns true if successful.
+    pub fn try_move(&mut self, column: u8) -> bool {
+        if column >= connect_four::WIDTH || !self.board.can_play(column) {
+            false
+        } else {
+            self.board = self.board.play(column);
+            true
+        }
+    }
+    /// Get the opponent of the given user in this [`ConnectFourGame`].
+    pub fn get_opponent(&self, player: ConnectFourPlayer) -> Option<ConnectFourPlayer> {
+        match (player == self.red_player, player == self.yellow_player) {
+            (false, false) => None,
+            (false, true) => Some(self.red_player),
+            (true, false) => Some(self.yellow_player),
+            (true, true) => Some(player), // Player is playing themselves
+        }
+    }
+}
+
+/// Connect Four scores
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ConnectFourScore {
+    /// Wins
+    pub wins: u64,
+    /// Losses
+    pub losses: u64,
+    /// Ties
+    pub ties: u64,
+    /// The number of times the player has conceded
+    pub concedes: u64,
+}
+
 bitflags! {
     /// Flags for TikTok embeds
     #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -1,12 +1,23 @@
-use crate::database::{
-    model::{
-        MaybeGuildString,
-        TicTacToeGame,
-        TicTacToePlayer,
-        TicTacToeScore,
-        TicTacToeTopPlayerScore,
+use crate::{
+    database::{
+        model::{
+            MaybeGuildString,
+            TicTacToeComputerScore,
+            TicTacToeGame,
+            TicTacToeGameResult,
+            TicTacToeGuildScore,
+            TicTacToeHeadToHeadScore,
+            TicTacToeHistoryGame,
+            TicTacToeInactiveGame,
+            TicTacToePlayer,
+            TicTacToeProfile,
+            TicTacToeScore,
+            TicTacToeTheme,
+            TicTacToeTopPlayerScore,
+        },
+        Database,
     },
-    Database,
+    util::elo,
 };
 use anyhow::Context;
 use rusqlite::{
@@ -16,7 +27,11 @@ use rusqlite::{
     TransactionBehavior,
 };
 use serenity::model::prelude::*;
-use tic_tac_toe::Board;
+use std::time::Duration;
+use tic_tac_toe::{
+    Board,
+    Team,
+};
 
 // Tic-Tac-Toe SQL
 const DELETE_TIC_TAC_TOE_GAME_SQL: &str = include_str!("../../sql/delete_tic_tac_toe_game.sql");
@@ -37,6 +52,41 @@ const INCREMENT_CONCEDES_SCORE_TIC_TAC_TOE_SQL: &str =
 const GET_TIC_TAC_TOE_SCORE_SQL: &str = include_str!("../../sql/get_tic_tac_toe_score.sql");
 const GET_TOP_TIC_TAC_TOE_SCORES_SQL: &str =
     include_str!("../../sql/get_top_tic_tac_toe_scores.sql");
+const GET_GLOBAL_TOP_TIC_TAC_TOE_SCORES_SQL: &str =
+    include_str!("../../sql/get_global_top_tic_tac_toe_scores.sql");
+const GET_TIC_TAC_TOE_GUILD_SCORES_SQL: &str =
+    include_str!("../../sql/get_tic_tac_toe_guild_scores.sql");
+const GET_TIC_TAC_TOE_HEAD_TO_HEAD_SCORES_SQL: &str =
+    include_str!("../../sql/get_tic_tac_toe_head_to_head_scores.sql");
+const GET_TIC_TAC_TOE_LONGEST_WIN_STREAK_SQL: &str =
+    include_str!("../../sql/get_tic_tac_toe_longest_win_streak.sql");
+const GET_TIC_TAC_TOE_RATING_SQL: &str = include_str!("../../sql/get_tic_tac_toe_rating.sql");
+const SET_TIC_TAC_TOE_RATING_SQL: &str = include_str!("../../sql/set_tic_tac_toe_rating.sql");
+const ARCHIVE_TIC_TAC_TOE_GAME_SQL: &str = include_str!("../../sql/archive_tic_tac_toe_game.sql");
+const GET_TIC_TAC_TOE_HISTORY_SQL: &str = include_str!("../../sql/get_tic_tac_toe_history.sql");
+const GET_TIC_TAC_TOE_HISTORY_GAME_SQL: &str =
+    include_str!("../../sql/get_tic_tac_toe_history_game.sql");
+const CLAIM_TIC_TAC_TOE_INACTIVITY_WARNINGS_SQL: &str =
+    include_str!("../../sql/claim_tic_tac_toe_inactivity_warnings.sql");
+const GET_EXPIRED_TIC_TAC_TOE_GAMES_SQL: &str =
+    include_str!("../../sql/get_expired_tic_tac_toe_games.sql");
+const GET_TIC_TAC_TOE_COMPUTER_SCORES_SQL: &str =
+    include_str!("../../sql/get_tic_tac_toe_computer_scores.sql");
+const GET_TIC_TAC_TOE_THEME_SQL: &str = include_str!("../../sql/get_tic_tac_toe_theme.sql");
+const SET_TIC_TAC_TOE_THEME_SQL: &str = include_str!("../../sql/set_tic_tac_toe_theme.sql");
+
+/// Get the fixed rating of the computer at a difficulty.
+///
+/// New players start at 1500.
+/// Hard plays perfectly, so it is rated well above them,
+/// while easy makes enough mistakes to be rated below them.
+fn get_computer_rating(difficulty: tic_tac_toe::Difficulty) -> f64 {
+    match difficulty {
+        tic_tac_toe::Difficulty::Easy => 1000.0,
+        tic_tac_toe::Difficulty::Medium => 1500.0,
+        tic_tac_toe::Difficulty::Hard => 2000.0,
+    }
+}
 
 /// Error that may occur while creating a tic-tac-toe game
 #[derive(Debug, thiserror::Error)]
@@ -107,6 +157,7 @@ fn get_tic_tac_toe_game(
                         board: Board::decode_u16(row.get(1)?),
                         x_player: row.get(2)?,
                         o_player: row.get(3)?,
+                        difficulty: TicTacToeGame::get_difficulty_from_row(row, 4)?,
                     },
                 ))
             },
@@ -114,13 +165,32 @@ fn get_tic_tac_toe_game(
         .optional()
 }
 
+/// Update the board of a game, recording the move that was made and where
 fn update_tic_tac_toe_game(
     txn: &rusqlite::Transaction<'_>,
     id: i64,
     board: Board,
+    move_index: u8,
+    channel_id: ChannelId,
 ) -> rusqlite::Result<()> {
     txn.prepare_cached(UPDATE_TIC_TAC_TOE_GAME_SQL)?
-        .execute(params![board.encode_u16(), id])?;
+        .execute(params![
+            board.encode_u16(),
+            move_index.to_string(),
+            i64::from(channel_id),
+            id
+        ])?;
+    Ok(())
+}
+
+/// Copy a game into the history with the given result
+fn archive_tic_tac_toe_game(
+    txn: &rusqlite::Transaction<'_>,
+    id: i64,
+    result: TicTacToeGameResult,
+) -> rusqlite::Result<()> {
+    txn.prepare_cached(ARCHIVE_TIC_TAC_TOE_GAME_SQL)?
+        .execute(params![result, id])?;
     Ok(())
 }
 
@@ -142,6 +212,100 @@ fn create_user_score_data(
     Ok(())
 }
 
+/// Get a player's rating.
+///
+/// `difficulty` is the difficulty of the computer, if it is playing.
+fn get_tic_tac_toe_rating(
+    txn: &rusqlite::Transaction<'_>,
+    guild_id: MaybeGuildString,
+    player: TicTacToePlayer,
+    difficulty: Option<tic_tac_toe::Difficulty>,
+) -> rusqlite::Result<f64> {
+    match player {
+        TicTacToePlayer::Computer => Ok(get_computer_rating(difficulty.unwrap_or_default())),
+        TicTacToePlayer::User(user_id) => {
+            create_user_score_data(txn, guild_id, user_id)?;
+            txn.prepare_cached(GET_TIC_TAC_TOE_RATING_SQL)?
+                .query_row(params![guild_id, i64::from(user_id)], |row| row.get(0))
+        }
+    }
+}
+
+/// Update the ratings of the players of a finished game.
+///
+/// `score` is the score of `player`: 1.0 for a win, 0.5 for a tie, and 0.0 for a loss.
+/// `difficulty` is the difficulty of the computer, if it played.
+/// The rating of the computer does not change.
+/// Users also have the game added to their rated games,
+/// as their other scores only count games against other users.
+fn update_tic_tac_toe_ratings(
+    txn: &rusqlite::Transaction<'_>,
+    guild_id: MaybeGuildString,
+    player: TicTacToePlayer,
+    opponent: TicTacToePlayer,
+    difficulty: Option<tic_tac_toe::Difficulty>,
+    score: f64,
+) -> rusqlite::Result<()> {
+    // Playing yourself should not change your rating
+    if player == opponent {
+        return Ok(());
+    }
+
+    let player_rating = get_tic_tac_toe_rating(txn, guild_id, player, difficulty)?;
+    let opponent_rating = get_tic_tac_toe_rating(txn, guild_id, opponent, difficulty)?;
+    let (player_rating, opponent_rating) =
+        elo::update_ratings(player_rating, opponent_rating, score);
+
+    for (player, rating) in [(player, player_rating), (opponent, opponent_rating)] {
+        if let TicTacToePlayer::User(user_id) = player {
+            txn.prepare_cached(SET_TIC_TAC_TOE_RATING_SQL)?
+                .execute(params![rating, guild_id, i64::from(user_id)])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Concede a tic-tac-toe game as part of a larger transaction.
+///
+/// Concedes count as losses for ratings.
+fn concede_tic_tac_toe_game(
+    txn: &rusqlite::Transaction<'_>,
+    id: i64,
+    guild_id: MaybeGuildString,
+    game: TicTacToeGame,
+    conceding_team: Team,
+) -> anyhow::Result<()> {
+    let conceding_player = game.get_player(conceding_team);
+    let opponent = game.get_player(conceding_team.inverse());
+
+    archive_tic_tac_toe_game(txn, id, TicTacToeGameResult::Concede(conceding_team))
+        .context("failed to archive game")?;
+    delete_tic_tac_toe_game(txn, id).context("failed to delete game")?;
+
+    update_tic_tac_toe_ratings(
+        txn,
+        guild_id,
+        conceding_player,
+        opponent,
+        game.difficulty,
+        0.0,
+    )
+    .context("failed to update ratings")?;
+
+    if let (TicTacToePlayer::User(conceding_player), TicTacToePlayer::User(opponent)) =
+        (conceding_player, opponent)
+    {
+        txn.prepare_cached(INCREMENT_CONCEDES_SCORE_TIC_TAC_TOE_SQL)?
+            .execute(params![guild_id, i64::from(conceding_player)])?;
+
+        txn.prepare_cached(INCREMENT_WINS_SCORE_TIC_TAC_TOE_SQL)?
+            .execute(params![guild_id, i64::from(opponent)])?;
+    }
+
+    Ok(())
+}
+
 /// Set a tic-tac-toe game as a draw as part of a larger transaction, consuming it.
 fn set_draw_tic_tac_toe_game(
     txn: rusqlite::Transaction<'_>,
@@ -149,7 +313,18 @@ fn set_draw_tic_tac_toe_game(
     guild_id: MaybeGuildString,
     game: TicTacToeGame,
 ) -> anyhow::Result<()> {
+    archive_tic_tac_toe_game(&txn, id, TicTacToeGameResult::Draw)
+        .context("failed to archive game")?;
     delete_tic_tac_toe_game(&txn, id).context("failed to delete game")?;
+    update_tic_tac_toe_ratings(
+        &txn,
+        guild_id,
+        game.x_player,
+        game.o_player,
+        game.difficulty,
+        0.5,
+    )
+    .context("failed to update ratings")?;
 
     if let (TicTacToePlayer::User(x_player), TicTacToePlayer::User(o_player)) =
         (game.x_player, game.o_player)
@@ -171,10 +346,17 @@ fn set_win_tic_tac_toe_game(
     txn: rusqlite::Transaction<'_>,
     id: i64,
     guild_id: MaybeGuildString,
-    winner: TicTacToePlayer,
-    loser: TicTacToePlayer,
+    game: TicTacToeGame,
+    winner_team: Team,
 ) -> anyhow::Result<()> {
+    let winner = game.get_player(winner_team);
+    let loser = game.get_player(winner_team.inverse());
+
+    archive_tic_tac_toe_game(&txn, id, TicTacToeGameResult::Win(winner_team))
+        .context("failed to archive game")?;
     delete_tic_tac_toe_game(&txn, id).context("failed to delete game")?;
+    update_tic_tac_toe_ratings(&txn, guild_id, winner, loser, game.difficulty, 1.0)
+        .context("failed to update ratings")?;
 
     if let (TicTacToePlayer::User(winner), TicTacToePlayer::User(loser)) = (winner, loser) {
         create_user_score_data(&txn, guild_id, winner)?;
@@ -199,6 +381,8 @@ impl Database {
         author: TicTacToePlayer,
         author_team: tic_tac_toe::Team,
         opponent: TicTacToePlayer,
+        difficulty: tic_tac_toe::Difficulty,
+        channel_id: ChannelId,
     ) -> Result<TicTacToeGame, TicTacToeCreateGameError> {
         let (x_player, o_player) = if author_team == tic_tac_toe::Team::X {
             (author, opponent)
@@ -238,19 +422,29 @@ impl Database {
                 }
             }
 
-            let mut game = TicTacToeGame::new(x_player, o_player);
+            let mut game = TicTacToeGame::new(x_player, o_player, difficulty);
+            let mut moves = String::new();
 
             // TODO: Iteratively perform AI steps?
             if x_player.is_computer() {
-                let (_score, index) = tic_tac_toe::minimax(game.board, tic_tac_toe::NUM_TILES);
+                let index = difficulty.choose_move(game.board, &mut rand::thread_rng());
                 game.board = game.board.set(index, Some(tic_tac_toe::Team::X));
+                moves = index.to_string();
             }
 
             let board = game.board.encode_u16();
             txn.prepare_cached(CREATE_TIC_TAC_TOE_GAME_SQL)
                 .context("failed to prepare query")
                 .map_err(TicTacToeCreateGameError::Database)?
-                .execute(params![board, x_player, o_player, guild_id])
+                .execute(params![
+                    board,
+                    x_player,
+                    o_player,
+                    guild_id,
+                    moves,
+                    i64::from(channel_id),
+                    game.difficulty.map(tic_tac_toe::Difficulty::as_str)
+                ])
                 .context("failed to create game in database")
                 .map_err(TicTacToeCreateGameError::Database)?;
 
@@ -271,6 +465,7 @@ impl Database {
         guild_id: MaybeGuildString,
         player: TicTacToePlayer,
         move_index: u8,
+        channel_id: ChannelId,
     ) -> Result<TicTacToeTryMoveResponse, TicTacToeTryMoveError> {
         self.access_db(move |db| {
             let txn = db
@@ -293,12 +488,15 @@ impl Database {
             if !move_successful {
                 return Err(TicTacToeTryMoveError::InvalidMove);
             }
+            update_tic_tac_toe_game(&txn, id, game.board, move_index, channel_id)
+                .context("failed to update game")
+                .map_err(TicTacToeTryMoveError::Database)?;
 
             if let Some(winner_team) = game.board.get_winner() {
                 let winner = game.get_player(winner_team);
                 let loser = game.get_player(winner_team.inverse());
 
-                set_win_tic_tac_toe_game(txn, id, guild_id, winner, loser)
+                set_win_tic_tac_toe_game(txn, id, guild_id, game, winner_team)
                     .map_err(TicTacToeTryMoveError::Database)?;
 
                 return Ok(TicTacToeTryMoveResponse::Winner {
@@ -316,14 +514,20 @@ impl Database {
 
             let opponent = game.get_player_turn();
             if opponent == TicTacToePlayer::Computer {
-                let (_score, index) = tic_tac_toe::minimax(game.board, tic_tac_toe::NUM_TILES);
+                let index = game
+                    .difficulty
+                    .unwrap_or_default()
+                    .choose_move(game.board, &mut rand::thread_rng());
                 game.board = game.board.set(index, Some(team_turn.inverse()));
+                update_tic_tac_toe_game(&txn, id, game.board, index, channel_id)
+                    .context("failed to update game")
+                    .map_err(TicTacToeTryMoveError::Database)?;
 
                 if let Some(winner_team) = game.board.get_winner() {
                     let winner = game.get_player(winner_team);
                     let loser = game.get_player(winner_team.inverse());
 
-                    set_win_tic_tac_toe_game(txn, id, guild_id, winner, loser)
+                    set_win_tic_tac_toe_game(txn, id, guild_id, game, winner_team)
                         .map_err(TicTacToeTryMoveError::Database)?;
 
                     return Ok(TicTacToeTryMoveResponse::Winner {
@@ -340,10 +544,6 @@ impl Database {
                 }
             }
 
-            update_tic_tac_toe_game(&txn, id, game.board)
-                .context("failed to update game")
-                .map_err(TicTacToeTryMoveError::Database)?;
-
             txn.commit()
                 .context("failed to commit")
                 .map_err(TicTacToeTryMoveError::Database)?;
@@ -386,22 +586,13 @@ impl Database {
                 get_tic_tac_toe_game(&txn, guild_id, player.into()).context("failed to query")?;
 
             if let Some((id, game)) = ret {
-                delete_tic_tac_toe_game(&txn, id).context("failed to delete game")?;
-
-                let conceding_player = TicTacToePlayer::from(player);
-                let opponent = game
-                    .get_opponent(conceding_player)
-                    .context("missing opponent")?;
+                let conceding_team = if game.x_player == TicTacToePlayer::from(player) {
+                    Team::X
+                } else {
+                    Team::O
+                };
 
-                if let (TicTacToePlayer::User(conceding_player), TicTacToePlayer::User(opponent)) =
-                    (conceding_player, opponent)
-                {
-                    txn.prepare_cached(INCREMENT_CONCEDES_SCORE_TIC_TAC_TOE_SQL)?
-                        .execute(params![guild_id, i64::from(conceding_player)])?;
-
-                    txn.prepare_cached(INCREMENT_WINS_SCORE_TIC_TAC_TOE_SQL)?
-                        .execute(params![guild_id, i64::from(opponent)])?;
-                }
+                concede_tic_tac_toe_game(&txn, id, guild_id, game, conceding_team)?;
             }
 
             txn.commit()
@@ -420,7 +611,7 @@ impl Database {
         self.access_db(move |db| {
             let txn = db.transaction()?;
             create_user_score_data(&txn, guild_id, player)?;
-            let ret = txn.prepare_cached(GET_TIC_TAC_TOE_SCORE_SQL)?.query_row(
+            let mut ret = txn.prepare_cached(GET_TIC_TAC_TOE_SCORE_SQL)?.query_row(
                 params![guild_id, i64::from(player)],
                 |row| {
                     Ok(TicTacToeScore {
@@ -428,9 +619,21 @@ impl Database {
                         losses: row.get(1)?,
                         ties: row.get(2)?,
                         concedes: row.get(3)?,
+                        rating: row.get(4)?,
+                        computer: Vec::new(),
                     })
                 },
             )?;
+            ret.computer = txn
+                .prepare_cached(GET_TIC_TAC_TOE_COMPUTER_SCORES_SQL)?
+                .query_map(
+                    named_params! {
+                        ":guild_id": guild_id,
+                        ":player": i64::from(player),
+                    },
+                    TicTacToeComputerScore::from_row,
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
             txn.commit().context("failed to commit").map(|_| ret)
         })
         .await?
@@ -451,4 +654,206 @@ impl Database {
         })
         .await?
     }
+
+    /// Get the top Tic-Tac-Toe scores across every server.
+    ///
+    /// The rating is the average of the ratings in each server,
+    /// weighted by the # of games played there.
+    pub async fn get_global_top_tic_tac_toe_scores(
+        &self,
+    ) -> anyhow::Result<Vec<TicTacToeTopPlayerScore>> {
+        self.access_db(move |db| {
+            let ret = db
+                .prepare_cached(GET_GLOBAL_TOP_TIC_TAC_TOE_SCORES_SQL)?
+                .query_map([], TicTacToeTopPlayerScore::from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(ret)
+        })
+        .await?
+    }
+
+    /// Get the Tic-Tac-Toe profile of a user, across every server
+    pub async fn get_tic_tac_toe_profile(
+        &self,
+        player: UserId,
+    ) -> anyhow::Result<TicTacToeProfile> {
+        self.access_db(move |db| {
+            let txn = db.transaction()?;
+            let guilds = txn
+                .prepare_cached(GET_TIC_TAC_TOE_GUILD_SCORES_SQL)?
+                .query_map([i64::from(player)], TicTacToeGuildScore::from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+            let longest_win_streak: u64 = txn
+                .prepare_cached(GET_TIC_TAC_TOE_LONGEST_WIN_STREAK_SQL)?
+                .query_row(
+                    named_params! {
+                        ":player": i64::from(player),
+                    },
+                    |row| row.get(0),
+                )
+                .optional()?
+                .unwrap_or(0);
+            let opponents = txn
+                .prepare_cached(GET_TIC_TAC_TOE_HEAD_TO_HEAD_SCORES_SQL)?
+                .query_map(
+                    named_params! {
+                        ":player": i64::from(player),
+                    },
+                    TicTacToeHeadToHeadScore::from_row,
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            txn.commit()
+                .context("failed to commit")
+                .map(|_| TicTacToeProfile {
+                    guilds,
+                    longest_win_streak,
+                    opponents,
+                })
+        })
+        .await?
+    }
+
+    /// Get the games that have gone without a move for `warn_after`, and were not warned about yet.
+    ///
+    /// The games are marked as warned, until the next move.
+    pub async fn claim_tic_tac_toe_inactivity_warnings(
+        &self,
+        warn_after: Duration,
+    ) -> anyhow::Result<Vec<TicTacToeInactiveGame>> {
+        let warn_after = i64::try_from(warn_after.as_secs()).context("warn time is too long")?;
+        self.access_db(move |db| {
+            let ret = db
+                .prepare_cached(CLAIM_TIC_TAC_TOE_INACTIVITY_WARNINGS_SQL)?
+                .query_map([warn_after], TicTacToeInactiveGame::from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(ret)
+        })
+        .await?
+    }
+
+    /// Concede the games that have gone without a move for `expire_after`.
+    ///
+    /// The player whose turn it is concedes.
+    ///
+    /// # Returns
+    /// Returns the conceded games
+    pub async fn expire_tic_tac_toe_games(
+        &self,
+        expire_after: Duration,
+    ) -> anyhow::Result<Vec<TicTacToeInactiveGame>> {
+        let expire_after =
+            i64::try_from(expire_after.as_secs()).context("expire time is too long")?;
+        self.access_db(move |db| {
+            let txn = db.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let ret = txn
+                .prepare_cached(GET_EXPIRED_TIC_TAC_TOE_GAMES_SQL)?
+                .query_map([expire_after], TicTacToeInactiveGame::from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            for inactive_game in ret.iter() {
+                concede_tic_tac_toe_game(
+                    &txn,
+                    inactive_game.id,
+                    inactive_game.guild_id,
+                    inactive_game.game,
+                    inactive_game.game.get_team_turn(),
+                )?;
+            }
+
+            txn.commit().context("failed to commit").map(|_| ret)
+        })
+        .await?
+    }
+
+    /// Get the most recent finished Tic-Tac-Toe games of a user in the current server
+    pub async fn get_tic_tac_toe_history(
+        &self,
+        guild_id: MaybeGuildString,
+        player: UserId,
+    ) -> anyhow::Result<Vec<TicTacToeHistoryGame>> {
+        self.access_db(move |db| {
+            let ret = db
+                .prepare_cached(GET_TIC_TAC_TOE_HISTORY_SQL)?
+                .query_map(
+                    named_params! {
+                        ":guild_id": guild_id,
+                        ":player": i64::from(player),
+                    },
+                    TicTacToeHistoryGame::from_row,
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(ret)
+        })
+        .await?
+    }
+
+    /// Get a finished Tic-Tac-Toe game of the current server by id
+    pub async fn get_tic_tac_toe_history_game(
+        &self,
+        guild_id: MaybeGuildString,
+        id: i64,
+    ) -> anyhow::Result<Option<TicTacToeHistoryGame>> {
+        self.access_db(move |db| {
+            let ret = db
+                .prepare_cached(GET_TIC_TAC_TOE_HISTORY_GAME_SQL)?
+                .query_row(params![id, guild_id], TicTacToeHistoryGame::from_row)
+                .optional()?;
+
+            Ok(ret)
+        })
+        .await?
+    }
+
+    /// Set the Tic-Tac-Toe board theme of a server.
+    ///
+    /// # Returns
+    /// Returns the old theme
+    pub async fn set_tic_tac_toe_theme(
+        &self,
+        guild_id: GuildId,
+        theme: TicTacToeTheme,
+    ) -> anyhow::Result<TicTacToeTheme> {
+        let guild_id = MaybeGuildString::from(Some(guild_id));
+        self.access_db(move |db| {
+            let txn = db.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let old_theme: TicTacToeTheme = txn
+                .prepare_cached(GET_TIC_TAC_TOE_THEME_SQL)?
+                .query_row([guild_id], |row| row.get(0))
+                .optional()?
+                .unwrap_or_default();
+            txn.prepare_cached(SET_TIC_TAC_TOE_THEME_SQL)?
+                .execute(params![guild_id, theme])?;
+
+            txn.commit()
+                .context("failed to set tic-tac-toe theme")
+                .map(|_| old_theme)
+        })
+        .await?
+    }
+
+    /// Get the Tic-Tac-Toe board theme of a server.
+    ///
+    /// DMs always use the default theme.
+    pub async fn get_tic_tac_toe_theme(
+        &self,
+        guild_id: Option<GuildId>,
+    ) -> anyhow::Result<TicTacToeTheme> {
+        let guild_id = match guild_id {
+            Some(guild_id) => MaybeGuildString::from(Some(guild_id)),
+            None => return Ok(TicTacToeTheme::default()),
+        };
+
+        self.access_db(move |db| {
+            db.prepare_cached(GET_TIC_TAC_TOE_THEME_SQL)?
+                .query_row([guild_id], |row| row.get(0))
+                .optional()
+                .context("failed to read database")
+                .map(Option::<TicTacToeTheme>::unwrap_or_default)
+        })
+        .await?
+    }
 }
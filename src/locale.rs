@@ -0,0 +1,60 @@
+use once_cell::sync::Lazy;
+use pikadick_slash_framework::Translations;
+
+/// Translation files, by Discord locale
+const LOCALE_FILES: &[(&str, &str)] = &[
+    ("en-US", include_str!("../assets/locales/en-US.toml")),
+    ("de", include_str!("../assets/locales/de.toml")),
+    ("es-ES", include_str!("../assets/locales/es-ES.toml")),
+];
+
+/// The translations of user-facing strings.
+///
+/// Nested tables in translation files are flattened into dot-separated keys,
+/// like "commands.ping.description".
+pub static TRANSLATIONS: Lazy<Translations> = Lazy::new(|| {
+    let mut translations = Translations::new();
+    for (locale, file) in LOCALE_FILES.iter() {
+        let table: toml::Table = toml::from_str(file)
+            .unwrap_or_else(|error| panic!("invalid translation file for \"{locale}\": {error}"));
+
+        let mut strings = Vec::new();
+        flatten_table(&table, None, &mut strings);
+        translations.add_locale(locale, strings);
+    }
+    translations
+});
+
+/// Flatten a table of strings into dot-separated keys
+fn flatten_table(table: &toml::Table, prefix: Option<&str>, strings: &mut Vec<(String, String)>) {
+    for (key, value) in table.iter() {
+        let key = match prefix {
+            Some(prefix) => format!("{prefix}.{key}"),
+            None => key.clone(),
+        };
+
+        match value {
+            toml::Value::String(value) => strings.push((key, value.clone())),
+            toml::Value::Table(table) => flatten_table(table, Some(key.as_str()), strings),
+            _ => panic!("translation \"{key}\" is not a string"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pikadick_slash_framework::DEFAULT_LOCALE;
+
+    #[test]
+    fn translations_load() {
+        assert_eq!(
+            TRANSLATIONS.get(DEFAULT_LOCALE, "commands.ping.response"),
+            "pong"
+        );
+        assert_eq!(
+            TRANSLATIONS.get("es-ES", "commands.ping.description"),
+            "Responde con pong"
+        );
+    }
+}
@@ -39,17 +39,22 @@ pub mod client_data;
 pub mod commands;
 pub mod config;
 pub mod database;
+pub mod locale;
 pub mod logger;
 pub mod setup;
 pub mod util;
 
 use crate::{
     cli_options::CliOptions,
-    client_data::ClientData,
+    client_data::{
+        ClientData,
+        SharedData,
+    },
     commands::*,
     config::{
         ActivityKind,
         Config,
+        DEFAULT_BUCKETS,
     },
     database::{
         model::TikTokEmbedFlags,
@@ -160,7 +165,11 @@ impl EventHandler for Handler {
 
         // TODO: Consider shutting down the bot. It might be possible to use old data though.
         if let Err(error) = slash_framework
-            .register(ctx.clone(), config.test_guild)
+            .register(
+                ctx.clone(),
+                config.test_guild,
+                config.slash_framework.dry_run,
+            )
             .await
             .context("failed to register slash commands")
         {
@@ -359,6 +368,7 @@ async fn help(
     urban,
     xkcd,
     tic_tac_toe,
+    connect_four,
     iqdb,
     reddit,
     leave,
@@ -496,20 +506,60 @@ async fn process_dispatch_error_future<'fut>(
     };
 }
 
-/// Set up a serenity client
-async fn setup_client(config: Arc<Config>) -> anyhow::Result<Client> {
+/// Set up a serenity client.
+///
+/// The shared data is added to the slash framework as state.
+async fn setup_client(config: Arc<Config>, shared_data: &SharedData) -> anyhow::Result<Client> {
     // Setup slash framework
-    let slash_framework = pikadick_slash_framework::FrameworkBuilder::new()
+    let mut slash_framework_builder = pikadick_slash_framework::FrameworkBuilder::new();
+    for (name, bucket) in config.slash_framework.all_buckets() {
+        let bucket = bucket
+            .build()
+            .with_context(|| format!("invalid slash framework bucket \"{name}\""))?;
+        slash_framework_builder.bucket(name, bucket);
+    }
+    let slash_framework = slash_framework_builder
+        .state(shared_data.nekos_client.clone())
+        .state(shared_data.r6tracker_client.clone())
+        .state(shared_data.rule34_client.clone())
+        .state(shared_data.reddit_embed_data.clone())
+        .state(shared_data.tic_tac_toe_data.clone())
+        .state(shared_data.iqdb_client.clone())
+        .state(shared_data.sauce_nao_client.clone())
+        .state(shared_data.tiktok_data.clone())
+        .state(shared_data.db.clone())
         .check(self::checks::enabled::create_slash_check)
         .help_command(create_slash_help_command()?)
+        .command(iqdb::create_slash_command()?)
         .command(nekos::create_slash_command()?)
         .command(ping::create_slash_command()?)
         .command(r6stats::create_slash_command()?)
         .command(r6tracker::create_slash_command()?)
+        .command(reddit_embed::create_slash_command()?)
         .command(rule34::create_slash_command()?)
+        .command(shift::create_slash_command()?)
         .command(tiktok_embed::create_slash_command()?)
         .command(chat::create_slash_command()?)
         .command(yodaspeak::create_slash_command()?)
+        .command(uwuify::create_slash_command()?)
+        .command(zalgo::create_slash_command()?)
+        .command(tic_tac_toe::create_slash_command()?)
+        .component_check(self::checks::enabled::create_component_check)
+        .component_handler(create_help_page_component_handler()?)
+        .component_handler(rule34::create_reroll_component_handler()?)
+        .component_handler(tic_tac_toe::create_move_component_handler()?)
+        .modal_check(self::checks::enabled::create_modal_check)
+        .modal_handler(chat::create_modal_handler()?)
+        .modal_handler(uwuify::create_modal_handler()?)
+        .modal_handler(zalgo::create_modal_handler()?)
+        .modal_handler(reddit_embed::create_settings_modal_handler()?)
+        .modal_handler(tiktok_embed::create_settings_modal_handler()?)
+        .context_menu_check(self::checks::enabled::create_context_menu_check)
+        .context_menu_command(sauce_nao::create_find_source_context_menu_command()?)
+        .context_menu_command(uwuify::create_context_menu_command()?)
+        .context_menu_command(yodaspeak::create_context_menu_command()?)
+        .context_menu_command(embed_links::create_context_menu_command()?)
+        .context_menu_command(tic_tac_toe::create_stats_context_menu_command()?)
         .build()?;
 
     // Create second prefix that is uppercase so we are case-insensitive
@@ -523,23 +573,13 @@ async fn setup_client(config: Arc<Config>) -> anyhow::Result<Client> {
         .case_insensitivity(true);
     let framework = StandardFramework::new();
     framework.configure(framework_config);
+    let mut framework = framework.help(&HELP).group(&GENERAL_GROUP);
+    for (name, delay) in DEFAULT_BUCKETS.iter() {
+        framework = framework
+            .bucket(*name, BucketBuilder::new_channel().delay(*delay))
+            .await;
+    }
     let framework = framework
-        .help(&HELP)
-        .group(&GENERAL_GROUP)
-        .bucket("r6stats", BucketBuilder::new_channel().delay(7))
-        .await
-        .bucket("r6tracker", BucketBuilder::new_channel().delay(7))
-        .await
-        .bucket("system", BucketBuilder::new_channel().delay(30))
-        .await
-        .bucket("quizizz", BucketBuilder::new_channel().delay(10))
-        .await
-        .bucket("insta-dl", BucketBuilder::new_channel().delay(10))
-        .await
-        .bucket("ttt-board", BucketBuilder::new_channel().delay(1))
-        .await
-        .bucket("default", BucketBuilder::new_channel().delay(1))
-        .await
         .before(before_handler)
         .after(after_handler)
         .unrecognised_command(unrecognised_command_handler)
@@ -712,17 +752,36 @@ fn real_main(setup_data: SetupData) -> anyhow::Result<()> {
 async fn async_main(config: Arc<Config>, database: Database) -> anyhow::Result<()> {
     // TODO: See if it is possible to start serenity without a network
     info!("setting up client...");
-    let mut client = setup_client(config.clone())
+    let shared_data = SharedData::init(&config, database.clone())
+        .await
+        .context("shared data initialization failed")?;
+    let mut client = setup_client(config.clone(), &shared_data)
         .await
         .context("failed to set up client")?;
 
-    let client_data = ClientData::init(client.shard_manager.clone(), config, database.clone())
-        .await
-        .context("client data initialization failed")?;
+    let mut client_data = ClientData::init(
+        client.shard_manager.clone(),
+        client.http.clone(),
+        config,
+        shared_data,
+    )
+    .await
+    .context("client data initialization failed")?;
 
     // Add all post-init client data changes here
     {
-        client_data.enabled_check_data.add_groups(&[&GENERAL_GROUP]);
+        let data_lock = client.data.read().await;
+        let slash_framework = data_lock
+            .get::<SlashFrameworkKey>()
+            .expect("missing slash framework");
+
+        client_data
+            .enabled_check_data
+            .add_groups(&[&GENERAL_GROUP])
+            .context("failed to add prefix commands to the enabled check")?;
+        client_data
+            .enabled_check_data
+            .add_slash_framework(slash_framework);
     }
 
     {
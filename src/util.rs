@@ -1,4 +1,5 @@
 mod ascii_table;
+pub mod elo;
 mod encoder_task;
 mod loading_reaction;
 mod timed_cache;
@@ -28,3 +29,15 @@ pub fn extract_urls(text: &str) -> impl Iterator<Item = Url> + '_ {
         .find_iter(text)
         .filter_map(|url_match| Url::parse(url_match.as_str()).ok())
 }
+
+/// Parse a yes or no answer from a settings form.
+///
+/// # Returns
+/// Returns `None` if the answer is not recognized.
+pub fn parse_yes_no(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "yes" | "y" | "true" | "enable" | "on" => Some(true),
+        "no" | "n" | "false" | "disable" | "off" => Some(false),
+        _ => None,
+    }
+}
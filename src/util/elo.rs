@@ -0,0 +1,34 @@
+/// The max rating change of a single game
+const K_FACTOR: f64 = 32.0;
+
+/// Get the expected score of a player against an opponent, from 0.0 to 1.0.
+pub fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10.0_f64.powf((opponent_rating - rating) / 400.0))
+}
+
+/// Get the new ratings of two players after a game.
+///
+/// `score` is the score of the first player: 1.0 for a win, 0.5 for a tie, and 0.0 for a loss.
+pub fn update_ratings(rating: f64, opponent_rating: f64, score: f64) -> (f64, f64) {
+    let change = K_FACTOR * (score - expected_score(rating, opponent_rating));
+    (rating + change, opponent_rating - change)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INITIAL_RATING: f64 = 1500.0;
+
+    #[test]
+    fn update_ratings_is_zero_sum() {
+        let (winner, loser) = update_ratings(INITIAL_RATING, INITIAL_RATING, 1.0);
+        assert_eq!(winner, INITIAL_RATING + 16.0);
+        assert_eq!(loser, INITIAL_RATING - 16.0);
+
+        let (underdog, favorite) = update_ratings(1400.0, 1800.0, 0.5);
+        assert!(underdog > 1400.0);
+        assert!(favorite < 1800.0);
+        assert!(((underdog + favorite) - 3200.0).abs() < 1e-9);
+    }
+}
@@ -66,6 +66,19 @@ where
             .map(|v| v.value().clone())
     }
 
+    /// Get all fresh entries
+    pub fn get_all_fresh(&self) -> Vec<(K, Arc<TimedCacheEntry<V>>)>
+    where
+        K: Clone,
+    {
+        self.0
+            .cache
+            .iter()
+            .filter(|entry| entry.is_fresh(self.0.expiry_time))
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
     /// Insert a K/V
     pub fn insert(&self, key: K, value: V) {
         self.0.cache.insert(